@@ -15,7 +15,7 @@ use bevy::{
 };
 use bevy_ecs_tilemap::prelude::*;
 use simulation::common::{
-    element::{Air, Element},
+    element::{Air, Element, FoodKind, Hardness},
     grid::{Grid, GridElements},
     position::Position,
     Zone,
@@ -72,7 +72,14 @@ pub fn spawn_element_tilemap(
 /// This *only* handles the initial rendering of the Element sprite. Updates are handled by other systems.
 pub fn on_spawn_element<Z: Zone + Copy>(
     mut element_query: Query<
-        (&Position, &Element, Entity, &Z),
+        (
+            &Position,
+            &Element,
+            Option<&Hardness>,
+            Option<&FoodKind>,
+            Entity,
+            &Z,
+        ),
         (Added<Element>, With<Z>, Without<Air>),
     >,
     grid_query: Query<&Grid, With<Z>>,
@@ -99,7 +106,9 @@ pub fn on_spawn_element<Z: Zone + Copy>(
         None => panic!("Expected ElementExposureMap to exist whenever grid is visible"),
     };
 
-    for (&element_position, &element, element_model_entity, zone) in element_query.iter_mut() {
+    for (&element_position, &element, hardness, food_kind, element_model_entity, zone) in
+        element_query.iter_mut()
+    {
         let element_exposure = element_exposure_map.0.get(&element_model_entity).unwrap();
 
         spawn_element_sprite(
@@ -107,6 +116,8 @@ pub fn on_spawn_element<Z: Zone + Copy>(
             element,
             element_position,
             *element_exposure,
+            hardness,
+            food_kind,
             zone.clone(),
             &grid,
             &mut commands,
@@ -120,7 +131,17 @@ pub fn on_spawn_element<Z: Zone + Copy>(
 /// Thus, when switching back to Nest, all Elements need to be redrawn once. Their underlying models
 /// have not been changed or added, though, so a separate spawn system is needed.
 pub fn spawn_elements<Z: Zone + Copy>(
-    mut element_query: Query<(&Position, &Element, Entity, &Z), (With<Z>, Without<Air>)>,
+    mut element_query: Query<
+        (
+            &Position,
+            &Element,
+            Option<&Hardness>,
+            Option<&FoodKind>,
+            Entity,
+            &Z,
+        ),
+        (With<Z>, Without<Air>),
+    >,
     grid_query: Query<&Grid, With<Z>>,
     mut commands: Commands,
     mut tilemap_query: Query<(Entity, &mut TileStorage), With<ElementTilemap>>,
@@ -129,7 +150,9 @@ pub fn spawn_elements<Z: Zone + Copy>(
 ) {
     let grid = grid_query.single();
 
-    for (&element_position, &element, element_model_entity, &zone) in element_query.iter_mut() {
+    for (&element_position, &element, hardness, food_kind, element_model_entity, &zone) in
+        element_query.iter_mut()
+    {
         let element_exposure = element_exposure_map.0.get(&element_model_entity).unwrap();
 
         spawn_element_sprite(
@@ -137,6 +160,8 @@ pub fn spawn_elements<Z: Zone + Copy>(
             element,
             element_position,
             *element_exposure,
+            hardness,
+            food_kind,
             zone,
             &grid,
             &mut commands,
@@ -193,7 +218,7 @@ pub fn on_update_element_position<Z: Zone>(
 
 pub fn process_element_exposure_changed_events<Z: Zone>(
     mut element_exposure_changed_events: ResMut<Events<ElementExposureChangedEvent>>,
-    element_query: Query<&Element, (With<Z>, Without<Air>)>,
+    element_query: Query<(&Element, Option<&FoodKind>), (With<Z>, Without<Air>)>,
     element_exposure_map: Option<Res<ElementExposureMap>>,
     mut commands: Commands,
     model_view_entity_map: Res<ModelViewEntityMap>,
@@ -218,9 +243,10 @@ pub fn process_element_exposure_changed_events<Z: Zone>(
 
     for event in element_exposure_changed_events.drain() {
         let element_model_entity = event.0;
-        let element = element_query.get(element_model_entity).unwrap();
+        let (element, food_kind) = element_query.get(element_model_entity).unwrap();
         let element_exposure = element_exposure_map.0.get(&element_model_entity).unwrap();
-        let texture_index = TileTextureIndex(get_element_index(*element_exposure, *element) as u32);
+        let texture_index =
+            TileTextureIndex(get_element_index(*element_exposure, *element, food_kind) as u32);
 
         let element_view_entity = match model_view_entity_map.get(&element_model_entity) {
             Some(&element_view_entity) => element_view_entity,
@@ -232,6 +258,38 @@ pub fn process_element_exposure_changed_events<Z: Zone>(
     }
 }
 
+/// When an Element is dug, and its Hardness reduced, lighten its sprite to show it's been weakened.
+pub fn on_update_element_hardness<Z: Zone>(
+    element_query: Query<(Entity, &Hardness), (Changed<Hardness>, With<Z>)>,
+    grid_query: Query<&Grid, With<Z>>,
+    mut commands: Commands,
+    model_view_entity_map: Res<ModelViewEntityMap>,
+    visible_grid: Res<VisibleGrid>,
+) {
+    let visible_grid_entity = match visible_grid.0 {
+        Some(visible_grid_entity) => visible_grid_entity,
+        None => return,
+    };
+
+    // Early exit when grid isn't visible because there's no view to update.
+    // Exit, rather than skipping system run, to prevent change detection from becoming backlogged.
+    if grid_query.get(visible_grid_entity).is_err() {
+        return;
+    }
+
+    for (element_model_entity, hardness) in element_query.iter() {
+        let element_view_entity = match model_view_entity_map.get(&element_model_entity) {
+            Some(&element_view_entity) => element_view_entity,
+            // It's OK to fail to find here because view might not have spawned yet. View will spawn with correct details.
+            None => continue,
+        };
+
+        commands
+            .entity(element_view_entity)
+            .insert(get_element_color(Some(hardness)));
+    }
+}
+
 pub fn insert_element_exposure_map<Z: Zone>(
     elements_query: Query<(Entity, &Position), (Without<Air>, With<Z>)>,
     mut commands: Commands,
@@ -283,6 +341,8 @@ fn spawn_element_sprite<Z: Zone>(
     element: Element,
     element_position: Position,
     element_exposure: ElementExposure,
+    hardness: Option<&Hardness>,
+    food_kind: Option<&FoodKind>,
     zone: Z,
     grid: &Grid,
     commands: &mut Commands,
@@ -297,7 +357,10 @@ fn spawn_element_sprite<Z: Zone>(
         TileBundle {
             position: tile_pos,
             tilemap_id: TilemapId(tilemap_entity),
-            texture_index: TileTextureIndex(get_element_index(element_exposure, element) as u32),
+            texture_index: TileTextureIndex(
+                get_element_index(element_exposure, element, food_kind) as u32,
+            ),
+            color: get_element_color(hardness),
             ..default()
         },
     );
@@ -307,6 +370,14 @@ fn spawn_element_sprite<Z: Zone>(
     tile_storage.set(&tile_pos, element_view_entity);
 }
 
+/// Harder elements are drawn darker so that strata are visible.
+fn get_element_color(hardness: Option<&Hardness>) -> TileColor {
+    let hardness = hardness.map_or(0, |hardness| hardness.0);
+    let brightness = (1.0 - 0.15 * hardness as f32).max(0.4);
+
+    TileColor(Color::srgb(brightness, brightness, brightness))
+}
+
 // TODO: Feel like it would be more clear to run this by relying on RemovedComponents + Changed and excluding Air.
 /// Eagerly calculate which sides of a given Element are exposed to Air.
 /// Run against all elements changing position - this supports recalculating on Element removal by responding to Air being added.
@@ -1,11 +1,14 @@
 use bevy::{asset::LoadState, prelude::*};
 
-use simulation::common::element::Element;
+use simulation::common::element::{Element, FoodKind};
 
 use crate::common::{LoadProgress, RenderingLoadProgress};
 
 use super::ElementExposure;
 
+/// One column per element, and per kind of food, in sprite_sheet.png.
+const SPRITE_SHEET_COLUMNS: usize = 9;
+
 #[derive(Resource)]
 pub struct ElementSpriteSheetHandle(pub Handle<Image>);
 
@@ -34,7 +37,13 @@ pub fn check_element_sprite_sheet_loaded(
     let load_state = asset_server.load_state(&element_sprite_sheet_handle.0);
 
     if load_state == LoadState::Loaded {
-        let texture_atlas = TextureAtlasLayout::from_grid(UVec2::splat(128), 3, 16, None, None);
+        let texture_atlas = TextureAtlasLayout::from_grid(
+            UVec2::splat(128),
+            SPRITE_SHEET_COLUMNS as u32,
+            16,
+            None,
+            None,
+        );
 
         commands.insert_resource(ElementTextureAtlasLayoutHandle(
             texture_atlases.add(texture_atlas),
@@ -63,7 +72,12 @@ pub fn check_element_sprite_sheet_loaded(
 // 13 - south/west/north exposed
 // 14 - west/north/east exposed
 // 15 - all exposed
-pub fn get_element_index(exposure: ElementExposure, element: Element) -> usize {
+// Each kind of food has its own column. Food without a FoodKind is seeds.
+pub fn get_element_index(
+    exposure: ElementExposure,
+    element: Element,
+    food_kind: Option<&FoodKind>,
+) -> usize {
     let row_index = match exposure {
         ElementExposure {
             north: false,
@@ -165,10 +179,18 @@ pub fn get_element_index(exposure: ElementExposure, element: Element) -> usize {
 
     let column_index = match element {
         Element::Dirt => 0,
-        Element::Food => 1,
+        Element::Food => match food_kind.copied().unwrap_or_default() {
+            FoodKind::Seed => 1,
+            FoodKind::Sugar => 7,
+            FoodKind::Protein => 8,
+        },
         Element::Sand => 2,
+        Element::Rock => 3,
+        Element::Water => 4,
+        Element::Mud => 5,
+        Element::Mold => 6,
         _ => panic!("Element {:?} not supported", element),
     };
 
-    row_index * 3 + column_index
+    row_index * SPRITE_SHEET_COLUMNS + column_index
 }
@@ -267,9 +267,9 @@ pub fn check_load_progress(
         return;
     }
 
-    if simulation_load_progress.save_file == LoadProgress::Failure {
-        next_app_state.set(AppState::MainMenu);
-    } else if simulation_load_progress.save_file == LoadProgress::Success {
-        next_app_state.set(AppState::FinishSetup);
+    match simulation_load_progress.save_file {
+        LoadProgress::Failure(_) => next_app_state.set(AppState::MainMenu),
+        LoadProgress::Success => next_app_state.set(AppState::FinishSetup),
+        _ => {}
     }
 }
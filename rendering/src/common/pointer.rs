@@ -7,6 +7,7 @@ use bevy::{prelude::*, window::PrimaryWindow};
 use simulation::{
     common::{
         ant::Ant,
+        element::FoodKind,
         grid::{Grid, GridElements},
         position::Position,
         Zone,
@@ -19,12 +20,16 @@ pub enum PointerAction {
     #[default]
     Select,
     DespawnElement,
-    SpawnFood,
+    SpawnFood(FoodKind),
     SpawnDirt,
     SpawnSand,
+    SpawnRock,
+    SpawnWater,
     KillAnt,
     SpawnWorkerAnt,
     DespawnWorkerAnt,
+    LandQueen,
+    DesignateGranary,
 }
 
 pub fn pointer_action_to_simulation_event<Z: Zone>(
@@ -37,14 +42,22 @@ pub fn pointer_action_to_simulation_event<Z: Zone>(
             panic!("Cannot convert PointerAction::Select to ExternalSimulationEvent")
         }
         PointerAction::DespawnElement => ExternalSimulationEvent::DespawnElement(position, zone),
-        PointerAction::SpawnFood => ExternalSimulationEvent::SpawnFood(position, zone),
+        PointerAction::SpawnFood(food_kind) => {
+            ExternalSimulationEvent::SpawnFood(position, food_kind, zone)
+        }
         PointerAction::SpawnDirt => ExternalSimulationEvent::SpawnDirt(position, zone),
         PointerAction::SpawnSand => ExternalSimulationEvent::SpawnSand(position, zone),
+        PointerAction::SpawnRock => ExternalSimulationEvent::SpawnRock(position, zone),
+        PointerAction::SpawnWater => ExternalSimulationEvent::SpawnWater(position, zone),
         PointerAction::KillAnt => ExternalSimulationEvent::KillAnt(position, zone),
         PointerAction::SpawnWorkerAnt => ExternalSimulationEvent::SpawnWorkerAnt(position, zone),
         PointerAction::DespawnWorkerAnt => {
             ExternalSimulationEvent::DespawnWorkerAnt(position, zone)
         }
+        PointerAction::LandQueen => ExternalSimulationEvent::LandQueen(position, zone),
+        PointerAction::DesignateGranary => {
+            ExternalSimulationEvent::DesignateGranary(position, zone)
+        }
     }
 }
 
@@ -12,7 +12,7 @@ use bevy::{color, prelude::*};
 use simulation::{
     common::{
         ant::{Ant, AntColor, AntInventory, AntName, Dead},
-        element::Element,
+        element::{Element, FoodKind},
         grid::Grid,
         position::Position,
     },
@@ -52,7 +52,7 @@ pub fn on_added_ant_at_crater(
         Added<AtCrater>,
     >,
     asset_server: Res<AssetServer>,
-    elements_query: Query<&Element>,
+    elements_query: Query<(&Element, Option<&FoodKind>)>,
     crater_query: Query<&Grid, With<Crater>>,
     element_texture_handle: Res<ElementSpriteSheetHandle>,
     element_texture_atlas_layout_handle: Res<ElementTextureAtlasLayoutHandle>,
@@ -107,7 +107,7 @@ pub fn spawn_ants(
     >,
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    elements_query: Query<&Element>,
+    elements_query: Query<(&Element, Option<&FoodKind>)>,
     crater_query: Query<&Grid, With<Crater>>,
     element_texture_handle: Res<ElementSpriteSheetHandle>,
     element_texture_atlas_layout_handle: Res<ElementTextureAtlasLayoutHandle>,
@@ -152,7 +152,7 @@ pub fn on_update_ant_inventory(
     mut commands: Commands,
     ant_model_query: Query<(Entity, Ref<AntInventory>, &CraterOrientation), With<AtCrater>>,
     mut ant_view_query: Query<&mut AntSpriteContainer>,
-    elements_query: Query<&Element>,
+    elements_query: Query<(&Element, Option<&FoodKind>)>,
     element_texture_handle: Res<ElementSpriteSheetHandle>,
     element_texture_atlas_layout_handle: Res<ElementTextureAtlasLayoutHandle>,
     model_view_entity_map: Res<ModelViewEntityMap>,
@@ -360,7 +360,7 @@ fn spawn_ant_sprite(
     inventory: &AntInventory,
     dead: Option<&Dead>,
     asset_server: &Res<AssetServer>,
-    elements_query: &Query<&Element>,
+    elements_query: &Query<(&Element, Option<&FoodKind>)>,
     grid: &Grid,
     element_texture_handle: &Res<ElementSpriteSheetHandle>,
     element_texture_atlas_layout_handle: &Res<ElementTextureAtlasLayoutHandle>,
@@ -451,12 +451,12 @@ fn spawn_ant_sprite(
 
 fn get_inventory_item_bundle(
     element_entity: Entity,
-    elements_query: &Query<&Element>,
+    elements_query: &Query<(&Element, Option<&FoodKind>)>,
     element_texture_handle: &Res<ElementSpriteSheetHandle>,
     element_texture_atlas_layout_handle: &Res<ElementTextureAtlasLayoutHandle>,
     orientation: &CraterOrientation,
 ) -> (SpriteBundle, TextureAtlas) {
-    let element = elements_query.get(element_entity).unwrap();
+    let (element, food_kind) = elements_query.get(element_entity).unwrap();
 
     let element_exposure = ElementExposure {
         north: true,
@@ -477,7 +477,7 @@ fn get_inventory_item_bundle(
         },
         TextureAtlas {
             layout: element_texture_atlas_layout_handle.0.clone(),
-            index: get_element_index(element_exposure, *element),
+            index: get_element_index(element_exposure, *element, food_kind),
         },
     )
 }
@@ -1,13 +1,13 @@
 use super::AntSpriteContainer;
 use crate::common::{visible_grid::VisibleGrid, ModelViewEntityMap};
 use bevy::prelude::*;
-use bevy_turborand::{DelegatedRng, GlobalRng};
+use bevy_turborand::{DelegatedRng, RngComponent};
 use simulation::{
-    common::{ant::hunger::AntAteFoodEvent, grid::Grid},
-    nest_simulation::{
-        ant::sleep::Asleep,
-        nest::{AtNest, Nest},
+    common::{
+        ant::{hunger::AntAteFoodEvent, task::AntTask},
+        grid::Grid,
     },
+    nest_simulation::nest::{AtNest, Nest},
     settings::Settings,
     story_time::{StoryTime, DEFAULT_TICKS_PER_SECOND},
 };
@@ -157,15 +157,21 @@ pub fn on_ant_ate_food(
 }
 
 /// Periodically show sleeping emotes above sleeping ants.
+/// Emotes are cosmetic so they draw from their own source of randomness. Drawing from GlobalRng would make the simulation's
+/// outcome depend on how often this system happens to run.
 pub fn ants_sleep_emote(
-    ants_query: Query<Entity, (With<Asleep>, With<AtNest>)>,
+    ants_query: Query<(Entity, &AntTask), With<AtNest>>,
     mut commands: Commands,
-    mut rng: ResMut<GlobalRng>,
+    mut rng: Local<RngComponent>,
     settings: Res<Settings>,
     model_view_entity_map: Res<ModelViewEntityMap>,
     story_time: Res<StoryTime>,
 ) {
-    for ant_model_entity in ants_query.iter() {
+    for (ant_model_entity, ant_task) in ants_query.iter() {
+        if !ant_task.is_asleep() {
+            continue;
+        }
+
         let ant_view_entity = match model_view_entity_map.get(&ant_model_entity) {
             Some(ant_view_entity) => *ant_view_entity,
             None => continue,
@@ -183,13 +189,16 @@ pub fn ants_sleep_emote(
 
 /// Despawn sleeping emote when ants wake up. It's possible they weren't showing an emote, so need to be a little careful.
 pub fn on_ant_wake_up(
-    // TODO: Maybe this should be event-driven and have an AntWakeUpEvent instead? That way this won't run when ants are getting destroyed.
-    mut removed: RemovedComponents<Asleep>,
+    ants_query: Query<(Entity, &AntTask), (Changed<AntTask>, With<AtNest>)>,
     model_view_entity_map: Res<ModelViewEntityMap>,
     mut commands: Commands,
     ant_view_query: Query<Option<&Emote>, With<AntSpriteContainer>>,
 ) {
-    for ant_model_entity in removed.read() {
+    for (ant_model_entity, ant_task) in ants_query.iter() {
+        if ant_task.is_asleep() {
+            continue;
+        }
+
         if let Some(&ant_view_entity) = model_view_entity_map.get(&ant_model_entity) {
             let existing_emote = ant_view_query.get(ant_view_entity).unwrap();
 
@@ -1,5 +1,6 @@
 pub mod emote;
 
+use super::brood::get_brood_image;
 use crate::common::{
     element::{
         sprite_sheet::{
@@ -13,13 +14,14 @@ use crate::common::{
 use bevy::{color, prelude::*};
 use simulation::{
     common::{
-        ant::{Ant, AntColor, AntInventory, AntName, AntRole, Dead},
-        element::Element,
+        ant::{Ant, AntColor, AntInventory, AntName, AntRole, Dead, InventoryItem},
+        element::{Element, FoodKind},
         grid::Grid,
         position::Position,
     },
     nest_simulation::{
-        ant::{NestFacing, NestOrientation},
+        ant::{nuptial_flight::Winged, NestFacing, NestOrientation},
+        brood::BroodStage,
         nest::AtNest,
     },
 };
@@ -34,10 +36,12 @@ pub struct AntSpriteContainer {
     pub label_entity: Entity,
     pub inventory_item_entity: Option<Entity>,
     pub emote_entity: Option<Entity>,
+    pub wings_entity: Option<Entity>,
 }
 
 /// When an ant model gains AtNest render an associated ant sprite.
 /// This handles the initial rendering of the ant sprite on load as well as when ants transition between zones.
+/// Corpses regain their sprite, too, when they're set down after being carried.
 pub fn on_added_ant_at_nest(
     mut commands: Commands,
     ants_query: Query<
@@ -50,11 +54,13 @@ pub fn on_added_ant_at_nest(
             &AntRole,
             &AntInventory,
             Option<&Dead>,
+            Option<&Winged>,
         ),
-        Added<AtNest>,
+        (Or<(Added<AtNest>, Added<Position>)>, With<AtNest>),
     >,
     asset_server: Res<AssetServer>,
-    elements_query: Query<&Element>,
+    elements_query: Query<(&Element, Option<&FoodKind>)>,
+    brood_query: Query<&BroodStage>,
     grid_query: Query<&Grid, With<AtNest>>,
     element_texture_handle: Res<ElementSpriteSheetHandle>,
     element_texture_atlas_layout_handle: Res<ElementTextureAtlasLayoutHandle>,
@@ -72,9 +78,15 @@ pub fn on_added_ant_at_nest(
 
     let grid = grid_query.single();
 
-    for (ant_model_entity, position, color, orientation, name, role, inventory, dead) in
+    for (ant_model_entity, position, color, orientation, name, role, inventory, dead, winged) in
         ants_query.iter()
     {
+        // Simulation can tick multiple times before rendering, so a corpse could be picked up and set down again
+        // before its old view was despawned.
+        if let Some(ant_view_entity) = model_view_entity_map.remove(&ant_model_entity) {
+            commands.entity(ant_view_entity).despawn_recursive();
+        }
+
         spawn_ant_sprite(
             &mut commands,
             ant_model_entity,
@@ -85,8 +97,10 @@ pub fn on_added_ant_at_nest(
             role,
             inventory,
             dead,
+            winged,
             &asset_server,
             &elements_query,
+            &brood_query,
             &grid,
             &element_texture_handle,
             &element_texture_atlas_layout_handle,
@@ -109,12 +123,14 @@ pub fn spawn_ants(
             &AntRole,
             &AntInventory,
             Option<&Dead>,
+            Option<&Winged>,
         ),
         With<AtNest>,
     >,
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    elements_query: Query<&Element>,
+    elements_query: Query<(&Element, Option<&FoodKind>)>,
+    brood_query: Query<&BroodStage>,
     grid_query: Query<&Grid, With<AtNest>>,
     element_texture_handle: Res<ElementSpriteSheetHandle>,
     element_texture_atlas_layout_handle: Res<ElementTextureAtlasLayoutHandle>,
@@ -122,7 +138,7 @@ pub fn spawn_ants(
 ) {
     let grid = grid_query.single();
 
-    for (ant_model_entity, position, color, orientation, name, role, inventory, dead) in
+    for (ant_model_entity, position, color, orientation, name, role, inventory, dead, winged) in
         ant_model_query.iter()
     {
         spawn_ant_sprite(
@@ -135,8 +151,10 @@ pub fn spawn_ants(
             role,
             inventory,
             dead,
+            winged,
             &asset_server,
             &elements_query,
+            &brood_query,
             &grid,
             &element_texture_handle,
             &element_texture_atlas_layout_handle,
@@ -160,7 +178,9 @@ pub fn on_update_ant_inventory(
     mut commands: Commands,
     ant_model_query: Query<(Entity, Ref<AntInventory>), With<AtNest>>,
     mut ant_view_query: Query<&mut AntSpriteContainer>,
-    elements_query: Query<&Element>,
+    asset_server: Res<AssetServer>,
+    elements_query: Query<(&Element, Option<&FoodKind>)>,
+    brood_query: Query<&BroodStage>,
     element_texture_handle: Res<ElementSpriteSheetHandle>,
     element_texture_atlas_layout_handle: Res<ElementTextureAtlasLayoutHandle>,
     model_view_entity_map: Res<ModelViewEntityMap>,
@@ -195,16 +215,17 @@ pub fn on_update_ant_inventory(
                 ant_sprite_container.inventory_item_entity = None;
             }
 
-            if let Some(element_entity) = inventory.0 {
-                let inventory_item_bundle = get_inventory_item_bundle(
-                    element_entity,
+            if let Some(item_entity) = inventory.0 {
+                let ant_inventory_item_entity = spawn_inventory_item(
+                    &mut commands,
+                    item_entity,
+                    &asset_server,
                     &elements_query,
+                    &brood_query,
                     &element_texture_handle,
                     &element_texture_atlas_layout_handle,
                 );
 
-                let ant_inventory_item_entity = commands.spawn(inventory_item_bundle).id();
-
                 commands
                     .entity(ant_sprite_container.sprite_entity)
                     .push_children(&[ant_inventory_item_entity]);
@@ -351,6 +372,43 @@ pub fn on_added_ant_dead(
     }
 }
 
+/// When corpses are picked up they're drawn in their carrier's mouth, instead, so their own view is despawned.
+pub fn on_ant_corpse_picked_up(
+    ant_model_query: Query<Entity, (With<Ant>, Added<InventoryItem>, With<AtNest>)>,
+    mut commands: Commands,
+    mut model_view_entity_map: ResMut<ModelViewEntityMap>,
+) {
+    for ant_model_entity in ant_model_query.iter() {
+        if let Some(ant_view_entity) = model_view_entity_map.remove(&ant_model_entity) {
+            commands.entity(ant_view_entity).despawn_recursive();
+        }
+    }
+}
+
+/// When a queen lands from her nuptial flight she sheds her wings, so they're no longer drawn.
+pub fn on_removed_ant_winged(
+    mut removed: RemovedComponents<Winged>,
+    mut ant_view_query: Query<&mut AntSpriteContainer>,
+    mut commands: Commands,
+    model_view_entity_map: Res<ModelViewEntityMap>,
+) {
+    for ant_model_entity in removed.read() {
+        let Some(&ant_view_entity) = model_view_entity_map.get(&ant_model_entity) else {
+            continue;
+        };
+
+        let Ok(mut ant_sprite_container) = ant_view_query.get_mut(ant_view_entity) else {
+            continue;
+        };
+
+        if let Some(wings_entity) = ant_sprite_container.wings_entity {
+            // Surprisingly, Bevy doesn't fix parent/child relationship when despawning children, so do it manually.
+            commands.entity(wings_entity).remove_parent().despawn();
+            ant_sprite_container.wings_entity = None;
+        }
+    }
+}
+
 /// Remove resources, etc.
 pub fn cleanup_ants() {}
 
@@ -366,8 +424,10 @@ fn spawn_ant_sprite(
     role: &AntRole,
     inventory: &AntInventory,
     dead: Option<&Dead>,
+    winged: Option<&Winged>,
     asset_server: &Res<AssetServer>,
-    elements_query: &Query<&Element>,
+    elements_query: &Query<(&Element, Option<&FoodKind>)>,
+    brood_query: &Query<&BroodStage>,
     grid: &Grid,
     element_texture_handle: &Res<ElementSpriteSheetHandle>,
     element_texture_atlas_layout_handle: &Res<ElementTextureAtlasLayoutHandle>,
@@ -385,6 +445,33 @@ fn spawn_ant_sprite(
         ("images/ant.png", color.0)
     };
 
+    let inventory_item_entity = inventory.0.map(|item_entity| {
+        spawn_inventory_item(
+            commands,
+            item_entity,
+            asset_server,
+            elements_query,
+            brood_query,
+            element_texture_handle,
+            element_texture_atlas_layout_handle,
+        )
+    });
+
+    // Wings are drawn behind the queen's body so they look to be folded over her back.
+    let wings_entity = winged.map(|_| {
+        commands
+            .spawn(SpriteBundle {
+                texture: asset_server.load("images/wings.png"),
+                transform: Transform::from_xyz(-0.1, 0.3, -0.1),
+                sprite: Sprite {
+                    custom_size: Some(Vec2::splat(1.0)),
+                    ..default()
+                },
+                ..default()
+            })
+            .id()
+    });
+
     // Spawn AntSprite with child inventory/hat
     let mut ant_sprite = commands.spawn((SpriteBundle {
         texture: asset_server.load(sprite_image),
@@ -402,20 +489,15 @@ fn spawn_ant_sprite(
         ..default()
     },));
 
-    let mut inventory_item_entity = None;
+    if let Some(inventory_item_entity) = inventory_item_entity {
+        ant_sprite.push_children(&[inventory_item_entity]);
+    }
 
-    ant_sprite.with_children(|parent: &mut ChildBuilder<'_>| {
-        if let Some(element_entity) = inventory.0 {
-            let bundle = get_inventory_item_bundle(
-                element_entity,
-                &elements_query,
-                &element_texture_handle,
-                &element_texture_atlas_layout_handle,
-            );
-
-            inventory_item_entity = Some(parent.spawn(bundle).id());
-        }
+    if let Some(wings_entity) = wings_entity {
+        ant_sprite.push_children(&[wings_entity]);
+    }
 
+    ant_sprite.with_children(|parent: &mut ChildBuilder<'_>| {
         if *role == AntRole::Queen {
             parent.spawn(SpriteBundle {
                 texture: asset_server.load("images/crown.png"),
@@ -457,6 +539,7 @@ fn spawn_ant_sprite(
                 label_entity,
                 inventory_item_entity,
                 emote_entity: None,
+                wings_entity,
             },
             translation_offset,
             SpatialBundle {
@@ -474,13 +557,62 @@ fn spawn_ant_sprite(
     model_view_entity_map.insert(model_entity, ant_view_entity);
 }
 
+/// Ants carry either an element, brood, or a corpse. Brood and corpses aren't part of the element sprite sheet so they're drawn from their own image.
+fn spawn_inventory_item(
+    commands: &mut Commands,
+    item_entity: Entity,
+    asset_server: &Res<AssetServer>,
+    elements_query: &Query<(&Element, Option<&FoodKind>)>,
+    brood_query: &Query<&BroodStage>,
+    element_texture_handle: &Res<ElementSpriteSheetHandle>,
+    element_texture_atlas_layout_handle: &Res<ElementTextureAtlasLayoutHandle>,
+) -> Entity {
+    if let Ok(&stage) = brood_query.get(item_entity) {
+        return commands
+            .spawn(SpriteBundle {
+                transform: Transform::from_xyz(1.0, 0.25, 1.0),
+                sprite: Sprite {
+                    custom_size: Some(Vec2::splat(1.0)),
+                    ..default()
+                },
+                texture: asset_server.load(get_brood_image(stage)),
+                ..default()
+            })
+            .id();
+    }
+
+    if !elements_query.contains(item_entity) {
+        return commands
+            .spawn(SpriteBundle {
+                transform: Transform::from_xyz(1.0, 0.25, 1.0),
+                sprite: Sprite {
+                    color: Color::Srgba(color::palettes::basic::GRAY),
+                    custom_size: Some(Vec2::splat(1.0)),
+                    ..default()
+                },
+                texture: asset_server.load("images/ant_dead.png"),
+                ..default()
+            })
+            .id();
+    }
+
+    let inventory_item_bundle = get_inventory_item_bundle(
+        item_entity,
+        elements_query,
+        element_texture_handle,
+        element_texture_atlas_layout_handle,
+    );
+
+    commands.spawn(inventory_item_bundle).id()
+}
+
 fn get_inventory_item_bundle(
     element_entity: Entity,
-    elements_query: &Query<&Element>,
+    elements_query: &Query<(&Element, Option<&FoodKind>)>,
     element_texture_handle: &Res<ElementSpriteSheetHandle>,
     element_texture_atlas_layout_handle: &Res<ElementTextureAtlasLayoutHandle>,
 ) -> (SpriteBundle, TextureAtlas) {
-    let element = elements_query.get(element_entity).unwrap();
+    let (element, food_kind) = elements_query.get(element_entity).unwrap();
 
     let element_exposure = ElementExposure {
         north: true,
@@ -501,7 +633,7 @@ fn get_inventory_item_bundle(
         },
         TextureAtlas {
             layout: element_texture_atlas_layout_handle.0.clone(),
-            index: get_element_index(element_exposure, *element),
+            index: get_element_index(element_exposure, *element, food_kind),
         },
     )
 }
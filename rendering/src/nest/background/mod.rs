@@ -7,6 +7,7 @@ use simulation::{
     common::{grid::Grid, position::Position},
     nest_simulation::nest::{AtNest, Nest},
     story_time::{StoryTime, TimeInfo},
+    weather::Weather,
 };
 
 #[derive(Component)]
@@ -39,6 +40,7 @@ fn get_sky_gradient_color(
     current_decimal_hours: f32,
     sunrise_decimal_hours: f32,
     sunset_decimal_hours: f32,
+    is_raining: bool,
 ) -> (Color, Color) {
     let midnight = Color::srgba(0.0471, 0.0353, 0.0392, 1.0);
     let predawn = Color::srgba(0.0471, 0.0353, 0.0392, 1.0);
@@ -91,6 +93,16 @@ fn get_sky_gradient_color(
         south_color = interpolate_color(start_color, end_color, 1.0 - (1.0 - progress).powf(3.0));
     }
 
+    if is_raining {
+        // Rain clouds wash out the sky, but the time of day still shows through them.
+        let overcast = Color::srgba(0.42, 0.44, 0.47, 1.0);
+
+        return (
+            interpolate_color(north_color, overcast, 0.6),
+            interpolate_color(south_color, overcast, 0.6),
+        );
+    }
+
     (north_color, south_color)
 }
 
@@ -100,6 +112,7 @@ pub fn update_sky_background(
     nest_query: Query<&Nest>,
     visible_grid: Res<VisibleGrid>,
     story_time: Res<StoryTime>,
+    weather: Res<Weather>,
 ) {
     let visible_grid_entity = match visible_grid.0 {
         Some(visible_grid_entity) => visible_grid_entity,
@@ -113,8 +126,9 @@ pub fn update_sky_background(
     let nest = nest_query.single();
     let time_info = story_time.as_time_info();
 
-    // Update the sky's colors once a minute of elapsed *story time* not real-world time.
-    if time_info.days() == last_update_sky.0.days()
+    // Update the sky's colors once a minute of elapsed *story time* not real-world time, or whenever the weather changes.
+    if !weather.is_changed()
+        && time_info.days() == last_update_sky.0.days()
         && time_info.hours() == last_update_sky.0.hours()
         // Check if difference between time_info and last_update_sky minutes is 1
         && (time_info.minutes() - last_update_sky.0.minutes()).abs() < 1
@@ -130,6 +144,7 @@ pub fn update_sky_background(
         current_decimal_hours,
         sunrise_decimal_hours,
         sunset_decimal_hours,
+        weather.is_raining(),
     );
     for (mut tile_color, position) in sky_tile_query.iter_mut() {
         let t_y: f32 = position.y as f32 / nest.surface_level() as f32;
@@ -176,6 +191,7 @@ pub fn spawn_background(
     nest_query: Query<(&Grid, &Nest)>,
     mut tilemap_query: Query<(Entity, &mut TileStorage), With<BackgroundTilemap>>,
     story_time: Res<StoryTime>,
+    weather: Res<Weather>,
 ) {
     let (grid, nest) = nest_query.single();
     let air_height = nest.surface_level() + 1;
@@ -190,6 +206,7 @@ pub fn spawn_background(
         current_decimal_hours,
         sunrise_decimal_hours,
         sunset_decimal_hours,
+        weather.is_raining(),
     );
 
     let width = grid.width();
@@ -0,0 +1,194 @@
+use crate::common::{
+    visible_grid::{grid_to_world_position, VisibleGrid},
+    ModelViewEntityMap,
+};
+use bevy::prelude::*;
+use simulation::{
+    common::{ant::InventoryItem, grid::Grid, position::Position},
+    nest_simulation::{
+        brood::{Brood, BroodStage},
+        nest::AtNest,
+    },
+};
+use std::ops::Add;
+
+#[derive(Component)]
+pub struct BroodSprite;
+
+// Brood go above elements, but below ants, so ants are seen tending to them rather than hidden behind them.
+const BROOD_TRANSLATION_OFFSET: Vec3 = Vec3::new(0.0, 0.0, 0.5);
+
+/// When a brood model gains a Position, because it was just laid or because it was set down, render it.
+pub fn on_spawn_brood(
+    brood_model_query: Query<
+        (Entity, &Position, &BroodStage),
+        (With<Brood>, Added<Position>, With<AtNest>),
+    >,
+    mut brood_view_query: Query<&mut Transform, With<BroodSprite>>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    grid_query: Query<&Grid, With<AtNest>>,
+    mut model_view_entity_map: ResMut<ModelViewEntityMap>,
+    visible_grid: Res<VisibleGrid>,
+) {
+    let visible_grid_entity = match visible_grid.0 {
+        Some(visible_grid_entity) => visible_grid_entity,
+        None => return,
+    };
+
+    let grid = match grid_query.get(visible_grid_entity) {
+        Ok(grid) => grid,
+        Err(_) => return,
+    };
+
+    for (brood_model_entity, position, stage) in brood_model_query.iter() {
+        // Simulation can tick multiple times before rendering, so brood could be set down, picked up, and set down again.
+        if let Some(&brood_view_entity) = model_view_entity_map.get(&brood_model_entity) {
+            if let Ok(mut transform) = brood_view_query.get_mut(brood_view_entity) {
+                transform.translation =
+                    grid_to_world_position(grid, *position).add(BROOD_TRANSLATION_OFFSET);
+            }
+
+            continue;
+        }
+
+        spawn_brood_sprite(
+            brood_model_entity,
+            position,
+            stage,
+            grid,
+            &asset_server,
+            &mut commands,
+            &mut model_view_entity_map,
+        );
+    }
+}
+
+/// When user switches to a different scene (Nest->Crater) all Nest views are despawned.
+/// Thus, when switching back to Nest, all brood need to be redrawn once.
+pub fn spawn_brood(
+    brood_model_query: Query<(Entity, &Position, &BroodStage), (With<Brood>, With<AtNest>)>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    grid_query: Query<&Grid, With<AtNest>>,
+    mut model_view_entity_map: ResMut<ModelViewEntityMap>,
+) {
+    let grid = grid_query.single();
+
+    for (brood_model_entity, position, stage) in brood_model_query.iter() {
+        spawn_brood_sprite(
+            brood_model_entity,
+            position,
+            stage,
+            grid,
+            &asset_server,
+            &mut commands,
+            &mut model_view_entity_map,
+        );
+    }
+}
+
+/// When brood are picked up they're drawn in their carrier's mouth, instead, so their own view is despawned.
+pub fn on_brood_picked_up(
+    brood_model_query: Query<Entity, (With<Brood>, Added<InventoryItem>, With<AtNest>)>,
+    mut commands: Commands,
+    mut model_view_entity_map: ResMut<ModelViewEntityMap>,
+) {
+    for brood_model_entity in brood_model_query.iter() {
+        if let Some(brood_view_entity) = model_view_entity_map.remove(&brood_model_entity) {
+            commands.entity(brood_view_entity).despawn_recursive();
+        }
+    }
+}
+
+/// When brood fall, their view needs to follow.
+pub fn on_update_brood_position(
+    brood_model_query: Query<(Entity, Ref<Position>), (With<Brood>, With<AtNest>)>,
+    mut brood_view_query: Query<&mut Transform, With<BroodSprite>>,
+    grid_query: Query<&Grid, With<AtNest>>,
+    model_view_entity_map: Res<ModelViewEntityMap>,
+    visible_grid: Res<VisibleGrid>,
+) {
+    let visible_grid_entity = match visible_grid.0 {
+        Some(visible_grid_entity) => visible_grid_entity,
+        None => return,
+    };
+
+    let grid = match grid_query.get(visible_grid_entity) {
+        Ok(grid) => grid,
+        Err(_) => return,
+    };
+
+    for (brood_model_entity, position) in brood_model_query.iter() {
+        if position.is_added() || !position.is_changed() {
+            continue;
+        }
+
+        if let Some(&brood_view_entity) = model_view_entity_map.get(&brood_model_entity) {
+            if let Ok(mut transform) = brood_view_query.get_mut(brood_view_entity) {
+                transform.translation =
+                    grid_to_world_position(grid, *position).add(BROOD_TRANSLATION_OFFSET);
+            }
+        }
+    }
+}
+
+/// When brood develop into their next stage, their sprite changes to match.
+pub fn on_update_brood_stage(
+    brood_model_query: Query<(Entity, Ref<BroodStage>), (With<Brood>, With<AtNest>)>,
+    mut brood_view_query: Query<&mut Handle<Image>, With<BroodSprite>>,
+    asset_server: Res<AssetServer>,
+    model_view_entity_map: Res<ModelViewEntityMap>,
+) {
+    for (brood_model_entity, stage) in brood_model_query.iter() {
+        if stage.is_added() || !stage.is_changed() {
+            continue;
+        }
+
+        if let Some(&brood_view_entity) = model_view_entity_map.get(&brood_model_entity) {
+            if let Ok(mut image_handle) = brood_view_query.get_mut(brood_view_entity) {
+                *image_handle = asset_server.load(get_brood_image(*stage));
+            }
+        }
+    }
+}
+
+pub fn get_brood_image(stage: BroodStage) -> &'static str {
+    match stage {
+        BroodStage::Egg => "images/egg.png",
+        BroodStage::Larva => "images/larva.png",
+        BroodStage::Pupa => "images/pupa.png",
+    }
+}
+
+/// Non-System Helper Functions:
+
+fn spawn_brood_sprite(
+    brood_model_entity: Entity,
+    position: &Position,
+    stage: &BroodStage,
+    grid: &Grid,
+    asset_server: &Res<AssetServer>,
+    commands: &mut Commands,
+    model_view_entity_map: &mut ResMut<ModelViewEntityMap>,
+) {
+    let brood_view_entity = commands
+        .spawn((
+            BroodSprite,
+            SpriteBundle {
+                texture: asset_server.load(get_brood_image(*stage)),
+                sprite: Sprite {
+                    custom_size: Some(Vec2::splat(1.0)),
+                    ..default()
+                },
+                transform: Transform::from_translation(
+                    grid_to_world_position(grid, *position).add(BROOD_TRANSLATION_OFFSET),
+                ),
+                ..default()
+            },
+            AtNest,
+        ))
+        .id();
+
+    model_view_entity_map.insert(brood_model_entity, brood_view_entity);
+}
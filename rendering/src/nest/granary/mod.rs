@@ -0,0 +1,99 @@
+use crate::common::{
+    visible_grid::{grid_to_world_position, VisibleGrid},
+    ModelViewEntityMap,
+};
+use bevy::prelude::*;
+use simulation::{
+    common::{grid::Grid, position::Position},
+    nest_simulation::{
+        granary::{Granary, GRANARY_RADIUS},
+        nest::AtNest,
+    },
+};
+use std::ops::Add;
+
+#[derive(Component)]
+pub struct GranarySprite;
+
+// Granaries are drawn over elements, but beneath brood and ants, as a faint tint marking out the chamber.
+const GRANARY_TRANSLATION_OFFSET: Vec3 = Vec3::new(0.0, 0.0, 0.25);
+
+/// When a granary is designated, render it.
+pub fn on_spawn_granary(
+    granary_model_query: Query<(Entity, &Position), (Added<Granary>, With<AtNest>)>,
+    mut commands: Commands,
+    grid_query: Query<&Grid, With<AtNest>>,
+    mut model_view_entity_map: ResMut<ModelViewEntityMap>,
+    visible_grid: Res<VisibleGrid>,
+) {
+    let visible_grid_entity = match visible_grid.0 {
+        Some(visible_grid_entity) => visible_grid_entity,
+        None => return,
+    };
+
+    let grid = match grid_query.get(visible_grid_entity) {
+        Ok(grid) => grid,
+        Err(_) => return,
+    };
+
+    for (granary_model_entity, position) in granary_model_query.iter() {
+        spawn_granary_sprite(
+            granary_model_entity,
+            position,
+            grid,
+            &mut commands,
+            &mut model_view_entity_map,
+        );
+    }
+}
+
+/// When user switches to a different scene (Nest->Crater) all Nest views are despawned.
+/// Thus, when switching back to Nest, all granaries need to be redrawn once.
+pub fn spawn_granaries(
+    granary_model_query: Query<(Entity, &Position), (With<Granary>, With<AtNest>)>,
+    mut commands: Commands,
+    grid_query: Query<&Grid, With<AtNest>>,
+    mut model_view_entity_map: ResMut<ModelViewEntityMap>,
+) {
+    let grid = grid_query.single();
+
+    for (granary_model_entity, position) in granary_model_query.iter() {
+        spawn_granary_sprite(
+            granary_model_entity,
+            position,
+            grid,
+            &mut commands,
+            &mut model_view_entity_map,
+        );
+    }
+}
+
+/// Non-System Helper Functions:
+
+fn spawn_granary_sprite(
+    granary_model_entity: Entity,
+    position: &Position,
+    grid: &Grid,
+    commands: &mut Commands,
+    model_view_entity_map: &mut ResMut<ModelViewEntityMap>,
+) {
+    let granary_view_entity = commands
+        .spawn((
+            GranarySprite,
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::srgba(0.96, 0.69, 0.13, 0.25),
+                    custom_size: Some(Vec2::splat((GRANARY_RADIUS * 2 + 1) as f32)),
+                    ..default()
+                },
+                transform: Transform::from_translation(
+                    grid_to_world_position(grid, *position).add(GRANARY_TRANSLATION_OFFSET),
+                ),
+                ..default()
+            },
+            AtNest,
+        ))
+        .id();
+
+    model_view_entity_map.insert(granary_model_entity, granary_view_entity);
+}
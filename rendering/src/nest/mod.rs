@@ -1,5 +1,7 @@
 pub mod ant;
 pub mod background;
+pub mod brood;
+pub mod granary;
 pub mod pheromone;
 
 use crate::common::{on_model_removed_zone, visible_grid::set_visible_grid_state_nest};
@@ -11,21 +13,28 @@ use self::{
             ants_sleep_emote, despawn_expired_emotes, on_added_ant_emote, on_ant_ate_food,
             on_ant_wake_up, on_removed_ant_emote,
         },
-        on_added_ant_at_nest, on_added_ant_dead, on_update_ant_color, on_update_ant_inventory,
-        on_update_ant_orientation, on_update_ant_position, spawn_ants,
+        on_added_ant_at_nest, on_added_ant_dead, on_ant_corpse_picked_up, on_removed_ant_winged,
+        on_update_ant_color, on_update_ant_inventory, on_update_ant_orientation,
+        on_update_ant_position, spawn_ants,
     },
     background::{
         cleanup_background, initialize_background_resources, spawn_background,
         spawn_background_tilemap, update_sky_background, Background, BackgroundTilemap,
     },
+    brood::{
+        on_brood_picked_up, on_spawn_brood, on_update_brood_position, on_update_brood_stage,
+        spawn_brood,
+    },
+    granary::{on_spawn_granary, spawn_granaries},
     pheromone::{on_spawn_pheromone, on_update_pheromone_strength, spawn_pheromones},
 };
 use super::common::{
     despawn_view, despawn_view_by_model,
     element::{
         cleanup_elements, initialize_element_resources, insert_element_exposure_map,
-        on_spawn_element, on_update_element_position, process_element_exposure_changed_events,
-        remove_element_exposure_map, spawn_element_tilemap, spawn_elements,
+        on_spawn_element, on_update_element_hardness, on_update_element_position,
+        process_element_exposure_changed_events, remove_element_exposure_map,
+        spawn_element_tilemap, spawn_elements,
         sprite_sheet::{check_element_sprite_sheet_loaded, start_load_element_sprite_sheet},
         update_element_exposure_map, ElementTilemap,
     },
@@ -36,7 +45,11 @@ use bevy::prelude::*;
 use simulation::{
     app_state::AppState,
     common::{ant::Ant, element::Element, pheromone::Pheromone},
-    nest_simulation::nest::{AtNest, Nest},
+    nest_simulation::{
+        brood::Brood,
+        granary::Granary,
+        nest::{AtNest, Nest},
+    },
     CleanupSet, FinishSetupSet,
 };
 
@@ -72,17 +85,30 @@ impl Plugin for NestRenderingPlugin {
                     .chain(),
                 (
                     // Spawn
-                    (on_spawn_element::<AtNest>, on_spawn_pheromone),
+                    (
+                        on_spawn_element::<AtNest>,
+                        on_spawn_pheromone,
+                        on_spawn_brood,
+                        on_spawn_granary,
+                    ),
                     // Despawn
                     (
                         on_despawn::<Ant, AtNest>,
                         on_despawn::<Element, AtNest>,
                         on_despawn::<Pheromone, AtNest>,
+                        on_despawn::<Brood, AtNest>,
+                        on_despawn::<Granary, AtNest>,
+                        on_brood_picked_up,
+                        on_ant_corpse_picked_up,
                     ),
                     // Added
                     (on_added_ant_emote, on_added_ant_dead, on_added_ant_at_nest),
                     // Removed
-                    (on_removed_ant_emote, on_model_removed_zone::<AtNest>),
+                    (
+                        on_removed_ant_emote,
+                        on_removed_ant_winged,
+                        on_model_removed_zone::<AtNest>,
+                    ),
                     // Updated
                     (
                         on_update_ant_position,
@@ -90,7 +116,10 @@ impl Plugin for NestRenderingPlugin {
                         on_update_ant_color,
                         on_update_ant_inventory,
                         on_update_element_position::<AtNest>,
+                        on_update_element_hardness::<AtNest>,
                         on_update_pheromone_strength,
+                        on_update_brood_position,
+                        on_update_brood_stage,
                     ),
                     // Misc
                     (
@@ -127,6 +156,8 @@ impl Plugin for NestRenderingPlugin {
                     spawn_ants,
                     spawn_elements::<AtNest>,
                     spawn_pheromones,
+                    spawn_brood,
+                    spawn_granaries,
                     mark_nest_visible,
                 ),
             )
@@ -143,6 +174,8 @@ impl Plugin for NestRenderingPlugin {
                 despawn_view_by_model::<Element, AtNest>,
                 despawn_view::<ElementTilemap>,
                 despawn_view_by_model::<Pheromone, AtNest>,
+                despawn_view_by_model::<Brood, AtNest>,
+                despawn_view_by_model::<Granary, AtNest>,
                 remove_element_exposure_map,
                 mark_nest_hidden,
             )
@@ -160,6 +193,8 @@ impl Plugin for NestRenderingPlugin {
                 despawn_view_by_model::<Element, AtNest>,
                 cleanup_elements,
                 despawn_view_by_model::<Pheromone, AtNest>,
+                despawn_view_by_model::<Brood, AtNest>,
+                despawn_view_by_model::<Granary, AtNest>,
             )
                 .in_set(CleanupSet::BeforeSimulationCleanup),
         );
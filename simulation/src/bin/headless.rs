@@ -0,0 +1,457 @@
+//! Runs the simulation without a window, UI, or rendering so that `Settings` (especially `Probabilities`) can be tuned offline.
+//!
+//! Usage: cargo run -p simulation --bin headless -- [--ticks N] [--seed N] [--config FILE] [--replay FILE] [--record FILE]
+//!     [--hash-log FILE] [--compare FILE] [--desync-check] [--map FILE] [--crater-map FILE] [setting=value ...]
+//!
+//! Settings are addressed by their field path, e.g. `initial_ant_worker_count=20` or `probabilities.random_drop=0.01`.
+//! A config file contains one `setting=value` per line. Blank lines and lines starting with '#' are ignored.
+//! `--seed N` is shorthand for `rng_seed=N`. A random seed is chosen, and printed, if none is given so any run can be repeated.
+//! `ticks` may also be given in a config file. Command line arguments are applied after the config file.
+//! `--replay FILE` recreates a recorded colony: its Settings, and its tick count unless `--ticks` follows, are taken from
+//! the replay and its external events are re-injected at the ticks they were recorded. A warning is printed if the run
+//! ends before every recorded event is played, or carries on past the end of the recording.
+//! `--record FILE` writes the run's Settings and external events to a replay file once the run is over.
+//! `--map FILE` starts the colony from an authored PNG or ASCII map (see `simulation::scenario`) rather than a generated one.
+//! `--crater-map FILE` does the same for the crater. Replays don't include maps so pass the same maps when replaying.
+//!
+//! The world is hashed after every tick to check that a seeded run is reproducible:
+//! `--hash-log FILE` writes each tick's hash to a file. Run this before refactoring a system.
+//! `--compare FILE` compares each tick's hash against a file written by `--hash-log`. Run this after refactoring a system.
+//! `--desync-check` simulates the same colony twice, side by side, and compares their hashes every tick.
+//! If hashes differ then the first tick, and subsystem, to diverge is reported and the runner exits with a failure.
+//!
+//! NOTE: The runner never enters `AppState::Loading` and never initializes the save resources so it can't read or
+//! overwrite the user's saved colonies.
+
+use bevy::{
+    app::FixedMain, ecs::schedule::ScheduleLabel, prelude::*, reflect::GetPath,
+    state::app::StatesPlugin,
+};
+use bevy_turborand::prelude::*;
+use simulation::{
+    app_state::AppState,
+    common::{
+        ant::{Ant, AntRole, Dead},
+        element::Element,
+        position::Position,
+    },
+    crater_simulation::crater::AtCrater,
+    nest_simulation::nest::{AtNest, Nest},
+    replay::{Replay, ReplayPlayer, ReplayRecorder},
+    scenario::{Scenario, ScenarioMap},
+    settings::Settings,
+    story_time::StoryTime,
+    world_state_hash::{WorldStateDivergence, WorldStateHashLog},
+    SimulationPlugin,
+};
+use std::{fs, process::ExitCode, time::Instant};
+
+const DEFAULT_TICKS: usize = 10_000;
+
+struct HeadlessConfig {
+    ticks: usize,
+    settings: Settings,
+    replay: Option<Replay>,
+    scenario: Option<Scenario>,
+    record_path: Option<String>,
+    hash_log_path: Option<String>,
+    expected_hash_log: Option<WorldStateHashLog>,
+    is_desync_check: bool,
+}
+
+impl Default for HeadlessConfig {
+    fn default() -> Self {
+        Self {
+            ticks: DEFAULT_TICKS,
+            settings: Settings::default(),
+            replay: None,
+            scenario: None,
+            record_path: None,
+            hash_log_path: None,
+            expected_hash_log: None,
+            is_desync_check: false,
+        }
+    }
+}
+
+impl HeadlessConfig {
+    fn from_args(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut config = HeadlessConfig::default();
+
+        while let Some(arg) = args.next() {
+            let mut next_value = |flag: &str| {
+                args.next()
+                    .ok_or_else(|| format!("Missing value for {}", flag))
+            };
+
+            match arg.as_str() {
+                "--ticks" => config.apply("ticks", &next_value("--ticks")?)?,
+                "--seed" => config.apply("rng_seed", &next_value("--seed")?)?,
+                "--config" => {
+                    let path = next_value("--config")?;
+                    let contents = fs::read_to_string(&path)
+                        .map_err(|error| format!("Failed to read {}: {}", path, error))?;
+
+                    for line in contents.lines().map(str::trim) {
+                        if line.is_empty() || line.starts_with('#') {
+                            continue;
+                        }
+
+                        config.apply_assignment(line)?;
+                    }
+                }
+                "--replay" => {
+                    let path = next_value("--replay")?;
+                    let data = fs::read(&path)
+                        .map_err(|error| format!("Failed to read {}: {}", path, error))?;
+                    let replay = Replay::from_bytes(&data)?;
+
+                    config.settings = replay.settings;
+                    config.ticks = replay.elapsed_ticks.max(0) as usize;
+                    config.replay = Some(replay);
+                }
+                "--map" => {
+                    let scenario = Scenario::read(&next_value("--map")?)?;
+                    let crater_map = config.scenario.take().and_then(|scenario| scenario.crater);
+
+                    config.scenario = Some(Scenario {
+                        crater: scenario.crater.or(crater_map),
+                        ..scenario
+                    });
+                }
+                "--crater-map" => {
+                    let path = next_value("--crater-map")?;
+                    let data = fs::read(&path)
+                        .map_err(|error| format!("Failed to read {}: {}", path, error))?;
+
+                    let scenario = config.scenario.get_or_insert_with(Scenario::default);
+                    scenario.crater = Some(ScenarioMap::from_file(&path, &data)?);
+                    scenario.validate()?;
+                }
+                "--record" => config.record_path = Some(next_value("--record")?),
+                "--hash-log" => config.hash_log_path = Some(next_value("--hash-log")?),
+                "--compare" => {
+                    let path = next_value("--compare")?;
+                    let text = fs::read_to_string(&path)
+                        .map_err(|error| format!("Failed to read {}: {}", path, error))?;
+
+                    config.expected_hash_log = Some(WorldStateHashLog::from_text(&text)?);
+                }
+                "--desync-check" => config.is_desync_check = true,
+                _ => config.apply_assignment(&arg)?,
+            }
+        }
+
+        Ok(config)
+    }
+
+    fn is_hashing(&self) -> bool {
+        self.hash_log_path.is_some() || self.expected_hash_log.is_some() || self.is_desync_check
+    }
+
+    fn apply_assignment(&mut self, assignment: &str) -> Result<(), String> {
+        let (key, value) = assignment
+            .split_once('=')
+            .ok_or_else(|| format!("Expected setting=value but found {}", assignment))?;
+
+        self.apply(key.trim(), value.trim())
+    }
+
+    fn apply(&mut self, key: &str, value: &str) -> Result<(), String> {
+        let invalid_value = || format!("Invalid value for {}: {}", key, value);
+
+        match key {
+            "ticks" => self.ticks = value.parse().map_err(|_| invalid_value())?,
+            _ => {
+                let field = self
+                    .settings
+                    .reflect_path_mut(key)
+                    .map_err(|_| format!("Unknown setting {}", key))?;
+
+                if let Some(field) = field.downcast_mut::<f32>() {
+                    *field = value.parse().map_err(|_| invalid_value())?;
+                } else if let Some(field) = field.downcast_mut::<isize>() {
+                    *field = value.parse().map_err(|_| invalid_value())?;
+                } else if let Some(field) = field.downcast_mut::<u64>() {
+                    *field = value.parse().map_err(|_| invalid_value())?;
+                } else if let Some(field) = field.downcast_mut::<bool>() {
+                    *field = value.parse().map_err(|_| invalid_value())?;
+                } else {
+                    return Err(format!("Setting {} can't be configured headlessly", key));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+struct HeadlessSummary {
+    rng_seed: u64,
+    ticks: usize,
+    ants_alive: usize,
+    nest_food: usize,
+    crater_food: usize,
+    tunnel_area: usize,
+    is_queen_alive: bool,
+}
+
+impl HeadlessSummary {
+    fn new(world: &mut World, ticks: usize) -> Self {
+        let ants_alive = world
+            .query_filtered::<(), (With<Ant>, Without<Dead>)>()
+            .iter(world)
+            .count();
+
+        let is_queen_alive = world
+            .query_filtered::<&AntRole, (With<Ant>, Without<Dead>)>()
+            .iter(world)
+            .any(|ant_role| *ant_role == AntRole::Queen);
+
+        let nest_food = world
+            .query_filtered::<&Element, With<AtNest>>()
+            .iter(world)
+            .filter(|element| **element == Element::Food)
+            .count();
+
+        let crater_food = world
+            .query_filtered::<&Element, With<AtCrater>>()
+            .iter(world)
+            .filter(|element| **element == Element::Food)
+            .count();
+
+        let nest = *world.query::<&Nest>().single(world);
+        // Tunnels are the air which ants have dug out of the underground dirt.
+        let tunnel_area = world
+            .query_filtered::<(&Element, &Position), With<AtNest>>()
+            .iter(world)
+            .filter(|(element, position)| {
+                **element == Element::Air && nest.is_underground(position)
+            })
+            .count();
+
+        Self {
+            rng_seed: world.resource::<Settings>().rng_seed,
+            ticks,
+            ants_alive,
+            nest_food,
+            crater_food,
+            tunnel_area,
+            is_queen_alive,
+        }
+    }
+
+    fn print(&self) {
+        println!("Seed: {}", self.rng_seed);
+        println!("Ticks simulated: {}", self.ticks);
+        println!("Ants alive: {}", self.ants_alive);
+        println!(
+            "Food: {} (nest: {}, crater: {})",
+            self.nest_food + self.crater_food,
+            self.nest_food,
+            self.crater_food
+        );
+        println!("Tunnel area: {}", self.tunnel_area);
+        println!(
+            "Queen survived: {}",
+            if self.is_queen_alive { "yes" } else { "no" }
+        );
+    }
+}
+
+fn build_app(config: &HeadlessConfig) -> App {
+    let mut app = App::new();
+
+    // MinimalPlugins doesn't include state support which the simulation relies on.
+    app.add_plugins((MinimalPlugins, StatesPlugin));
+
+    // Use a shared, common source of randomness so that the simulation is deterministic.
+    // GlobalRng is reseeded from Settings when the sandbox is created.
+    app.add_plugins(RngPlugin::default());
+
+    // Starting in MainMenu skips loading the user's save. This must be inserted before SimulationPlugin
+    // initializes AppState because the first state to be inserted wins.
+    app.insert_state(AppState::MainMenu);
+    // Settings are only initialized, not inserted, when a new sandbox is started so these overrides are kept.
+    app.insert_resource(config.settings);
+
+    app.add_plugins(SimulationPlugin);
+
+    if let Some(replay) = &config.replay {
+        app.insert_resource(ReplayPlayer::new(replay));
+    }
+
+    if let Some(scenario) = &config.scenario {
+        app.insert_resource(scenario.clone());
+    }
+
+    if config.is_hashing() {
+        app.init_resource::<WorldStateHashLog>();
+    }
+
+    app.finish();
+    app.cleanup();
+
+    app
+}
+
+/// Mirror the main menu's "Sandbox Mode" button and step through setup until the story begins.
+fn start_new_sandbox(app: &mut App) {
+    // Run Startup and enter MainMenu.
+    app.update();
+
+    app.world_mut()
+        .resource_mut::<NextState<AppState>>()
+        .set(AppState::FinishSetup);
+
+    // Each setup state is entered on its own frame, as it would be when running normally, so change detection is
+    // cleared between setup and the first tick.
+    while *app.world().resource::<State<AppState>>().get()
+        != AppState::PostSetupClearChangeDetection
+    {
+        app.update();
+    }
+
+    // The story is entered without running a full frame because saving runs every frame while the story is told.
+    run_schedule(app, StateTransition);
+}
+
+fn run_schedule(app: &mut App, label: impl ScheduleLabel) {
+    app.world_mut().run_schedule(label);
+}
+
+/// Run a single simulation tick, skipping the real-time pacing of FixedUpdate, and everything else a frame would do.
+fn tick(app: &mut App) {
+    // First swaps event buffers so events, e.g. AntAteFoodEvent, don't accumulate forever.
+    run_schedule(app, First);
+    run_schedule(app, FixedMain);
+    run_schedule(app, StateTransition);
+    app.world_mut().clear_trackers();
+}
+
+fn is_story_over(app: &App) -> bool {
+    *app.world().resource::<State<AppState>>().get() == (AppState::TellStory { ended: true })
+}
+
+/// Compare the most recently hashed tick of two colonies which are being simulated side by side.
+fn find_latest_divergence(app: &App, expected_app: &App) -> Option<WorldStateDivergence> {
+    let (tick, actual) = app.world().resource::<WorldStateHashLog>().0.last()?;
+    let (_, expected) = expected_app
+        .world()
+        .resource::<WorldStateHashLog>()
+        .0
+        .last()?;
+
+    WorldStateDivergence::find(*tick, expected, actual)
+}
+
+/// A run only recreates a recording if it covers the same ticks. Stopping early drops recorded events and carrying on
+/// leaves the colony to its own devices, either of which is easy to do by accident with `--ticks`.
+fn warn_about_replay_coverage(app: &App, replay: &Replay) {
+    let elapsed_ticks = app.world().resource::<StoryTime>().elapsed_ticks();
+
+    if !app.world().resource::<ReplayPlayer>().is_finished() {
+        eprintln!(
+            "Warning: the run ended at tick {} before every event in the replay was played.",
+            elapsed_ticks
+        );
+    } else if elapsed_ticks > replay.elapsed_ticks {
+        eprintln!(
+            "Warning: the run outlasted the replay by {} ticks. No events were replayed after tick {}.",
+            elapsed_ticks - replay.elapsed_ticks,
+            replay.elapsed_ticks
+        );
+    }
+}
+
+fn main() -> ExitCode {
+    let config = match HeadlessConfig::from_args(std::env::args().skip(1)) {
+        Ok(config) => config,
+        Err(error) => {
+            eprintln!("{}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut app = build_app(&config);
+    start_new_sandbox(&mut app);
+
+    // The second colony is only simulated when checking for desyncs. It should always match the first.
+    let mut desync_check_app = config.is_desync_check.then(|| {
+        let mut app = build_app(&config);
+        start_new_sandbox(&mut app);
+        app
+    });
+
+    let start = Instant::now();
+    let mut ticks = 0;
+    let mut divergence = None;
+
+    while ticks < config.ticks && !is_story_over(&app) {
+        tick(&mut app);
+        ticks += 1;
+
+        if let Some(desync_check_app) = desync_check_app.as_mut() {
+            tick(desync_check_app);
+
+            divergence = find_latest_divergence(&app, desync_check_app);
+            if divergence.is_some() {
+                break;
+            }
+        }
+    }
+
+    let elapsed = start.elapsed();
+
+    if let Some(replay) = &config.replay {
+        warn_about_replay_coverage(&app, replay);
+    }
+
+    if let Some(record_path) = &config.record_path {
+        let elapsed_ticks = app.world().resource::<StoryTime>().elapsed_ticks();
+        let replay = app
+            .world()
+            .resource::<ReplayRecorder>()
+            .to_replay(elapsed_ticks);
+
+        if let Err(error) = fs::write(record_path, replay.to_bytes()) {
+            eprintln!("Failed to write {}: {}", record_path, error);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if let Some(hash_log_path) = &config.hash_log_path {
+        let hash_log = app.world().resource::<WorldStateHashLog>();
+
+        if let Err(error) = fs::write(hash_log_path, hash_log.to_text()) {
+            eprintln!("Failed to write {}: {}", hash_log_path, error);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if let Some(expected_hash_log) = &config.expected_hash_log {
+        divergence = divergence.or_else(|| {
+            app.world()
+                .resource::<WorldStateHashLog>()
+                .find_divergence(expected_hash_log)
+        });
+    }
+
+    HeadlessSummary::new(app.world_mut(), ticks).print();
+    println!(
+        "Real time: {:.2}s ({:.0} ticks/s)",
+        elapsed.as_secs_f64(),
+        ticks as f64 / elapsed.as_secs_f64().max(f64::EPSILON)
+    );
+
+    if let Some(divergence) = divergence {
+        println!("{}", divergence);
+        return ExitCode::FAILURE;
+    }
+
+    if config.is_desync_check || config.expected_hash_log.is_some() {
+        println!("No divergence");
+    }
+
+    ExitCode::SUCCESS
+}
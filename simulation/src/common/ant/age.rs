@@ -0,0 +1,151 @@
+use crate::{
+    common::{
+        ant::{death::CauseOfDeath, initiative::Initiative, AntRole, Dead},
+        Zone,
+    },
+    settings::Settings,
+    story_time::{DEFAULT_TICKS_PER_SECOND, SECONDS_PER_DAY},
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// The most ticks an ant's Initiative is delayed by, once it's on the verge of dying of old age.
+const MAX_ELDERLY_INITIATIVE_DELAY: f32 = 4.0;
+
+/// How long an ant has been alive. Ants slow down in their old age and die once their lifespan has run out.
+/// Lifespans are long enough that they're tracked in whole ticks, rather than as a percentage like Hunger, so no time is lost to rounding.
+#[derive(
+    Component, Debug, PartialEq, Eq, Copy, Clone, Serialize, Deserialize, Reflect, Default, Hash,
+)]
+#[reflect(Component)]
+pub struct Age {
+    ticks: isize,
+    max_ticks: isize,
+}
+
+impl Age {
+    pub fn new(max_time_seconds: isize) -> Self {
+        Self {
+            ticks: 0,
+            max_ticks: max_time_seconds * DEFAULT_TICKS_PER_SECOND,
+        }
+    }
+
+    /// Workers are short-lived, but the queen outlives many generations of them.
+    pub fn for_role(role: AntRole, settings: &Settings) -> Self {
+        match role {
+            AntRole::Worker => Self::new(settings.max_worker_age_time),
+            AntRole::Queen => Self::new(settings.max_queen_age_time),
+        }
+    }
+
+    /// How much of the ant's lifespan has passed, as a percentage.
+    pub fn value(&self) -> f32 {
+        self.ticks as f32 / self.max_ticks as f32 * 100.0
+    }
+
+    pub fn days(&self) -> f32 {
+        self.ticks as f32 / (SECONDS_PER_DAY * DEFAULT_TICKS_PER_SECOND) as f32
+    }
+
+    pub fn tick(&mut self) {
+        self.ticks = (self.ticks + 1).min(self.max_ticks);
+    }
+
+    pub fn is_elderly(&self) -> bool {
+        self.ticks >= self.max_ticks * 3 / 4
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.ticks >= self.max_ticks
+    }
+
+    /// How many extra ticks an ant waits for its Initiative to replenish. Ants don't slow down until they're elderly, and
+    /// then grow slower as they near the end of their lifespan.
+    pub fn get_initiative_delay(&self) -> isize {
+        if !self.is_elderly() {
+            return 0;
+        }
+
+        let elderly_ticks = self.max_ticks - self.max_ticks * 3 / 4;
+        let elderly_progress =
+            (self.ticks - self.max_ticks * 3 / 4) as f32 / elderly_ticks.max(1) as f32;
+
+        (elderly_progress * MAX_ELDERLY_INITIATIVE_DELAY).round() as isize
+    }
+}
+
+/// Ants grow older every tick, even while asleep, and die of old age once their lifespan has run out.
+pub fn ants_age<Z: Zone>(
+    mut ants_query: Query<(Entity, &mut Age), (Without<Dead>, With<Z>)>,
+    mut commands: Commands,
+) {
+    for (ant_entity, mut age) in ants_query.iter_mut() {
+        age.tick();
+
+        if age.is_expired() {
+            commands
+                .entity(ant_entity)
+                .insert((Dead, CauseOfDeath::OldAge))
+                .remove::<Initiative>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Age;
+    use crate::{
+        common::{
+            ant::{death::CauseOfDeath, initiative::Initiative, Dead},
+            position::Position,
+        },
+        test_support::TestSimulation,
+    };
+
+    // Confirm that an ant dies of old age once its lifespan has run out, and leaves a corpse behind.
+    #[test]
+    fn ant_dies_of_old_age() {
+        let mut simulation = TestSimulation::new(
+            "
+            .....
+            #####
+            #W..#
+            #####
+            ",
+        );
+        let ant_entity = simulation.nest_ant(Position::new(1, 2));
+        simulation
+            .world_mut()
+            .entity_mut(ant_entity)
+            .insert(Age::new(1));
+
+        simulation.tick(9);
+        assert!(simulation.world().get::<Dead>(ant_entity).is_none());
+
+        simulation.tick(1);
+        assert!(simulation.world().get::<Dead>(ant_entity).is_some());
+        assert_eq!(
+            simulation.world().get::<CauseOfDeath>(ant_entity),
+            Some(&CauseOfDeath::OldAge)
+        );
+        assert!(simulation.world().get::<Initiative>(ant_entity).is_none());
+    }
+
+    // Confirm that ants only slow down once they're elderly, and slow down the most at the very end of their lifespan.
+    #[test]
+    fn elderly_ants_slow_down() {
+        let mut age = Age::new(10);
+
+        for _ in 0..70 {
+            age.tick();
+        }
+        assert_eq!(age.get_initiative_delay(), 0);
+
+        for _ in 0..29 {
+            age.tick();
+        }
+        assert!(age.is_elderly());
+        assert_eq!(age.get_initiative_delay(), 4);
+    }
+}
@@ -0,0 +1,349 @@
+use crate::{
+    common::ant::{Ant, AntRole, Dead},
+    nest_simulation::brood::Brood,
+    settings::Settings,
+    story_time::DEFAULT_TICKS_PER_SECOND,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Workers specialize in the work they do. Young workers nurse the brood, then dig as they mature, and finally leave the nest to forage.
+/// Soldiers are held back from this progression to protect the colony.
+/// Queens don't have a caste.
+#[derive(
+    Component, Debug, PartialEq, Eq, Copy, Clone, Serialize, Deserialize, Reflect, Default, Hash,
+)]
+#[reflect(Component)]
+pub enum Caste {
+    #[default]
+    Nurse,
+    Digger,
+    Forager,
+    Soldier,
+}
+
+/// How strongly an ant is drawn to each kind of work. Weights scale the chance of an ant choosing the corresponding behavior.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct CasteWeights {
+    pub nursing: f32,
+    pub digging: f32,
+    pub foraging: f32,
+}
+
+impl Default for CasteWeights {
+    /// Ants without a caste, i.e. the queen, aren't specialized.
+    fn default() -> Self {
+        Self {
+            nursing: 1.0,
+            digging: 1.0,
+            foraging: 1.0,
+        }
+    }
+}
+
+impl Caste {
+    pub fn weights(&self) -> CasteWeights {
+        match self {
+            Caste::Nurse => CasteWeights {
+                nursing: 1.0,
+                digging: 0.25,
+                foraging: 0.1,
+            },
+            Caste::Digger => CasteWeights {
+                nursing: 0.25,
+                digging: 1.0,
+                foraging: 0.25,
+            },
+            Caste::Forager => CasteWeights {
+                nursing: 0.1,
+                digging: 0.25,
+                foraging: 1.0,
+            },
+            // TODO: Soldiers should guard the nest once there's something to guard it from.
+            Caste::Soldier => CasteWeights {
+                nursing: 0.0,
+                digging: 0.25,
+                foraging: 0.25,
+            },
+        }
+    }
+
+    /// Workers move on to the next caste as they age. Foragers and soldiers stay as they are.
+    pub fn next_by_age(&self) -> Self {
+        match self {
+            Caste::Nurse => Caste::Digger,
+            Caste::Digger => Caste::Forager,
+            Caste::Forager => Caste::Forager,
+            Caste::Soldier => Caste::Soldier,
+        }
+    }
+}
+
+/// How long an ant has served in its current caste.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Component)]
+pub struct CasteTenure {
+    value: f32,
+    max: f32,
+    rate: f32,
+}
+
+impl CasteTenure {
+    pub fn new(max_time_seconds: isize) -> Self {
+        let max = 100.0;
+        let rate = max / (max_time_seconds * DEFAULT_TICKS_PER_SECOND) as f32;
+
+        Self {
+            value: 0.0,
+            max,
+            rate,
+        }
+    }
+
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    pub fn tick(&mut self) {
+        self.value = (self.value + self.rate).min(self.max);
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.value >= self.max
+    }
+
+    pub fn reset(&mut self) {
+        self.value = 0.0;
+    }
+}
+
+/// A count of living workers in each caste.
+#[derive(Debug, Default)]
+struct CasteCensus {
+    nurses: usize,
+    soldiers: usize,
+    workers: usize,
+}
+
+impl CasteCensus {
+    fn add(&mut self, caste: Caste) {
+        self.workers += 1;
+
+        match caste {
+            Caste::Nurse => self.nurses += 1,
+            Caste::Soldier => self.soldiers += 1,
+            Caste::Digger | Caste::Forager => {}
+        }
+    }
+
+    fn remove(&mut self, caste: Caste) {
+        self.workers -= 1;
+
+        match caste {
+            Caste::Nurse => self.nurses -= 1,
+            Caste::Soldier => self.soldiers -= 1,
+            Caste::Digger | Caste::Forager => {}
+        }
+    }
+}
+
+/// The colony needs more nurses when there's more brood than its nurses can tend, and more soldiers when its guard is
+/// too small for its size. Otherwise, there's no pressing need and workers follow their usual progression.
+/// A ratio of zero, or less, in Settings means the colony never calls on workers to fill that caste.
+fn get_needed_caste(
+    census: &CasteCensus,
+    brood_count: usize,
+    settings: &Settings,
+) -> Option<Caste> {
+    let brood_per_nurse = usize::try_from(settings.brood_per_nurse).unwrap_or(0);
+    if brood_per_nurse > 0 && brood_count > census.nurses * brood_per_nurse {
+        return Some(Caste::Nurse);
+    }
+
+    let workers_per_soldier = usize::try_from(settings.workers_per_soldier).unwrap_or(0);
+    if census
+        .workers
+        .checked_div(workers_per_soldier)
+        .is_some_and(|needed_soldiers| census.soldiers < needed_soldiers)
+    {
+        return Some(Caste::Soldier);
+    }
+
+    None
+}
+
+/// Workers which don't have a caste, either because they've just emerged from their pupa or because they were
+/// loaded from a save which predates castes, join whichever caste the colony needs. Otherwise, they start out as nurses.
+pub fn ants_assign_caste(
+    ants_query: Query<(Entity, &AntRole, Option<&Caste>), (With<Ant>, Without<Dead>)>,
+    brood_query: Query<(), With<Brood>>,
+    settings: Res<Settings>,
+    mut commands: Commands,
+) {
+    let mut census = CasteCensus::default();
+    for caste in ants_query.iter().filter_map(|(_, _, caste)| caste) {
+        census.add(*caste);
+    }
+
+    let brood_count = brood_query.iter().count();
+
+    for (ant_entity, role, caste) in ants_query.iter() {
+        if *role != AntRole::Worker || caste.is_some() {
+            continue;
+        }
+
+        let caste = get_needed_caste(&census, brood_count, &settings).unwrap_or(Caste::Nurse);
+        census.add(caste);
+
+        commands
+            .entity(ant_entity)
+            .insert((caste, CasteTenure::new(settings.max_caste_time)));
+    }
+}
+
+/// Once a worker has served its tenure, it moves on to whichever caste the colony needs, or to the next caste for its age.
+pub fn ants_migrate_caste(
+    mut ants_query: Query<(&mut Caste, &mut CasteTenure), (With<Ant>, Without<Dead>)>,
+    brood_query: Query<(), With<Brood>>,
+    settings: Res<Settings>,
+) {
+    let mut census = CasteCensus::default();
+    for (caste, _) in ants_query.iter() {
+        census.add(*caste);
+    }
+
+    let brood_count = brood_query.iter().count();
+
+    for (mut caste, mut tenure) in ants_query.iter_mut() {
+        tenure.tick();
+
+        if !tenure.is_ready() {
+            continue;
+        }
+
+        // An ant doesn't count towards the colony's needs while it's deciding what to do next.
+        census.remove(*caste);
+
+        let next_caste =
+            get_needed_caste(&census, brood_count, &settings).unwrap_or(caste.next_by_age());
+        census.add(next_caste);
+
+        if *caste != next_caste {
+            *caste = next_caste;
+        }
+
+        tenure.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_needed_caste, Caste, CasteCensus, CasteTenure};
+    use crate::{
+        common::position::Position, nest_simulation::brood::BroodStage, settings::Settings,
+        test_support::TestSimulation,
+    };
+
+    // Confirm that a new worker starts out as a nurse and moves on to digging as it ages.
+    #[test]
+    fn nurse_becomes_digger_with_age() {
+        let settings = Settings {
+            max_caste_time: 1,
+            ..Settings::default()
+        };
+
+        let mut simulation = TestSimulation::with_settings(
+            "
+            .....
+            #####
+            #W..#
+            #####
+            ",
+            settings,
+        );
+        let ant_entity = simulation.nest_ant(Position::new(1, 2));
+
+        simulation.tick(1);
+        assert_eq!(
+            simulation.world().get::<Caste>(ant_entity),
+            Some(&Caste::Nurse)
+        );
+        assert!(simulation.world().get::<CasteTenure>(ant_entity).is_some());
+
+        simulation.tick(11);
+        assert_eq!(
+            simulation.world().get::<Caste>(ant_entity),
+            Some(&Caste::Digger)
+        );
+    }
+
+    // Confirm that a forager returns to nursing when there's more brood than the colony's nurses can tend.
+    #[test]
+    fn forager_returns_to_nursing_when_brood_need_tending() {
+        let settings = Settings {
+            max_caste_time: 1,
+            ..Settings::default()
+        };
+
+        let mut simulation = TestSimulation::with_settings(
+            "
+            .....
+            #####
+            #W..#
+            #####
+            ",
+            settings,
+        );
+        let ant_entity = simulation.nest_ant(Position::new(1, 2));
+        simulation
+            .world_mut()
+            .entity_mut(ant_entity)
+            .insert((Caste::Forager, CasteTenure::new(1)));
+        simulation.spawn_brood(Position::new(3, 2), BroodStage::Egg);
+
+        simulation.tick(11);
+
+        assert_eq!(
+            simulation.world().get::<Caste>(ant_entity),
+            Some(&Caste::Nurse)
+        );
+    }
+
+    // Confirm that the colony calls on nurses and soldiers in proportion to its brood and workers.
+    #[test]
+    fn needed_caste_follows_ratios() {
+        let settings = Settings {
+            brood_per_nurse: 4,
+            workers_per_soldier: 10,
+            ..Settings::default()
+        };
+        let census = CasteCensus {
+            nurses: 1,
+            soldiers: 0,
+            workers: 20,
+        };
+
+        assert_eq!(get_needed_caste(&census, 5, &settings), Some(Caste::Nurse));
+        assert_eq!(
+            get_needed_caste(&census, 4, &settings),
+            Some(Caste::Soldier)
+        );
+    }
+
+    // Confirm that a ratio of zero means the caste is never needed, rather than dividing by zero.
+    #[test]
+    fn zero_ratios_need_no_caste() {
+        let settings = Settings {
+            brood_per_nurse: 0,
+            workers_per_soldier: 0,
+            ..Settings::default()
+        };
+        let census = CasteCensus {
+            nurses: 0,
+            soldiers: 0,
+            workers: 20,
+        };
+
+        assert_eq!(get_needed_caste(&census, 5, &settings), None);
+    }
+}
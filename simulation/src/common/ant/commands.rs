@@ -1,10 +1,14 @@
 use crate::{
     common::{
         ant::{
-            digestion::Digestion, hunger::Hunger, AntBundle, AntColor, AntInventory, AntName,
-            AntRole, Initiative, InventoryItemBundle,
+            age::Age,
+            digestion::Digestion,
+            hunger::Hunger,
+            task::{finish_task, start_task, Task},
+            thirst::Thirst,
+            AntBundle, AntColor, AntInventory, AntName, AntRole, Initiative, InventoryItemBundle,
         },
-        element::{Element, ElementBundle},
+        element::{Element, ElementBundle, FoodKind, Freshness, Hardness},
         grid::{GridElements, GridElementsMut},
         position::Position,
         Zone,
@@ -47,6 +51,8 @@ pub trait AntCommandsExt {
         target_element_entity: Entity,
         zone: Z,
     );
+    fn start_task(&mut self, ant_entity: Entity, task: Task);
+    fn finish_task(&mut self, ant_entity: Entity, task: Task);
 }
 
 impl<'w, 's> AntCommandsExt for Commands<'w, 's> {
@@ -104,6 +110,14 @@ impl<'w, 's> AntCommandsExt for Commands<'w, 's> {
             zone,
         });
     }
+
+    fn start_task(&mut self, ant_entity: Entity, task: Task) {
+        self.add(StartTaskCommand { ant_entity, task });
+    }
+
+    fn finish_task(&mut self, ant_entity: Entity, task: Task) {
+        self.add(FinishTaskCommand { ant_entity, task });
+    }
 }
 
 struct DigElementCommand<Z: Zone + Copy> {
@@ -143,6 +157,29 @@ impl<Z: Zone + Copy> Command for DigElementCommand<Z> {
             }
         };
 
+        if !element.is_diggable() {
+            info!("Element {:?} can't be dug.", element);
+            return;
+        }
+
+        // Hard elements are chipped away, one dig at a time, before they give way.
+        if let Some(mut hardness) = world.get_mut::<Hardness>(element_entity) {
+            if hardness.0 > 0 {
+                hardness.0 -= 1;
+
+                match world.get_mut::<Initiative>(self.ant_entity) {
+                    Some(mut initiative) => initiative.consume(),
+                    None => panic!("Failed to get initiative for ant {:?}", self.ant_entity),
+                };
+
+                return;
+            }
+        }
+
+        // Food keeps going off while it's carried.
+        let freshness = world.get::<Freshness>(element_entity).copied();
+        let food_kind = world.get::<FoodKind>(element_entity).copied();
+
         world.entity_mut(element_entity).despawn();
 
         let air_entity = world
@@ -160,7 +197,7 @@ impl<Z: Zone + Copy> Command for DigElementCommand<Z> {
 
         // TODO: There's probably a more elegant way to express this - "denseness" of sand rather than changing between dirt/sand.
         let mut inventory_element = element;
-        if inventory_element == Element::Dirt {
+        if matches!(inventory_element, Element::Dirt | Element::Mud) {
             inventory_element = Element::Sand;
         }
 
@@ -168,6 +205,14 @@ impl<Z: Zone + Copy> Command for DigElementCommand<Z> {
             .spawn(InventoryItemBundle::new(inventory_element, self.zone))
             .id();
 
+        if let Some(freshness) = freshness {
+            world.entity_mut(inventory_item_entity).insert(freshness);
+        }
+
+        if let Some(food_kind) = food_kind {
+            world.entity_mut(inventory_item_entity).insert(food_kind);
+        }
+
         match world.get_mut::<AntInventory>(self.ant_entity) {
             Some(mut inventory) => inventory.0 = Some(inventory_item_entity),
             None => panic!("Failed to get inventory for ant {:?}", self.ant_entity),
@@ -219,6 +264,8 @@ impl<Z: Zone> Command for DropElementCommand<Z> {
         };
 
         let element = world.get::<Element>(inventory_item_entity).unwrap();
+        let freshness = world.get::<Freshness>(inventory_item_entity).copied();
+        let food_kind = world.get::<FoodKind>(inventory_item_entity).copied();
 
         // Add element to world.
         let element_entity = world
@@ -229,6 +276,14 @@ impl<Z: Zone> Command for DropElementCommand<Z> {
             ))
             .id();
 
+        if let Some(freshness) = freshness {
+            world.entity_mut(element_entity).insert(freshness);
+        }
+
+        if let Some(food_kind) = food_kind {
+            world.entity_mut(element_entity).insert(food_kind);
+        }
+
         let mut system_state: SystemState<GridElementsMut<Z>> = SystemState::new(world);
         let mut grid_elements = system_state.get_mut(world);
 
@@ -251,6 +306,29 @@ impl<Z: Zone> Command for DropElementCommand<Z> {
     }
 }
 
+struct StartTaskCommand {
+    ant_entity: Entity,
+    task: Task,
+}
+
+impl Command for StartTaskCommand {
+    fn apply(self, world: &mut World) {
+        start_task(self.ant_entity, self.task, world);
+    }
+}
+
+/// Finishing a task is ignored if the ant has since moved on to a different task.
+struct FinishTaskCommand {
+    ant_entity: Entity,
+    task: Task,
+}
+
+impl Command for FinishTaskCommand {
+    fn apply(self, world: &mut World) {
+        finish_task(self.ant_entity, self.task, world);
+    }
+}
+
 struct SpawnAntCommand<Z: Zone> {
     position: Position,
     color: AntColor,
@@ -280,6 +358,8 @@ impl<Z: Zone> Command for SpawnAntCommand<Z> {
                 self.zone,
                 Hunger::new(settings.max_hunger_time),
                 Digestion::new(settings.max_digestion_time),
+                Thirst::new(settings.max_thirst_time),
+                Age::for_role(self.role, settings),
             ))
             .id();
 
@@ -1,22 +1,83 @@
-use crate::common::{
-    ant::{commands::AntCommandsExt, AntInventory, Dead},
-    element::Element,
-    grid::GridElements,
-    position::Position,
-    Zone,
+use crate::{
+    common::{
+        ant::{commands::AntCommandsExt, Ant, AntInventory, Dead, InventoryItem},
+        element::Element,
+        grid::GridElements,
+        position::Position,
+        Zone,
+    },
+    nest_simulation::brood::Brood,
+    settings::Settings,
+    story_time::DEFAULT_TICKS_PER_SECOND,
 };
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Why an ant died. Ants which died before causes were recorded don't have one.
+#[derive(
+    Component, Debug, PartialEq, Eq, Copy, Clone, Serialize, Deserialize, Reflect, Default, Hash,
+)]
+#[reflect(Component)]
+pub enum CauseOfDeath {
+    #[default]
+    Starvation,
+    OldAge,
+    /// Killed by the player.
+    Killed,
+    Dehydration,
+}
+
+/// How long a corpse has been decaying. Like Age, decay is tracked in whole ticks because it takes days.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Component)]
+pub struct Decay {
+    ticks: isize,
+    max_ticks: isize,
+}
+
+impl Decay {
+    pub fn new(max_time_seconds: isize) -> Self {
+        Self {
+            ticks: 0,
+            max_ticks: max_time_seconds * DEFAULT_TICKS_PER_SECOND,
+        }
+    }
+
+    /// How far the corpse has decayed, as a percentage.
+    pub fn value(&self) -> f32 {
+        self.ticks as f32 / self.max_ticks as f32 * 100.0
+    }
+
+    pub fn tick(&mut self) {
+        self.ticks = (self.ticks + 1).min(self.max_ticks);
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.ticks >= self.max_ticks
+    }
+}
 
 /// Force ants to drop, or despawn, their inventory upon death.
 /// TODO:
 ///     * It might be preferable to find an adjacent, available zone to move inventory to rather than despawning.
 pub fn on_ants_add_dead<Z: Zone + Copy>(
-    ants_query: Query<(Entity, &Position, &AntInventory, &Z), (Added<Dead>, With<Z>)>,
+    mut ants_query: Query<(Entity, &Position, &mut AntInventory, &Z), (Added<Dead>, With<Z>)>,
+    carried_query: Query<(), Or<(With<Brood>, With<Ant>)>>,
     mut commands: Commands,
     grid_elements: GridElements<Z>,
 ) {
-    for (ant_entity, ant_position, ant_inventory, zone) in ants_query.iter() {
-        if ant_inventory.0 != None {
+    for (ant_entity, ant_position, mut ant_inventory, zone) in ants_query.iter_mut() {
+        if let Some(carried_entity) = ant_inventory
+            .0
+            .filter(|&entity| carried_query.contains(entity))
+        {
+            // Brood and corpses aren't elements, so they're left lying beside their carrier rather than dropped into the grid.
+            commands
+                .entity(carried_entity)
+                .insert(*ant_position)
+                .remove::<InventoryItem>();
+            ant_inventory.0 = None;
+        } else if ant_inventory.0 != None {
             let element_entity = grid_elements.entity(*ant_position);
 
             if grid_elements.is(*ant_position, Element::Air) {
@@ -27,3 +88,37 @@ pub fn on_ants_add_dead<Z: Zone + Copy>(
         }
     }
 }
+
+/// Corpses decay wherever they lie, even while being carried, and are gone once they've fully decayed.
+/// Corpses which don't have Decay, because they were loaded from a save which predates it, start decaying now.
+pub fn corpses_decay(
+    mut corpses_query: Query<(Entity, Option<&mut Decay>), (With<Ant>, With<Dead>)>,
+    mut ants_query: Query<&mut AntInventory, Without<Dead>>,
+    settings: Res<Settings>,
+    mut commands: Commands,
+) {
+    for (corpse_entity, decay) in corpses_query.iter_mut() {
+        let Some(mut decay) = decay else {
+            commands
+                .entity(corpse_entity)
+                .insert(Decay::new(settings.max_corpse_decay_time));
+            continue;
+        };
+
+        decay.tick();
+
+        if !decay.is_expired() {
+            continue;
+        }
+
+        // Nothing is left for the ant carrying the corpse, if any, to carry.
+        if let Some(mut inventory) = ants_query
+            .iter_mut()
+            .find(|inventory| inventory.0 == Some(corpse_entity))
+        {
+            inventory.0 = None;
+        }
+
+        commands.entity(corpse_entity).despawn();
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    common::{ant::Dead, Zone},
+    common::{ant::Dead, element::FoodKind, Zone},
     story_time::DEFAULT_TICKS_PER_SECOND,
 };
 
@@ -14,6 +14,8 @@ pub struct Digestion {
     pub value: f32,
     max: f32,
     rate: f32,
+    /// What was last eaten, which decides how quickly the stomach is digested.
+    food_kind: FoodKind,
 }
 
 impl Digestion {
@@ -26,6 +28,7 @@ impl Digestion {
             value: 100.0,
             max,
             rate,
+            food_kind: FoodKind::default(),
         }
     }
 
@@ -41,12 +44,29 @@ impl Digestion {
         self.max
     }
 
+    pub fn food_kind(&self) -> FoodKind {
+        self.food_kind
+    }
+
     pub fn increment(&mut self, percent: f32) {
         self.value += (self.max() * percent).min(self.value());
     }
 
+    /// Fill the stomach with a piece of food. How much it fills, and how quickly it's digested, depends on its kind.
+    pub fn eat(&mut self, food_kind: FoodKind) {
+        self.increment(-food_kind.nutrition());
+        self.food_kind = food_kind;
+    }
+
+    /// Pass undigested food on to another stomach, which is then digesting whatever kind of food was shared.
+    pub fn regurgitate(&mut self, other: &mut Digestion, amount: f32) {
+        self.value += amount;
+        other.value -= amount;
+        other.food_kind = self.food_kind;
+    }
+
     pub fn tick(&mut self) -> f32 {
-        let new_value = (self.value + self.rate).min(self.max);
+        let new_value = (self.value + self.rate * self.food_kind.digestion_speed()).min(self.max);
         let change = new_value - self.value;
         self.value = new_value;
         change
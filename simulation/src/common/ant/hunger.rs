@@ -1,8 +1,13 @@
-use super::digestion::Digestion;
+use super::{
+    digestion::Digestion,
+    thirst::{get_shared_water_amount, Thirst},
+};
 use crate::{
     common::{
-        ant::{commands::AntCommandsExt, AntInventory, AntRole, Dead, Initiative},
-        element::Element,
+        ant::{
+            commands::AntCommandsExt, death::CauseOfDeath, AntInventory, AntRole, Dead, Initiative,
+        },
+        element::{Element, FoodKind},
         grid::GridElements,
         position::Position,
         Zone,
@@ -85,6 +90,7 @@ pub fn ants_hunger_act<Z: Zone + Copy>(
             Entity,
             &Hunger,
             &mut Digestion,
+            Option<&mut Thirst>,
             &NestOrientation,
             &Position,
             &mut AntInventory,
@@ -94,7 +100,7 @@ pub fn ants_hunger_act<Z: Zone + Copy>(
         With<Z>,
     >,
     grid_elements: GridElements<Z>,
-    elements_query: Query<&Element, With<Z>>,
+    elements_query: Query<(&Element, Option<&FoodKind>), With<Z>>,
     mut commands: Commands,
     mut ant_ate_food_event_writer: EventWriter<AntAteFoodEvent>,
 ) {
@@ -102,6 +108,7 @@ pub fn ants_hunger_act<Z: Zone + Copy>(
         ant_entity,
         hunger,
         mut digestion,
+        thirst,
         orientation,
         position,
         mut inventory,
@@ -112,7 +119,7 @@ pub fn ants_hunger_act<Z: Zone + Copy>(
         if hunger.is_starved() {
             commands
                 .entity(ant_entity)
-                .insert(Dead)
+                .insert((Dead, CauseOfDeath::Starvation))
                 .remove::<Initiative>();
         } else if hunger.is_peckish() {
             if !initiative.can_act() {
@@ -126,37 +133,49 @@ pub fn ants_hunger_act<Z: Zone + Copy>(
                     let food_entity = grid_elements.entity(ahead_position);
                     commands.dig(ant_entity, ahead_position, *food_entity, *zone);
                 }
-            } else {
-                let element = elements_query.get(inventory.0.unwrap()).unwrap();
-
-                if *element == Element::Food {
-                    inventory.0 = None;
+            } else if let Ok((Element::Food, food_kind)) = elements_query.get(inventory.0.unwrap())
+            {
+                // Ants can carry things other than elements, such as brood, which they mustn't eat.
+                inventory.0 = None;
 
-                    digestion.increment(-0.20);
-                    initiative.consume();
+                let food_kind = food_kind.copied().unwrap_or_default();
+                digestion.eat(food_kind);
 
-                    ant_ate_food_event_writer.send(AntAteFoodEvent(ant_entity));
+                // Moist food quenches thirst as well as hunger.
+                if let Some(mut thirst) = thirst {
+                    thirst.drink(food_kind.moisture());
                 }
+
+                initiative.consume();
+
+                ant_ate_food_event_writer.send(AntAteFoodEvent(ant_entity));
             }
         }
     }
 }
 
+/// An ant regurgitates up to 20% of its digestion at a time.
+pub fn get_regurgitation_amount(digestion: &Digestion) -> f32 {
+    (digestion.max() * 0.20).min(digestion.value())
+}
+
 // TODO: I haven't strongly considered whether this logic is robust enough to run AtCrater, it was written for AtNest originally.
 // If an ant is face-to-face with another ant then it is able to regurgitate food from itself to the other ant.
 // It will only do this if the other ant is hungry.
 // If the queen is starving then a worker will transfer food to it irrespective of the workers hunger level. The worker gives all it has up to 20%.
 // If the other ant is hungry, then a worker will transfer food if it is well fed. This ensures workers don't spend time transferring food to a hungry ant but, in the process, make themselves hungry.
+// Water is shared in the same way, and at the same time, as food. Thirsty ants are given water by ants which aren't thirsty.
 
-// Step 1: Find all ants which are hungry or worse.
-// Step 2: For each hungry-or-worse ant, look at the position directly in front of it.
-// Step 3: If there is an ant in that position, and if that ant is facing towards the hungry ant, then transfer food to the hungry ant.
+// Step 1: Find all ants which are hungry, or thirsty, or worse.
+// Step 2: For each such ant, look at the position directly in front of it.
+// Step 3: If there is an ant in that position, and if that ant is facing towards the needy ant, then transfer food and/or water to the needy ant.
 pub fn ants_hunger_regurgitate<Z: Zone>(
     mut ants_hunger_query: Query<
         (
             Entity,
             &Hunger,
             &mut Digestion,
+            Option<&mut Thirst>,
             &NestOrientation,
             &Position,
             &AntInventory,
@@ -167,71 +186,102 @@ pub fn ants_hunger_regurgitate<Z: Zone>(
     >,
     mut ant_ate_food_event_writer: EventWriter<AntAteFoodEvent>,
 ) {
-    let peckish_ants = ants_hunger_query
+    let needy_ants = ants_hunger_query
         .iter()
-        .filter(|(_, hunger, _, _, _, inventory, initiative, _)| {
-            initiative.can_act() && hunger.is_peckish() && inventory.0 == None
+        .filter(|(_, hunger, _, thirst, _, _, inventory, initiative, _)| {
+            initiative.can_act()
+                && inventory.0 == None
+                && (hunger.is_peckish() || thirst.map_or(false, |thirst| thirst.is_thirsty()))
         })
         .collect::<Vec<_>>();
 
     let mut results = vec![];
 
-    for (ant_entity, ant_hunger, _, ant_orientation, ant_position, _, _, ant_role) in peckish_ants {
+    for (ant_entity, ant_hunger, _, ant_thirst, ant_orientation, ant_position, _, _, ant_role) in
+        needy_ants
+    {
         let ahead_position = ant_orientation.get_ahead_position(ant_position);
 
-        if let Some((other_ant_entity, other_ant_hunger, other_ant_digestion, _, _, _, _, _)) =
-            ants_hunger_query
-                .iter()
-                // Support ontop of as well as in front because its kinda challenging to ensure queen can have an ant directly in front of them.
-                .find(
-                    |(
-                        other_ant_entity,
-                        _,
-                        _,
-                        other_ant_orientation,
-                        &other_ant_position,
-                        other_ant_inventory,
-                        other_ant_initiative,
-                        _,
-                    )| {
-                        if !other_ant_initiative.can_act() || other_ant_inventory.0 != None {
-                            return false;
-                        }
-
-                        // If ants are adjacent and facing one another - allow regurgitation.
-                        if other_ant_position == ahead_position
-                            && other_ant_orientation.get_ahead_position(&other_ant_position)
-                                == *ant_position
-                        {
-                            return true;
-                        }
-
-                        // If ants are standing ontop of one another (and not the same ant) - allow regurgitation
-                        if other_ant_position == *ant_position && *other_ant_entity != ant_entity {
-                            return true;
-                        }
-
+        if let Some((
+            other_ant_entity,
+            other_ant_hunger,
+            other_ant_digestion,
+            other_ant_thirst,
+            _,
+            _,
+            _,
+            _,
+            _,
+        )) = ants_hunger_query
+            .iter()
+            // Support ontop of as well as in front because its kinda challenging to ensure queen can have an ant directly in front of them.
+            .find(
+                |(
+                    other_ant_entity,
+                    _,
+                    _,
+                    _,
+                    other_ant_orientation,
+                    &other_ant_position,
+                    other_ant_inventory,
+                    other_ant_initiative,
+                    _,
+                )| {
+                    if !other_ant_initiative.can_act() || other_ant_inventory.0 != None {
                         return false;
-                    },
-                )
+                    }
+
+                    // If ants are adjacent and facing one another - allow regurgitation.
+                    if other_ant_position == ahead_position
+                        && other_ant_orientation.get_ahead_position(&other_ant_position)
+                            == *ant_position
+                    {
+                        return true;
+                    }
+
+                    // If ants are standing ontop of one another (and not the same ant) - allow regurgitation
+                    if other_ant_position == *ant_position && *other_ant_entity != ant_entity {
+                        return true;
+                    }
+
+                    return false;
+                },
+            )
         {
-            if *ant_role == AntRole::Queen
-                || (ant_hunger.is_starving() && !other_ant_hunger.is_hungry())
-                || (ant_hunger.is_hungry() && other_ant_hunger.is_full())
+            let mut digestion_transfer_amount = 0.0;
+            if ant_hunger.is_peckish()
+                && (*ant_role == AntRole::Queen
+                    || (ant_hunger.is_starving() && !other_ant_hunger.is_hungry())
+                    || (ant_hunger.is_hungry() && other_ant_hunger.is_full()))
             {
-                // Transfer up to 20% digestion from other_ant to ant.
-                let digestion_transfer_amount =
-                    (other_ant_digestion.max() * 0.20).min(other_ant_digestion.value());
+                digestion_transfer_amount = get_regurgitation_amount(other_ant_digestion);
+            }
 
-                if digestion_transfer_amount > 0.0 {
-                    results.push((ant_entity, other_ant_entity, digestion_transfer_amount));
+            let mut water_transfer_amount = 0.0;
+            if let (Some(ant_thirst), Some(other_ant_thirst)) = (ant_thirst, other_ant_thirst) {
+                if ant_thirst.is_thirsty()
+                    && (*ant_role == AntRole::Queen
+                        || (ant_thirst.is_dehydrating() && !other_ant_thirst.is_parched())
+                        || (ant_thirst.is_parched() && other_ant_thirst.is_quenched()))
+                {
+                    water_transfer_amount = get_shared_water_amount(other_ant_thirst);
                 }
             }
+
+            if digestion_transfer_amount > 0.0 || water_transfer_amount > 0.0 {
+                results.push((
+                    ant_entity,
+                    other_ant_entity,
+                    digestion_transfer_amount,
+                    water_transfer_amount,
+                ));
+            }
         }
     }
 
-    for (ant_entity, other_ant_entity, digestion_transfer_amount) in results {
-        let [(_, _, mut digestion, _, _, _, mut ant_initiative, _), (_, _, mut other_ant_digestion, _, _, _, mut other_ant_initiative, _)] =
+    for (ant_entity, other_ant_entity, digestion_transfer_amount, water_transfer_amount) in results
+    {
+        let [(_, _, mut digestion, thirst, _, _, _, mut ant_initiative, _), (_, _, mut other_ant_digestion, other_ant_thirst, _, _, _, mut other_ant_initiative, _)] =
             ants_hunger_query
                 .get_many_mut([ant_entity, other_ant_entity])
                 .unwrap();
@@ -243,14 +293,144 @@ pub fn ants_hunger_regurgitate<Z: Zone>(
             continue;
         }
 
-        // TODO: Consider `is_digesting` because an ant remains hungry for a while, even if its digesting food,
-        // so it would be possible to transfer it too much food if don't consider how full its stomach is
-        digestion.value -= digestion_transfer_amount;
-        other_ant_digestion.value += digestion_transfer_amount;
+        if digestion_transfer_amount > 0.0 {
+            // TODO: Consider `is_digesting` because an ant remains hungry for a while, even if its digesting food,
+            // so it would be possible to transfer it too much food if don't consider how full its stomach is
+            other_ant_digestion.regurgitate(&mut digestion, digestion_transfer_amount);
+
+            ant_ate_food_event_writer.send(AntAteFoodEvent(ant_entity));
+        }
+
+        if water_transfer_amount > 0.0 {
+            if let (Some(mut thirst), Some(mut other_ant_thirst)) = (thirst, other_ant_thirst) {
+                other_ant_thirst.give_water(&mut thirst, water_transfer_amount);
+            }
+        }
 
         ant_initiative.consume();
         other_ant_initiative.consume();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Hunger;
+    use crate::{
+        common::{
+            ant::{digestion::Digestion, thirst::Thirst, AntInventory, Dead},
+            element::{Element, FoodKind},
+            position::Position,
+        },
+        nest_simulation::ant::NestFacing,
+        settings::Settings,
+        story_time::DEFAULT_TICKS_PER_SECOND,
+        test_support::TestSimulation,
+    };
+
+    // Confirm that an ant with nothing to eat starves to death.
+    #[test]
+    fn ant_without_food_starves() {
+        let settings = Settings {
+            max_hunger_time: 1,
+            ..Settings::default()
+        };
+
+        let mut simulation = TestSimulation::with_settings(
+            "
+            ...
+            ###
+            #W#
+            ###
+            ",
+            settings,
+        );
+
+        let ant_entity = simulation.nest_ant(Position::new(1, 2));
+
+        simulation.tick(DEFAULT_TICKS_PER_SECOND as usize - 1);
+        assert!(simulation.world().get::<Dead>(ant_entity).is_none());
+
+        simulation.tick(2);
+        assert!(simulation.world().get::<Dead>(ant_entity).is_some());
+    }
+
+    // Confirm that a hungry ant eats food in front of it.
+    #[test]
+    fn hungry_ant_eats_food() {
+        let mut simulation = TestSimulation::new(
+            "
+            ....
+            ####
+            #Wf#
+            ####
+            ",
+        );
+
+        let ant_entity = simulation.nest_ant(Position::new(1, 2));
+        simulation.face_nest_ant(ant_entity, NestFacing::Right);
+        simulation
+            .world_mut()
+            .get_mut::<Hunger>(ant_entity)
+            .unwrap()
+            .set_value(60.0);
+
+        simulation.tick(30);
+
+        assert_eq!(simulation.inventory_element(ant_entity), None);
+        assert!(simulation
+            .world()
+            .get::<Digestion>(ant_entity)
+            .unwrap()
+            .is_digesting());
+        assert!(!simulation.nest_map().contains('f'));
+    }
+
+    // Confirm that protein fills an ant up more than sugar does, but sugar is digested more quickly.
+    #[test]
+    fn food_kind_decides_nutrition_and_digestion_speed() {
+        let settings = Settings::default();
+        let mut sugar_digestion = Digestion::new(settings.max_digestion_time);
+        let mut protein_digestion = Digestion::new(settings.max_digestion_time);
+
+        sugar_digestion.eat(FoodKind::Sugar);
+        protein_digestion.eat(FoodKind::Protein);
+
+        assert!(protein_digestion.value() < sugar_digestion.value());
+        assert!(sugar_digestion.tick() > protein_digestion.tick());
+    }
 
-        ant_ate_food_event_writer.send(AntAteFoodEvent(ant_entity));
+    // Confirm that eating moist food quenches an ant's thirst as well as its hunger.
+    #[test]
+    fn moist_food_quenches_thirst() {
+        let mut simulation = TestSimulation::new(
+            "
+            ...
+            ###
+            #W#
+            ###
+            ",
+        );
+
+        let ant_entity = simulation.nest_ant(Position::new(1, 2));
+        simulation.give_nest_ant(ant_entity, Element::Food);
+
+        let world = simulation.world_mut();
+        let food_entity = world.get::<AntInventory>(ant_entity).unwrap().0.unwrap();
+        world.entity_mut(food_entity).insert(FoodKind::Sugar);
+        let mut ant = world.entity_mut(ant_entity);
+        ant.get_mut::<Hunger>().unwrap().set_value(60.0);
+        ant.get_mut::<Thirst>().unwrap().set_value(60.0);
+
+        simulation.tick(30);
+
+        assert_eq!(simulation.inventory_element(ant_entity), None);
+        assert!(
+            simulation
+                .world()
+                .get::<Thirst>(ant_entity)
+                .unwrap()
+                .value()
+                < 40.0
+        );
     }
 }
@@ -1,3 +1,4 @@
+use super::age::Age;
 use crate::common::Zone;
 use bevy::prelude::*;
 use bevy_turborand::{DelegatedRng, GlobalRng};
@@ -57,12 +58,12 @@ impl Initiative {
 
 // Each ant maintains an internal timer that determines when it will act next.
 // This adds a little realism by varying when movements occur and allows for flexibility
-// in the simulation run speed.
+// in the simulation run speed. Elderly ants take longer to replenish their initiative.
 pub fn ants_initiative<Z: Zone>(
-    mut alive_ants_query: Query<&mut Initiative, With<Z>>,
+    mut alive_ants_query: Query<(&mut Initiative, Option<&Age>), With<Z>>,
     mut rng: ResMut<GlobalRng>,
 ) {
-    for mut initiative in alive_ants_query.iter_mut() {
+    for (mut initiative, age) in alive_ants_query.iter_mut() {
         if initiative.timer > 0 {
             initiative.timer -= 1;
 
@@ -75,5 +76,6 @@ pub fn ants_initiative<Z: Zone>(
         }
 
         *initiative = Initiative::new(&mut rng.reborrow());
+        initiative.timer += age.map_or(0, |age| age.get_initiative_delay());
     }
 }
@@ -1,13 +1,25 @@
+pub mod age;
+pub mod caste;
 pub mod commands;
 pub mod death;
 pub mod digestion;
 pub mod hunger;
 pub mod initiative;
+pub mod task;
+pub mod thirst;
 // pub mod sleep;
 mod name_list;
 
 use self::{
-    digestion::Digestion, hunger::Hunger, initiative::Initiative, name_list::get_random_name,
+    age::Age,
+    caste::{Caste, CasteTenure},
+    death::{CauseOfDeath, Decay},
+    digestion::Digestion,
+    hunger::Hunger,
+    initiative::Initiative,
+    name_list::get_random_name,
+    task::{AntTask, AntTaskTransition, Task},
+    thirst::Thirst,
 };
 use crate::common::{element::Element, position::Position, Zone};
 use bevy::{
@@ -30,7 +42,10 @@ where
     color: AntColor,
     hunger: Hunger,
     digestion: Digestion,
+    thirst: Thirst,
+    age: Age,
     inventory: AntInventory,
+    task: AntTask,
     zone: Z,
 }
 
@@ -45,6 +60,8 @@ impl<Z: Zone> AntBundle<Z> {
         zone: Z,
         hunger: Hunger,
         digestion: Digestion,
+        thirst: Thirst,
+        age: Age,
     ) -> Self {
         Self {
             ant: Ant,
@@ -57,6 +74,9 @@ impl<Z: Zone> AntBundle<Z> {
             zone,
             hunger,
             digestion,
+            thirst,
+            age,
+            task: AntTask::default(),
         }
     }
 }
@@ -97,7 +117,9 @@ pub struct Ant;
 
 impl Ant {}
 
-#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[derive(
+    Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default, Hash,
+)]
 #[reflect(Component)]
 pub enum AntRole {
     #[default]
@@ -137,11 +159,22 @@ pub fn register_ant(app_type_registry: ResMut<AppTypeRegistry>) {
     app_type_registry.write().register::<AntRole>();
     app_type_registry.write().register::<AntInventory>();
     app_type_registry.write().register::<InventoryItem>();
+    app_type_registry.write().register::<Caste>();
+    app_type_registry.write().register::<CasteTenure>();
 
     app_type_registry.write().register::<Dead>();
+    app_type_registry.write().register::<CauseOfDeath>();
+    app_type_registry.write().register::<Decay>();
+    app_type_registry.write().register::<Age>();
     app_type_registry.write().register::<Hunger>();
+    app_type_registry.write().register::<Thirst>();
     app_type_registry.write().register::<Digestion>();
 
-    // TODO: This might be nest-specific, but maybe needs to be supported at crater just in case
-    // app_type_registry.write().register::<Asleep>();
+    app_type_registry.write().register::<AntTask>();
+    app_type_registry.write().register::<Task>();
+    app_type_registry.write().register::<Vec<Task>>();
+    app_type_registry.write().register::<AntTaskTransition>();
+    app_type_registry
+        .write()
+        .register::<Vec<AntTaskTransition>>();
 }
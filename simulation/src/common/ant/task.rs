@@ -0,0 +1,269 @@
+use crate::{
+    common::ant::{initiative::Initiative, Dead},
+    nest_simulation::ant::{
+        nesting::{Nested, Nesting},
+        nuptial_flight::{Flight, Winged},
+    },
+};
+use bevy::prelude::*;
+use bevy_turborand::GlobalRng;
+use serde::{Deserialize, Serialize};
+use std::mem::discriminant;
+
+/// How many transitions are remembered by `AntTask::history`.
+const MAX_TASK_HISTORY: usize = 10;
+
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+pub enum Task {
+    /// Nothing in particular. The ant falls back on its default behaviors, i.e. wandering, digging, and dropping.
+    #[default]
+    Idle,
+    /// The queen is on her nuptial flight, looking for somewhere to land.
+    Flying(Flight),
+    /// The queen is founding the nest.
+    Nesting(Nesting),
+    /// Following a tunnel pheromone. The value is the pheromone's remaining strength, which fades with each step taken.
+    Tunneling(f32),
+    /// Following a chamber pheromone. The value is the pheromone's remaining strength, which fades with each step taken.
+    Chambering(f32),
+    /// Out in the crater, laying down a trail of Nest pheromone. The value is the strength of the next pheromone laid.
+    LeavingNest(f32),
+    /// Out in the crater, laying down a trail of Food pheromone. The value is the strength of the next pheromone laid.
+    LeavingFood(f32),
+    Asleep,
+}
+
+impl Task {
+    /// Whether an ant doing this task is allowed to start doing `next`. Transitions which aren't listed are rejected.
+    pub fn can_transition_to(&self, next: &Task) -> bool {
+        match (self, next) {
+            // Sleeping ants need to wake up before doing anything else.
+            (Task::Asleep, _) => false,
+            (_, Task::Asleep) => true,
+            // Wings are only shed once so there's no taking flight again.
+            (_, Task::Flying(_)) => false,
+            (Task::Idle, _) => true,
+            (Task::Flying(_), Task::Nesting(_)) => true,
+            (Task::Nesting(_), Task::Tunneling(_) | Task::Chambering(_)) => true,
+            (
+                Task::Tunneling(_) | Task::Chambering(_),
+                Task::Tunneling(_) | Task::Chambering(_) | Task::LeavingNest(_),
+            ) => true,
+            (
+                Task::LeavingNest(_) | Task::LeavingFood(_),
+                Task::LeavingNest(_) | Task::LeavingFood(_),
+            ) => true,
+            _ => false,
+        }
+    }
+
+    /// Whether this task is put on hold, and resumed once `next` is finished, rather than being abandoned in favor of it.
+    fn is_resumed_after(&self, next: &Task) -> bool {
+        match (self, next) {
+            (Task::Idle, _) => false,
+            // Ants pick up where they left off once they wake up.
+            (_, Task::Asleep) => true,
+            // Founding the nest is a long undertaking which the queen always returns to.
+            (Task::Nesting(_), _) => true,
+            _ => false,
+        }
+    }
+
+    /// Side-effects of starting a task. Not run when a task which was put on hold is resumed.
+    pub(crate) fn on_enter(&self, ant_entity: Entity, world: &mut World) {
+        // Sleeping ants don't move or act.
+        if *self == Task::Asleep {
+            world.entity_mut(ant_entity).remove::<Initiative>();
+        }
+    }
+
+    /// Side-effects of finishing, or abandoning, a task. Not run when a task is put on hold.
+    pub(crate) fn on_exit(&self, ant_entity: Entity, world: &mut World) {
+        match self {
+            Task::Asleep => {
+                // Ants which died in their sleep stay without initiative.
+                if world.get::<Dead>(ant_entity).is_some() {
+                    return;
+                }
+
+                let initiative = Initiative::new(&mut world.resource_mut::<GlobalRng>());
+                world.entity_mut(ant_entity).insert(initiative);
+            }
+            // Queens shed their wings once they've landed.
+            Task::Flying(_) => {
+                world.entity_mut(ant_entity).remove::<Winged>();
+            }
+            // The nest only needs founding once.
+            Task::Nesting(_) => {
+                world.entity_mut(ant_entity).insert(Nested);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+pub struct AntTaskTransition {
+    pub from: Task,
+    pub to: Task,
+}
+
+/// What an ant is currently doing. Ants only do one thing at a time, so this is the single source of truth for their
+/// behavior, but a task can be interrupted by another and then resumed once the interruption is over.
+///
+/// Tasks are changed through `AntCommandsExt::start_task` and `AntCommandsExt::finish_task` so that transitions are
+/// validated and their hooks are run. Progress within a task, i.e. the strength of a pheromone being followed, is
+/// updated directly through `current_mut`.
+#[derive(Component, Debug, PartialEq, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Component)]
+pub struct AntTask {
+    current: Task,
+    /// Tasks which were put on hold, most recent last.
+    interrupted: Vec<Task>,
+    /// The most recent transitions, oldest first, to help explain why an ant is doing what it's doing.
+    history: Vec<AntTaskTransition>,
+}
+
+impl AntTask {
+    pub fn new(task: Task) -> Self {
+        Self {
+            current: task,
+            ..default()
+        }
+    }
+
+    pub fn current(&self) -> &Task {
+        &self.current
+    }
+
+    pub fn current_mut(&mut self) -> &mut Task {
+        &mut self.current
+    }
+
+    pub fn interrupted(&self) -> &[Task] {
+        &self.interrupted
+    }
+
+    pub fn history(&self) -> &[AntTaskTransition] {
+        &self.history
+    }
+
+    pub fn is_asleep(&self) -> bool {
+        self.current == Task::Asleep
+    }
+
+    /// Whether the ant is founding the nest, even if it's been interrupted to do something else.
+    pub fn is_nesting(&self) -> bool {
+        std::iter::once(&self.current)
+            .chain(self.interrupted.iter())
+            .any(|task| matches!(task, Task::Nesting(_)))
+    }
+
+    /// Switch to `next`. Returns whether the previous task was put on hold rather than abandoned.
+    /// Restarting the current task, with new progress, isn't considered a transition.
+    fn start(&mut self, next: Task) -> bool {
+        if discriminant(&self.current) == discriminant(&next) {
+            self.current = next;
+            return false;
+        }
+
+        let previous = std::mem::replace(&mut self.current, next);
+        self.record(previous, next);
+
+        let is_resumed = previous.is_resumed_after(&next);
+        if is_resumed {
+            self.interrupted.push(previous);
+        }
+
+        is_resumed
+    }
+
+    /// Stop doing the current task and resume the most recently interrupted task, if any.
+    fn finish(&mut self) {
+        let next = self.interrupted.pop().unwrap_or_default();
+        let previous = std::mem::replace(&mut self.current, next);
+        self.record(previous, next);
+    }
+
+    fn record(&mut self, from: Task, to: Task) {
+        if self.history.len() >= MAX_TASK_HISTORY {
+            self.history.remove(0);
+        }
+
+        self.history.push(AntTaskTransition { from, to });
+    }
+}
+
+/// Start a task, running the hooks of any task which is abandoned and the hooks of the task being started.
+pub(crate) fn start_task(ant_entity: Entity, next: Task, world: &mut World) {
+    let Some(mut ant_task) = world.get_mut::<AntTask>(ant_entity) else {
+        info!("Failed to get task for ant {:?}", ant_entity);
+        return;
+    };
+
+    let previous = ant_task.current;
+    if discriminant(&previous) == discriminant(&next) {
+        ant_task.start(next);
+        return;
+    }
+
+    if !previous.can_transition_to(&next) {
+        info!(
+            "Ant {:?} can't transition from {:?} to {:?}.",
+            ant_entity, previous, next
+        );
+        return;
+    }
+
+    if !ant_task.start(next) {
+        previous.on_exit(ant_entity, world);
+    }
+
+    next.on_enter(ant_entity, world);
+}
+
+/// Finish a task, if the ant is still doing it, and run its hooks. Resumed tasks were never exited so aren't re-entered.
+pub(crate) fn finish_task(ant_entity: Entity, task: Task, world: &mut World) {
+    let Some(mut ant_task) = world.get_mut::<AntTask>(ant_entity) else {
+        info!("Failed to get task for ant {:?}", ant_entity);
+        return;
+    };
+
+    let previous = ant_task.current;
+    if discriminant(&previous) != discriminant(&task) {
+        return;
+    }
+
+    ant_task.finish();
+    previous.on_exit(ant_entity, world);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AntTask, Task};
+    use crate::nest_simulation::ant::nesting::Nesting;
+
+    // Confirm that a task which is put on hold is resumed once the interruption is finished.
+    #[test]
+    fn interrupted_task_is_resumed() {
+        let mut ant_task = AntTask::new(Task::Nesting(Nesting::NotStarted));
+
+        assert!(ant_task.start(Task::Tunneling(8.0)));
+        assert!(ant_task.start(Task::Asleep));
+        assert!(ant_task.is_nesting());
+
+        ant_task.finish();
+        assert_eq!(*ant_task.current(), Task::Tunneling(8.0));
+
+        ant_task.finish();
+        assert_eq!(*ant_task.current(), Task::Nesting(Nesting::NotStarted));
+        assert_eq!(ant_task.history().len(), 4);
+    }
+
+    // Confirm that sleeping ants must wake before doing anything else.
+    #[test]
+    fn asleep_cannot_transition() {
+        assert!(!Task::Asleep.can_transition_to(&Task::Tunneling(8.0)));
+        assert!(Task::Tunneling(8.0).can_transition_to(&Task::Asleep));
+    }
+}
@@ -0,0 +1,252 @@
+use crate::{
+    common::{
+        ant::{death::CauseOfDeath, Dead, Initiative},
+        element::Element,
+        grid::GridElements,
+        position::Position,
+        Zone,
+    },
+    nest_simulation::ant::NestOrientation,
+    story_time::DEFAULT_TICKS_PER_SECOND,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// How much of an ant's Thirst is relieved by a drink of water.
+const WATER_DRUNK_PER_SIP: f32 = 0.50;
+
+/// Ants need water as well as food. Thirst builds up just like Hunger and is relieved by drinking water, eating moist
+/// food, or being given water by another ant.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Component)]
+pub struct Thirst {
+    value: f32,
+    max: f32,
+    rate: f32,
+}
+
+impl Thirst {
+    pub fn new(max_time_seconds: isize) -> Self {
+        let max = 100.0;
+        let rate = max / (max_time_seconds * DEFAULT_TICKS_PER_SECOND) as f32;
+
+        Self {
+            value: 0.0,
+            max,
+            rate,
+        }
+    }
+
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    pub fn max(&self) -> f32 {
+        self.max
+    }
+
+    pub fn set_value(&mut self, value: f32) {
+        self.value = value.min(self.max).max(0.0);
+    }
+
+    pub fn tick(&mut self) {
+        self.value = (self.value + self.rate).min(self.max);
+    }
+
+    /// Relieve thirst by a fraction of the ant's capacity.
+    pub fn drink(&mut self, percent: f32) {
+        self.set_value(self.value - self.max * percent);
+    }
+
+    /// Pass water on to another ant. The water given is no longer available to this ant.
+    pub fn give_water(&mut self, other: &mut Thirst, amount: f32) {
+        self.set_value(self.value + amount);
+        other.set_value(other.value - amount);
+    }
+
+    pub fn is_quenched(&self) -> bool {
+        self.value < self.max * 0.25
+    }
+
+    pub fn is_thirsty(&self) -> bool {
+        self.value >= self.max * 0.25
+    }
+
+    pub fn is_parched(&self) -> bool {
+        self.value >= self.max * 0.50
+    }
+
+    pub fn is_dehydrating(&self) -> bool {
+        self.value >= self.max * 0.75
+    }
+
+    pub fn is_dehydrated(&self) -> bool {
+        self.value >= self.max
+    }
+}
+
+/// An ant gives away up to 20% of its capacity for water at a time, but never more than it holds.
+pub fn get_shared_water_amount(thirst: &Thirst) -> f32 {
+    (thirst.max() * 0.20).min(thirst.max() - thirst.value())
+}
+
+pub fn ants_thirst_tick<Z: Zone>(
+    mut ants_thirst_query: Query<&mut Thirst, (Without<Dead>, With<Z>)>,
+) {
+    for mut thirst in ants_thirst_query.iter_mut() {
+        thirst.tick();
+    }
+}
+
+/// Ants die of dehydration just like they die of starvation. Thirsty ants drink from water in front of them.
+/// Water isn't used up by drinking, so a pool in the nest is a lasting source of water.
+pub fn ants_thirst_act<Z: Zone>(
+    mut ants_thirst_query: Query<
+        (
+            Entity,
+            &mut Thirst,
+            &NestOrientation,
+            &Position,
+            &mut Initiative,
+        ),
+        With<Z>,
+    >,
+    grid_elements: GridElements<Z>,
+    mut commands: Commands,
+) {
+    for (ant_entity, mut thirst, orientation, position, mut initiative) in
+        ants_thirst_query.iter_mut()
+    {
+        if thirst.is_dehydrated() {
+            commands
+                .entity(ant_entity)
+                .insert((Dead, CauseOfDeath::Dehydration))
+                .remove::<Initiative>();
+        } else if thirst.is_thirsty() {
+            if !initiative.can_act() {
+                continue;
+            }
+
+            let ahead_position = orientation.get_ahead_position(position);
+            if grid_elements.is(ahead_position, Element::Water) {
+                thirst.drink(WATER_DRUNK_PER_SIP);
+                initiative.consume();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Thirst;
+    use crate::{
+        common::{
+            ant::{death::CauseOfDeath, digestion::Digestion},
+            element::Element,
+            position::Position,
+        },
+        nest_simulation::ant::NestFacing,
+        settings::Settings,
+        story_time::DEFAULT_TICKS_PER_SECOND,
+        test_support::TestSimulation,
+    };
+
+    // Confirm that an ant with nothing to drink dies of dehydration.
+    #[test]
+    fn ant_without_water_dies_of_dehydration() {
+        let settings = Settings {
+            max_thirst_time: 1,
+            ..Settings::default()
+        };
+
+        let mut simulation = TestSimulation::with_settings(
+            "
+            ...
+            ###
+            #W#
+            ###
+            ",
+            settings,
+        );
+
+        let ant_entity = simulation.nest_ant(Position::new(1, 2));
+
+        simulation.tick(DEFAULT_TICKS_PER_SECOND as usize + 1);
+
+        assert_eq!(
+            simulation.world().get::<CauseOfDeath>(ant_entity),
+            Some(&CauseOfDeath::Dehydration)
+        );
+    }
+
+    // Confirm that a thirsty ant drinks from water in front of it without using the water up. The water is walled in by
+    // rock so it can't soak into the dirt.
+    #[test]
+    fn thirsty_ant_drinks_water() {
+        let mut simulation = TestSimulation::new(
+            "
+            ....
+            RRRR
+            RWwR
+            RRRR
+            ",
+        );
+
+        let ant_entity = simulation.nest_ant(Position::new(1, 2));
+        simulation.face_nest_ant(ant_entity, NestFacing::Right);
+        simulation
+            .world_mut()
+            .get_mut::<Thirst>(ant_entity)
+            .unwrap()
+            .set_value(60.0);
+
+        simulation.tick(10);
+
+        assert!(simulation
+            .world()
+            .get::<Thirst>(ant_entity)
+            .unwrap()
+            .is_quenched());
+        assert_eq!(simulation.nest_element(Position::new(2, 2)), Element::Water);
+    }
+
+    // Confirm that a thirsty ant is given water by a well watered ant facing it.
+    #[test]
+    fn ants_share_water() {
+        let mut simulation = TestSimulation::new(
+            "
+            .....
+            #####
+            #WW.#
+            #####
+            ",
+        );
+
+        let thirsty_ant_entity = simulation.nest_ant(Position::new(1, 2));
+        simulation.face_nest_ant(thirsty_ant_entity, NestFacing::Right);
+        simulation
+            .world_mut()
+            .get_mut::<Thirst>(thirsty_ant_entity)
+            .unwrap()
+            .set_value(60.0);
+
+        let other_ant_entity = simulation.nest_ant(Position::new(2, 2));
+        simulation.face_nest_ant(other_ant_entity, NestFacing::Left);
+
+        simulation.tick(6);
+
+        let thirst = simulation
+            .world()
+            .get::<Thirst>(thirsty_ant_entity)
+            .unwrap();
+        let other_thirst = simulation.world().get::<Thirst>(other_ant_entity).unwrap();
+        assert!(thirst.value() < 60.0);
+        assert!(other_thirst.value() > 10.0);
+        // Sharing water alone doesn't feed the ant.
+        assert!(!simulation
+            .world()
+            .get::<Digestion>(thirsty_ant_entity)
+            .unwrap()
+            .is_digesting());
+    }
+}
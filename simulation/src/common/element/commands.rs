@@ -1,4 +1,4 @@
-use super::{Element, ElementBundle};
+use super::{Element, ElementBundle, FoodKind};
 use crate::common::{
     grid::{GridElements, GridElementsMut},
     position::Position,
@@ -18,6 +18,13 @@ pub trait ElementCommandsExt {
         target_element: Entity,
         zone: Z,
     );
+    fn replace_food<Z: Zone>(
+        &mut self,
+        position: Position,
+        food_kind: FoodKind,
+        target_element: Entity,
+        zone: Z,
+    );
     fn spawn_element<Z: Zone>(&mut self, position: Position, element: Element, zone: Z);
     fn toggle_element_command<C: Component, Z: Zone>(
         &mut self,
@@ -45,6 +52,21 @@ impl<'w, 's> ElementCommandsExt for Commands<'w, 's> {
         })
     }
 
+    fn replace_food<Z: Zone>(
+        &mut self,
+        position: Position,
+        food_kind: FoodKind,
+        target_element: Entity,
+        zone: Z,
+    ) {
+        self.add(ReplaceFoodCommand {
+            position,
+            target_element,
+            food_kind,
+            zone,
+        })
+    }
+
     fn spawn_element<Z: Zone>(&mut self, position: Position, element: Element, zone: Z) {
         self.add(SpawnElementCommand {
             element,
@@ -80,33 +102,74 @@ struct ReplaceElementCommand<Z: Zone> {
 
 impl<Z: Zone> Command for ReplaceElementCommand<Z> {
     fn apply(self, world: &mut World) {
-        let mut system_state: SystemState<GridElements<Z>> = SystemState::new(world);
-        let grid_elements = system_state.get(world);
+        replace_element(
+            world,
+            self.target_element,
+            self.element,
+            self.position,
+            self.zone,
+        );
+    }
+}
 
-        let existing_entity = match grid_elements.get_entity(self.position) {
-            Some(entity) => entity,
-            None => {
-                info!("No entity found at position {:?}", self.position);
-                return;
-            }
-        };
+struct ReplaceFoodCommand<Z: Zone> {
+    target_element: Entity,
+    food_kind: FoodKind,
+    position: Position,
+    zone: Z,
+}
 
-        if *existing_entity != self.target_element {
-            info!("Existing entity doesn't match the current entity.");
-            return;
+impl<Z: Zone> Command for ReplaceFoodCommand<Z> {
+    fn apply(self, world: &mut World) {
+        // FoodKind is inserted alongside Element so the food is never seen without its kind.
+        if let Some(entity) = replace_element(
+            world,
+            self.target_element,
+            Element::Food,
+            self.position,
+            self.zone,
+        ) {
+            world.entity_mut(entity).insert(self.food_kind);
+        }
+    }
+}
+
+/// Swap the element at `position` for a new one, returning the new element's entity if the swap took place.
+fn replace_element<Z: Zone>(
+    world: &mut World,
+    target_element: Entity,
+    element: Element,
+    position: Position,
+    zone: Z,
+) -> Option<Entity> {
+    let mut system_state: SystemState<GridElements<Z>> = SystemState::new(world);
+    let grid_elements = system_state.get(world);
+
+    let existing_entity = match grid_elements.get_entity(position) {
+        Some(entity) => *entity,
+        None => {
+            info!("No entity found at position {:?}", position);
+            return None;
         }
+    };
 
-        world.entity_mut(*existing_entity).despawn();
+    if existing_entity != target_element {
+        info!("Existing entity doesn't match the current entity.");
+        return None;
+    }
 
-        let entity = world
-            .spawn(ElementBundle::new(self.element, self.position, self.zone))
-            .id();
+    world.entity_mut(existing_entity).despawn();
 
-        let mut system_state: SystemState<GridElementsMut<Z>> = SystemState::new(world);
-        let mut grid_elements = system_state.get_mut(world);
+    let entity = world
+        .spawn(ElementBundle::new(element, position, zone))
+        .id();
 
-        grid_elements.set(self.position, entity);
-    }
+    let mut system_state: SystemState<GridElementsMut<Z>> = SystemState::new(world);
+    let mut grid_elements = system_state.get_mut(world);
+
+    grid_elements.set(position, entity);
+
+    Some(entity)
 }
 
 struct SpawnElementCommand<Z: Zone> {
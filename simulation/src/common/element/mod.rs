@@ -1,6 +1,9 @@
 pub mod commands;
 
-use crate::common::{position::Position, Zone};
+use crate::{
+    common::{position::Position, Zone},
+    story_time::DEFAULT_TICKS_PER_SECOND,
+};
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -20,6 +23,22 @@ pub struct Sand;
 #[reflect(Component)]
 pub struct Food;
 
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct Rock;
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct Water;
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct Mud;
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct Mold;
+
 #[derive(
     Component, Eq, Hash, PartialEq, Copy, Clone, Debug, Serialize, Deserialize, Reflect, Default,
 )]
@@ -30,6 +49,102 @@ pub enum Element {
     Dirt,
     Sand,
     Food,
+    Rock,
+    Water,
+    Mud,
+    Mold,
+}
+
+impl Element {
+    /// Rock is immovable and can't be dug. Air is nothing to dig and Water can't be picked up.
+    pub fn is_diggable(&self) -> bool {
+        !matches!(self, Element::Air | Element::Rock | Element::Water)
+    }
+}
+
+/// How many more times an element must be dug before it gives way. Elements without Hardness are excavated in one dig.
+#[derive(Component, Reflect, Default, Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[reflect(Component)]
+pub struct Hardness(pub isize);
+
+/// How long food has been lying in the nest. Like Age, freshness is tracked in whole ticks because food keeps for days.
+/// Food doesn't spoil out in the crater so it only gains Freshness once it's been brought into the nest.
+#[derive(
+    Component, Reflect, Default, Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize,
+)]
+#[reflect(Component)]
+pub struct Freshness {
+    ticks: isize,
+    max_ticks: isize,
+}
+
+impl Freshness {
+    pub fn new(max_time_seconds: isize) -> Self {
+        Self {
+            ticks: 0,
+            max_ticks: max_time_seconds * DEFAULT_TICKS_PER_SECOND,
+        }
+    }
+
+    /// How fresh the food still is, as a percentage.
+    pub fn value(&self) -> f32 {
+        (self.max_ticks - self.ticks) as f32 / self.max_ticks as f32 * 100.0
+    }
+
+    pub fn tick(&mut self) {
+        self.ticks = (self.ticks + 1).min(self.max_ticks);
+    }
+
+    pub fn is_spoiled(&self) -> bool {
+        self.ticks >= self.max_ticks
+    }
+}
+
+/// What sort of food a Food element is. Food without a FoodKind, such as food from a save which predates it, is seeds.
+#[derive(
+    Component, Eq, Hash, PartialEq, Copy, Clone, Debug, Serialize, Deserialize, Reflect, Default,
+)]
+#[reflect(Component)]
+pub enum FoodKind {
+    #[default]
+    Seed,
+    /// Honeydew and other sugary food. Quick to digest but it doesn't go far.
+    Sugar,
+    /// Insects and other protein. Slow to digest but very filling, and what larvae need to grow.
+    Protein,
+}
+
+impl FoodKind {
+    pub fn all() -> [FoodKind; 3] {
+        [FoodKind::Seed, FoodKind::Sugar, FoodKind::Protein]
+    }
+
+    /// How much of an ant's stomach is filled by eating one piece of this food, as a fraction.
+    pub fn nutrition(&self) -> f32 {
+        match self {
+            FoodKind::Seed => 0.20,
+            FoodKind::Sugar => 0.10,
+            FoodKind::Protein => 0.30,
+        }
+    }
+
+    /// How quickly this food is digested relative to seeds.
+    pub fn digestion_speed(&self) -> f32 {
+        match self {
+            FoodKind::Seed => 1.0,
+            FoodKind::Sugar => 3.0,
+            FoodKind::Protein => 0.5,
+        }
+    }
+
+    /// How much of an ant's Thirst is relieved by eating one piece of this food, as a fraction. Seeds are dry.
+    pub fn moisture(&self) -> f32 {
+        match self {
+            FoodKind::Seed => 0.0,
+            FoodKind::Sugar => 0.30,
+            FoodKind::Protein => 0.10,
+        }
+    }
 }
 
 #[derive(Bundle)]
@@ -58,6 +173,13 @@ pub fn register_element(app_type_registry: ResMut<AppTypeRegistry>) {
     app_type_registry.write().register::<Food>();
     app_type_registry.write().register::<Dirt>();
     app_type_registry.write().register::<Sand>();
+    app_type_registry.write().register::<Rock>();
+    app_type_registry.write().register::<Water>();
+    app_type_registry.write().register::<Mud>();
+    app_type_registry.write().register::<Mold>();
+    app_type_registry.write().register::<Hardness>();
+    app_type_registry.write().register::<Freshness>();
+    app_type_registry.write().register::<FoodKind>();
 }
 
 /// Element entities are represented by their Element enum, but the value of this enum isn't Queryable.
@@ -66,7 +188,16 @@ pub fn register_element(app_type_registry: ResMut<AppTypeRegistry>) {
 pub fn map_element_to_marker(
     element_query: Query<
         (Entity, &Element),
-        (Without<Air>, Without<Dirt>, Without<Sand>, Without<Food>),
+        (
+            Without<Air>,
+            Without<Dirt>,
+            Without<Sand>,
+            Without<Food>,
+            Without<Rock>,
+            Without<Water>,
+            Without<Mud>,
+            Without<Mold>,
+        ),
     >,
     mut commands: Commands,
 ) {
@@ -84,6 +215,18 @@ pub fn map_element_to_marker(
             Element::Food => {
                 commands.entity(entity).insert(Food);
             }
+            Element::Rock => {
+                commands.entity(entity).insert(Rock);
+            }
+            Element::Water => {
+                commands.entity(entity).insert(Water);
+            }
+            Element::Mud => {
+                commands.entity(entity).insert(Mud);
+            }
+            Element::Mold => {
+                commands.entity(entity).insert(Mold);
+            }
         }
     }
 }
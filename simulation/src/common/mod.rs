@@ -11,11 +11,14 @@ use crate::{
 
 use self::{
     ant::{
-        death::on_ants_add_dead,
+        age::ants_age,
+        caste::{ants_assign_caste, ants_migrate_caste},
+        death::{corpses_decay, on_ants_add_dead},
         digestion::ants_digestion,
         hunger::{ants_hunger_act, ants_hunger_regurgitate, ants_hunger_tick, AntAteFoodEvent},
         initiative::ants_initiative,
         register_ant,
+        thirst::{ants_thirst_act, ants_thirst_tick},
     },
     element::register_element,
     pheromone::register_pheromone,
@@ -28,17 +31,26 @@ use super::{
         initialize_external_event_resources, process_external_event,
         remove_external_event_resources,
     },
+    replay::{play_replay_events, remove_replay_resources, start_replay_recording, ReplayPlayer},
     save::{
-        bind_save_onbeforeunload, delete_save_file, initialize_save_resources, load_save_file,
-        remove_save_resources, save, unbind_save_onbeforeunload,
+        bind_save_onbeforeunload, close_save_slot, initialize_save_resources, load_save_file,
+        remove_save_resources, save, save_on_exit, unbind_save_onbeforeunload,
+    },
+    scenario::{apply_scenario_settings, remove_scenario_resources, Scenario},
+    settings::{
+        initialize_settings_resources, register_settings, remove_settings_resources,
+        seed_global_rng,
     },
-    settings::{initialize_settings_resources, register_settings, remove_settings_resources},
     story_time::{
         initialize_story_time_resources, register_story_time, remove_story_time_resources,
         setup_story_time, update_story_elapsed_ticks, update_story_real_world_time,
         update_time_scale, StoryPlaybackState,
     },
-    CleanupSet, FinishSetupSet, SimulationTickSet,
+    weather::{
+        initialize_weather_resources, register_weather, remove_weather_resources, update_weather,
+    },
+    world_state_hash::{record_world_state_hash, WorldStateHashLog},
+    CleanupSet, FinishSetupSet, SimulationTickSet, SimulationZoneSet,
 };
 use bevy::prelude::*;
 
@@ -70,7 +82,9 @@ pub enum LoadProgress {
     NotStarted,
     Loading,
     Success,
-    Failure,
+    /// Optionally explains, in a user-facing way, why loading failed.
+    /// No reason is given when failure is expected such as when there's no save to load.
+    Failure(Option<String>),
 }
 
 #[derive(Resource, Default, Debug)]
@@ -98,6 +112,7 @@ impl Plugin for CommonSimulationPlugin {
                 register_settings,
                 register_common,
                 register_story_time,
+                register_weather,
                 register_element,
                 register_pheromone,
                 register_ant,
@@ -115,7 +130,23 @@ impl Plugin for CommonSimulationPlugin {
                 .chain(),
         );
 
-        app.add_systems(OnExit(AppState::MainMenu), initialize_settings_resources);
+        // Only create a new world when the user is starting a new story from the main menu.
+        // The main menu can also transition back to Loading to resume a saved colony.
+        app.add_systems(
+            OnTransition {
+                exited: AppState::MainMenu,
+                entered: AppState::FinishSetup,
+            },
+            (
+                initialize_settings_resources,
+                apply_deferred,
+                apply_scenario_settings.run_if(resource_exists::<Scenario>),
+                seed_global_rng,
+                start_replay_recording,
+            )
+                .chain()
+                .in_set(SimulationZoneSet::Common),
+        );
 
         app.add_systems(
             OnEnter(AppState::FinishSetup),
@@ -127,6 +158,7 @@ impl Plugin for CommonSimulationPlugin {
                     set_rate_of_time,
                 )
                     .chain(),
+                initialize_weather_resources,
                 initialize_external_event_resources,
                 bind_save_onbeforeunload,
                 // TODO: This needs to run once before Simulation runs because UI update runs before first simulation tick.
@@ -145,6 +177,7 @@ impl Plugin for CommonSimulationPlugin {
         app.add_systems(
             FixedUpdate,
             (
+                play_replay_events.run_if(resource_exists::<ReplayPlayer>),
                 process_external_event::<AtNest>,
                 process_external_event::<AtCrater>,
                 apply_deferred,
@@ -160,28 +193,36 @@ impl Plugin for CommonSimulationPlugin {
             FixedUpdate,
             (
                 apply_deferred,
+                update_weather,
                 (
                     ants_digestion::<AtNest>,
                     ants_digestion::<AtCrater>,
                     ants_hunger_tick::<AtNest>,
                     ants_hunger_tick::<AtCrater>,
+                    ants_thirst_tick::<AtNest>,
+                    ants_thirst_tick::<AtCrater>,
                     ants_hunger_act::<AtNest>,
                     ants_hunger_act::<AtCrater>,
+                    ants_thirst_act::<AtNest>,
+                    ants_thirst_act::<AtCrater>,
                     apply_deferred,
                     ants_hunger_regurgitate::<AtNest>,
                     ants_hunger_regurgitate::<AtCrater>,
                     apply_deferred,
                 )
                     .chain(),
+                (ants_assign_caste, ants_migrate_caste, apply_deferred).chain(),
+                (ants_age::<AtNest>, ants_age::<AtCrater>, apply_deferred).chain(),
                 on_ants_add_dead::<AtNest>,
                 on_ants_add_dead::<AtCrater>,
+                (corpses_decay, apply_deferred).chain(),
             )
                 .chain()
                 .run_if(
                     in_state(AppState::TellStory { ended: false })
                         .and_then(not(in_state(StoryPlaybackState::Paused))),
                 )
-                .in_set(SimulationTickSet::SimulationTick),
+                .in_set(SimulationZoneSet::Common),
         );
 
         app.add_systems(
@@ -193,6 +234,7 @@ impl Plugin for CommonSimulationPlugin {
                 ants_initiative::<AtNest>,
                 ants_initiative::<AtCrater>,
                 update_story_elapsed_ticks,
+                record_world_state_hash.run_if(resource_exists::<WorldStateHashLog>),
             )
                 .chain()
                 .in_set(SimulationTickSet::PostSimulationTick)
@@ -239,19 +281,31 @@ impl Plugin for CommonSimulationPlugin {
             // TODO: It's weird (incorrect) that this is declared in `simulation` but that the `save` directory is external to simulation.
             // I think this should get moved up a level.
             save.run_if(
-                in_state(AppState::TellStory { ended: false }).and_then(in_state(StoryPlaybackState::Playing)),
+                in_state(AppState::TellStory { ended: false })
+                    .and_then(in_state(StoryPlaybackState::Playing)),
             ),
         );
 
+        // AppExit is sent during Update so checking for it in Last ensures the save is written before the app closes.
+        app.add_systems(Last, save_on_exit);
+
+        // Close the save slot before any models are despawned so that a snapshot can still be taken of them.
+        app.add_systems(
+            OnEnter(AppState::Cleanup),
+            close_save_slot.in_set(CleanupSet::BeforeSimulationCleanup),
+        );
+
         app.add_systems(
             OnEnter(AppState::Cleanup),
             (
                 unbind_save_onbeforeunload,
-                delete_save_file,
                 remove_story_time_resources,
+                remove_weather_resources,
                 remove_settings_resources,
                 remove_save_resources,
                 remove_external_event_resources,
+                remove_replay_resources,
+                remove_scenario_resources,
                 remove_loading_resources,
                 restart,
             )
@@ -9,7 +9,9 @@ use std::marker::PhantomData;
 
 /// TODO: It's weird that Pheromone defaults to Tunnel when, in reality, no default would be more sensible.
 /// TODO: It's possible that Pheromone should be split in two: CraterPheromone and NestPheromone. There's no overlap between the two.
-#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[derive(
+    Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default, Hash,
+)]
 #[reflect(Component)]
 pub enum Pheromone {
     #[default]
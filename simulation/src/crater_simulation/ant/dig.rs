@@ -73,3 +73,41 @@ pub fn ants_dig(
         *orientation = orientation.turn_around();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        common::{element::Element, position::Position},
+        settings::Settings,
+        test_support::TestSimulation,
+    };
+
+    // Confirm that a worker in the crater picks up food beside it.
+    #[test]
+    fn worker_picks_up_food() {
+        let mut simulation = TestSimulation::with_crater(
+            "
+            ...
+            ###
+            ",
+            "
+            .f...
+            fWf..
+            .f...
+            .....
+            .....
+            ",
+            Settings::default(),
+        );
+
+        let ant_entity = simulation.crater_ant(Position::new(1, 1));
+
+        simulation.tick(10);
+
+        assert_eq!(
+            simulation.inventory_element(ant_entity),
+            Some(Element::Food)
+        );
+        assert_eq!(simulation.crater_map().matches('f').count(), 3);
+    }
+}
@@ -1,10 +1,13 @@
 use bevy::prelude::*;
 use bevy_turborand::{DelegatedRng, GlobalRng};
-use serde::{Deserialize, Serialize};
 
 use crate::{
     common::{
-        ant::initiative::Initiative,
+        ant::{
+            commands::AntCommandsExt,
+            initiative::Initiative,
+            task::{AntTask, Task},
+        },
         pheromone::{commands::PheromoneCommandsExt, Pheromone, PheromoneStrength},
         position::Position,
     },
@@ -12,30 +15,24 @@ use crate::{
     settings::Settings,
 };
 
-// TODO: Need to persist LeavingNest and LeavingFood
-#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
-#[reflect(Component)]
-pub struct LeavingNest(pub f32);
-
-#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
-#[reflect(Component)]
-pub struct LeavingFood(pub f32);
-
 pub fn ants_emit_pheromone(
-    mut ants_query: Query<
-        (
-            Entity,
-            &Position,
-            &Initiative,
-            AnyOf<(&mut LeavingFood, &mut LeavingNest)>,
-        ),
-        With<AtCrater>,
-    >,
+    mut ants_query: Query<(Entity, &Position, &Initiative, &mut AntTask), With<AtCrater>>,
     mut commands: Commands,
     settings: Res<Settings>,
     mut rng: ResMut<GlobalRng>,
 ) {
-    for (ant_entity, position, initiative, (leaving_food, leaving_nest)) in ants_query.iter_mut() {
+    for (ant_entity, position, initiative, mut ant_task) in ants_query.iter_mut() {
+        // Each pheromone laid is a little weaker than the last so the trail leads back to where it started.
+        let (pheromone, strength, next_task) = match *ant_task.current() {
+            Task::LeavingFood(strength) => {
+                (Pheromone::Food, strength, Task::LeavingFood(strength - 1.0))
+            }
+            Task::LeavingNest(strength) => {
+                (Pheromone::Nest, strength, Task::LeavingNest(strength - 1.0))
+            }
+            _ => continue,
+        };
+
         // Ants don't move every tick, if initative isn't checked then will leave multiple pheromone entries on same tile
         if !initiative.can_move() {
             continue;
@@ -45,35 +42,18 @@ pub fn ants_emit_pheromone(
             continue;
         }
 
-        if let Some(mut leaving_food) = leaving_food {
-            commands.spawn_pheromone(
-                *position,
-                Pheromone::Food,
-                // TODO: Read 50 from config
-                PheromoneStrength::new(leaving_food.0, 50.0),
-                AtCrater,
-            );
+        commands.spawn_pheromone(
+            *position,
+            pheromone,
+            // TODO: Read 50 from config
+            PheromoneStrength::new(strength, 50.0),
+            AtCrater,
+        );
 
-            leaving_food.0 -= 1.0;
+        *ant_task.current_mut() = next_task;
 
-            if leaving_food.0 <= 0.0 {
-                commands.entity(ant_entity).remove::<LeavingFood>();
-            }
-        }
-
-        if let Some(mut leaving_nest) = leaving_nest {
-            commands.spawn_pheromone(
-                *position,
-                Pheromone::Nest,
-                PheromoneStrength::new(leaving_nest.0, 50.0),
-                AtCrater,
-            );
-
-            leaving_nest.0 -= 1.0;
-
-            if leaving_nest.0 <= 0.0 {
-                commands.entity(ant_entity).remove::<LeavingNest>();
-            }
+        if strength - 1.0 <= 0.0 {
+            commands.finish_task(ant_entity, next_task);
         }
     }
 }
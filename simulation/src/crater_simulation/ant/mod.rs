@@ -7,18 +7,17 @@ pub mod wander;
 
 use crate::common::position::Position;
 
-use self::emit_pheromone::{LeavingFood, LeavingNest};
 use bevy::prelude::*;
 use bevy_turborand::{DelegatedRng, GlobalRng};
 use serde::{Deserialize, Serialize};
 
 pub fn register_ant(app_type_registry: ResMut<AppTypeRegistry>) {
-    app_type_registry.write().register::<LeavingFood>();
-    app_type_registry.write().register::<LeavingNest>();
     app_type_registry.write().register::<CraterOrientation>();
 }
 
-#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[derive(
+    Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default, Hash,
+)]
 #[reflect(Component)]
 pub enum CraterOrientation {
     #[default]
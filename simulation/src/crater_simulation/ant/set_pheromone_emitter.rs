@@ -1,8 +1,9 @@
-use crate::{common::ant::AntInventory, crater_simulation::crater::AtCrater};
+use crate::{
+    common::ant::{commands::AntCommandsExt, task::Task, AntInventory},
+    crater_simulation::crater::AtCrater,
+};
 use bevy::prelude::*;
 
-use super::emit_pheromone::{LeavingFood, LeavingNest};
-
 pub fn ants_set_pheromone_emitter(
     ants_query: Query<(Entity, Ref<AntInventory>), With<AtCrater>>,
     mut commands: Commands,
@@ -13,11 +14,7 @@ pub fn ants_set_pheromone_emitter(
 
     for (ant_entity, inventory) in ants_query.iter() {
         if inventory.is_changed() && inventory.0.is_some() {
-            commands
-                .entity(ant_entity)
-                // TODO: It would be nice to convey through the type system that only one can be applied at a time
-                .insert(LeavingFood(50.0))
-                .remove::<LeavingNest>();
+            commands.start_task(ant_entity, Task::LeavingFood(50.0));
         }
     }
 }
@@ -3,10 +3,15 @@ use bevy_turborand::{DelegatedRng, GlobalRng};
 
 use crate::{
     common::{
-        ant::{initiative::Initiative, AntInventory},
+        ant::{
+            commands::AntCommandsExt,
+            initiative::Initiative,
+            task::{AntTask, Task},
+            AntInventory,
+        },
         position::Position,
     },
-    crater_simulation::{ant::emit_pheromone::LeavingNest, crater::AtCrater},
+    crater_simulation::crater::AtCrater,
     nest_simulation::{
         ant::{NestAngle, NestFacing, NestOrientation},
         nest::{AtNest, Nest},
@@ -14,7 +19,7 @@ use crate::{
     settings::Settings,
 };
 
-use super::{emit_pheromone::LeavingFood, CraterOrientation};
+use super::CraterOrientation;
 
 // TODO: Maybe put this in common since it relies on knowledge of AtCrater and AtNest
 
@@ -28,6 +33,7 @@ pub fn ants_travel_to_nest(
             &CraterOrientation,
             &Position,
             &AntInventory,
+            &AntTask,
         ),
         With<AtCrater>,
     >,
@@ -38,7 +44,9 @@ pub fn ants_travel_to_nest(
 ) {
     let nest = nest_query.single();
 
-    for (ant_entity, mut initiative, orientation, position, inventory) in ants_query.iter_mut() {
+    for (ant_entity, mut initiative, orientation, position, inventory, ant_task) in
+        ants_query.iter_mut()
+    {
         if !initiative.can_move() {
             continue;
         }
@@ -64,9 +72,7 @@ pub fn ants_travel_to_nest(
         ant_entity_commands
             .remove::<AtCrater>()
             .remove::<CraterOrientation>()
-            .insert(AtNest)
-            .remove::<LeavingNest>()
-            .remove::<LeavingFood>();
+            .insert(AtNest);
 
         // TODO: There could be dirt/sand/food at the nest entrance - need to search and find a good place to put ant
         // Make sure the ant is on its feet when it enters the nest
@@ -91,6 +97,11 @@ pub fn ants_travel_to_nest(
             }
         }
 
+        // Pheromone trails are only laid in the crater.
+        if let task @ (Task::LeavingNest(_) | Task::LeavingFood(_)) = *ant_task.current() {
+            commands.finish_task(ant_entity, task);
+        }
+
         initiative.consume();
     }
 }
@@ -3,21 +3,27 @@ pub mod emit_pheromone;
 use crate::{
     common::{
         ant::{
-            digestion::Digestion, hunger::Hunger, initiative::Initiative, AntBundle, AntColor,
-            AntInventory, AntName, AntRole,
+            age::Age,
+            digestion::Digestion,
+            hunger::Hunger,
+            initiative::Initiative,
+            task::{AntTask, Task},
+            thirst::Thirst,
+            AntBundle, AntColor, AntInventory, AntName, AntRole,
         },
-        element::{Element, ElementBundle},
+        element::{Element, ElementBundle, FoodKind},
         grid::{ElementEntityPositionCache, Grid},
         position::Position,
         Zone,
     },
+    scenario::Scenario,
     settings::Settings,
 };
 use bevy::{prelude::*, utils::HashSet};
 use bevy_turborand::{DelegatedRng, GlobalRng};
 use serde::{Deserialize, Serialize};
 
-use super::ant::{emit_pheromone::LeavingNest, CraterOrientation};
+use super::ant::CraterOrientation;
 
 #[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
 #[reflect(Component)]
@@ -75,9 +81,21 @@ pub fn insert_crater_grid(
 /// creating a new world and loading an existing world.
 pub fn spawn_crater_elements(
     settings: Res<Settings>,
+    scenario: Option<Res<Scenario>>,
     mut commands: Commands,
     mut rng: ResMut<GlobalRng>,
 ) {
+    if let Some(crater_map) = scenario
+        .as_ref()
+        .and_then(|scenario| scenario.crater.as_ref())
+    {
+        for &(position, element) in crater_map.elements.iter() {
+            commands.spawn(ElementBundle::new(element, position, AtCrater));
+        }
+
+        return;
+    }
+
     let food_positions = spawn_food(&settings, &mut rng, &mut commands);
 
     // Spawn Air everywhere food wasn't spawned
@@ -97,7 +115,8 @@ fn spawn_food(
     rng: &mut ResMut<GlobalRng>,
     commands: &mut Commands,
 ) -> HashSet<Position> {
-    let food_blocks_to_spawn = 3;
+    // Each block is a different kind of food - a seed cache, honeydew and an insect carcass.
+    let food_kinds = FoodKind::all();
 
     // Center of the crater
     let center_x = settings.crater_width / 2;
@@ -111,7 +130,7 @@ fn spawn_food(
 
     let mut food_positions = HashSet::new();
 
-    for _ in 0..food_blocks_to_spawn {
+    for food_kind in food_kinds {
         let mut valid_start_position_found = false;
         let mut start_x = 0;
         let mut start_y = 0;
@@ -138,7 +157,10 @@ fn spawn_food(
                 if x < settings.crater_width && y < settings.crater_height {
                     let food_position = Position::new(x, y);
 
-                    commands.spawn(ElementBundle::new(Element::Food, food_position, AtCrater));
+                    commands.spawn((
+                        ElementBundle::new(Element::Food, food_position, AtCrater),
+                        food_kind,
+                    ));
 
                     food_positions.insert(food_position);
                 }
@@ -151,11 +173,48 @@ fn spawn_food(
 
 pub fn spawn_crater_ants(
     settings: Res<Settings>,
+    scenario: Option<Res<Scenario>>,
     mut rng: ResMut<GlobalRng>,
     mut commands: Commands,
 ) {
     let mut rng = rng.reborrow();
 
+    // Authored maps place every ant themselves.
+    if let Some(crater_map) = scenario
+        .as_ref()
+        .and_then(|scenario| scenario.crater.as_ref())
+    {
+        for &(position, ant_role) in crater_map.ants.iter() {
+            let name = match ant_role {
+                AntRole::Queen => AntName(String::from("Queen")),
+                AntRole::Worker => AntName::random(&mut rng),
+            };
+
+            let entity = commands
+                .spawn(AntBundle::new(
+                    position,
+                    AntColor(settings.ant_color),
+                    AntInventory::default(),
+                    ant_role,
+                    name,
+                    Initiative::new(&mut rng),
+                    AtCrater,
+                    Hunger::new(settings.max_hunger_time),
+                    Digestion::new(settings.max_digestion_time),
+                    Thirst::new(settings.max_thirst_time),
+                    Age::for_role(ant_role, &settings),
+                ))
+                .id();
+
+            commands
+                .entity(entity)
+                .insert(CraterOrientation::random(&mut rng))
+                .insert(AntTask::new(Task::LeavingNest(50.0)));
+        }
+
+        return;
+    }
+
     // NOTE: Just spawning some ants for prototyping
     (0..0).for_each(|_| {
         let x_offset = match rng.bool() {
@@ -183,13 +242,15 @@ pub fn spawn_crater_ants(
                 AtCrater,
                 Hunger::new(settings.max_hunger_time),
                 Digestion::new(settings.max_digestion_time),
+                Thirst::new(settings.max_thirst_time),
+                Age::for_role(AntRole::Worker, &settings),
             ))
             .id();
 
         commands
             .entity(entity)
             .insert(CraterOrientation::random(&mut rng))
-            .insert(LeavingNest(50.0));
+            .insert(AntTask::new(Task::LeavingNest(50.0)));
     });
 }
 
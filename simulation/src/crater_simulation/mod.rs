@@ -12,7 +12,7 @@ use crate::{
         },
     },
     story_time::StoryPlaybackState,
-    SimulationTickSet,
+    SimulationZoneSet,
 };
 
 use self::{
@@ -27,10 +27,7 @@ use self::{
         register_crater, spawn_crater, spawn_crater_ants, spawn_crater_elements, AtCrater, Crater,
     },
 };
-use super::{
-    despawn_model, insert_crater_grid, settings::initialize_settings_resources, AppState,
-    CleanupSet, FinishSetupSet,
-};
+use super::{despawn_model, insert_crater_grid, AppState, CleanupSet, FinishSetupSet};
 use bevy::prelude::*;
 
 pub struct CraterSimulationPlugin;
@@ -40,16 +37,19 @@ impl Plugin for CraterSimulationPlugin {
         app.add_systems(Startup, (register_crater, register_ant));
 
         app.add_systems(
-            OnExit(AppState::MainMenu),
+            OnTransition {
+                exited: AppState::MainMenu,
+                entered: AppState::FinishSetup,
+            },
             (
                 // Call `apply_deferred` to ensure Settings (via `initialize_settings_resources`) is available for use.
                 apply_deferred,
                 spawn_crater,
                 apply_deferred,
-                (spawn_crater_elements, spawn_crater_ants),
+                (spawn_crater_elements, spawn_crater_ants).chain(),
             )
                 .chain()
-                .after(initialize_settings_resources),
+                .in_set(SimulationZoneSet::Crater),
         );
 
         app.add_systems(
@@ -81,7 +81,7 @@ impl Plugin for CraterSimulationPlugin {
                         .and_then(not(in_state(StoryPlaybackState::Paused))),
                 )
                 .chain()
-                .in_set(SimulationTickSet::SimulationTick),
+                .in_set(SimulationZoneSet::Crater),
         );
 
         app.add_systems(
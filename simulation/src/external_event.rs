@@ -1,33 +1,48 @@
 use crate::{
     common::{
         ant::{
-            commands::AntCommandsExt, initiative::Initiative, AntColor, AntInventory, AntName,
-            AntRole, Dead,
+            commands::AntCommandsExt,
+            death::CauseOfDeath,
+            initiative::Initiative,
+            task::{AntTask, Task},
+            Ant, AntColor, AntInventory, AntName, AntRole, Dead, InventoryItem,
         },
-        element::{commands::ElementCommandsExt, Element},
+        element::{commands::ElementCommandsExt, Element, FoodKind},
         grid::GridElements,
         position::Position,
         Zone,
     },
     crater_simulation::{ant::CraterOrientation, crater::AtCrater},
     nest_simulation::{
-        ant::{NestAngle, NestFacing, NestOrientation},
-        nest::AtNest,
+        ant::{
+            nesting::get_nest_site_range, nuptial_flight::Flight, nursing::is_in_chamber,
+            NestAngle, NestFacing, NestOrientation,
+        },
+        brood::Brood,
+        granary::{is_in_granary, Granary},
+        nest::{AtNest, Nest},
     },
+    replay::{ReplayExternalSimulationEvent, ReplayRecorder},
     settings::Settings,
+    story_time::StoryTime,
 };
 use bevy::prelude::*;
 use bevy_turborand::GlobalRng;
+use serde::{Deserialize, Serialize};
 
-#[derive(Event, PartialEq, Copy, Clone, Debug)]
+#[derive(Event, PartialEq, Copy, Clone, Debug, Serialize, Deserialize)]
 pub enum ExternalSimulationEvent<Z: Zone> {
     DespawnElement(Position, Z),
-    SpawnFood(Position, Z),
+    SpawnFood(Position, FoodKind, Z),
     SpawnDirt(Position, Z),
     SpawnSand(Position, Z),
     KillAnt(Position, Z),
     SpawnWorkerAnt(Position, Z),
     DespawnWorkerAnt(Position, Z),
+    SpawnRock(Position, Z),
+    SpawnWater(Position, Z),
+    LandQueen(Position, Z),
+    DesignateGranary(Position, Z),
 }
 
 pub fn initialize_external_event_resources(mut commands: Commands) {
@@ -44,20 +59,42 @@ pub fn remove_external_event_resources(mut commands: Commands) {
 /// Process user input events at the start of the FixedUpdate simulation loop.
 /// Need to process them manually because they'd be cleared at the end of the next Update
 /// which might occur before the next time FixedUpdate runs.
+/// Processed events are recorded, when recording, so that the story can be replayed.
 pub fn process_external_event<Z: Zone + Copy>(
     mut external_simulation_events: ResMut<Events<ExternalSimulationEvent<Z>>>,
     mut commands: Commands,
     settings: Res<Settings>,
     mut rng: ResMut<GlobalRng>,
-    ants_query: Query<(Entity, &Position, &AntRole, &AntInventory), With<Z>>,
+    ants_query: Query<
+        (
+            Entity,
+            &Position,
+            &AntRole,
+            &AntInventory,
+            &AntTask,
+            Has<Dead>,
+        ),
+        With<Z>,
+    >,
+    carried_query: Query<(), Or<(With<Brood>, With<Ant>)>>,
+    granary_query: Query<(Entity, &Position), (With<Granary>, With<Z>)>,
+    nest_query: Query<&Nest>,
     grid_elements: GridElements<Z>,
-) {
+    story_time: Res<StoryTime>,
+    mut replay_recorder: Option<ResMut<ReplayRecorder>>,
+) where
+    ExternalSimulationEvent<Z>: Into<ReplayExternalSimulationEvent>,
+{
     for event in external_simulation_events.drain() {
+        if let Some(replay_recorder) = replay_recorder.as_mut() {
+            replay_recorder.record(story_time.elapsed_ticks(), event);
+        }
+
         match event {
-            ExternalSimulationEvent::SpawnFood(grid_position, zone) => {
+            ExternalSimulationEvent::SpawnFood(grid_position, food_kind, zone) => {
                 if grid_elements.is(grid_position, Element::Air) {
                     let entity = grid_elements.entity(grid_position);
-                    commands.replace_element(grid_position, Element::Food, *entity, zone);
+                    commands.replace_food(grid_position, food_kind, *entity, zone);
                 }
             }
             ExternalSimulationEvent::SpawnSand(grid_position, zone) => {
@@ -72,6 +109,18 @@ pub fn process_external_event<Z: Zone + Copy>(
                     commands.replace_element(grid_position, Element::Dirt, *entity, zone);
                 }
             }
+            ExternalSimulationEvent::SpawnRock(grid_position, zone) => {
+                if grid_elements.is(grid_position, Element::Air) {
+                    let entity = grid_elements.entity(grid_position);
+                    commands.replace_element(grid_position, Element::Rock, *entity, zone);
+                }
+            }
+            ExternalSimulationEvent::SpawnWater(grid_position, zone) => {
+                if grid_elements.is(grid_position, Element::Air) {
+                    let entity = grid_elements.entity(grid_position);
+                    commands.replace_element(grid_position, Element::Water, *entity, zone);
+                }
+            }
             ExternalSimulationEvent::DespawnElement(grid_position, zone) => {
                 // TODO: Why is this allowed to fail?
                 if let Some(entity) = grid_elements.get_entity(grid_position) {
@@ -107,27 +156,164 @@ pub fn process_external_event<Z: Zone + Copy>(
                 }
             }
             ExternalSimulationEvent::KillAnt(grid_position, _zone) => {
-                if let Some((entity, _, _, _)) = ants_query
+                // Corpses keep their original cause of death.
+                if let Some((entity, _, _, _, _, _)) = ants_query
                     .iter()
-                    .find(|(_, &position, _, _)| position == grid_position)
+                    .find(|(_, &position, _, _, _, is_dead)| position == grid_position && !is_dead)
                 {
-                    commands.entity(entity).insert(Dead).remove::<Initiative>();
+                    commands
+                        .entity(entity)
+                        .insert((Dead, CauseOfDeath::Killed))
+                        .remove::<Initiative>();
                 }
             }
             ExternalSimulationEvent::DespawnWorkerAnt(grid_position, _zone) => {
-                if let Some((ant_entity, _, _, inventory)) =
-                    ants_query.iter().find(|(_, &position, &role, _)| {
+                if let Some((ant_entity, &ant_position, _, inventory, _, _)) =
+                    ants_query.iter().find(|(_, &position, &role, _, _, _)| {
                         position == grid_position && role == AntRole::Worker
                     })
                 {
                     // TODO: This should happen automatically when an ant is despawned
-                    if let Some(element_entity) = &inventory.0 {
-                        commands.entity(*element_entity).despawn();
+                    if let Some(carried_entity) = inventory.0 {
+                        if carried_query.contains(carried_entity) {
+                            // Brood and corpses outlive their carrier, as they do when it dies, and are left where it was.
+                            commands
+                                .entity(carried_entity)
+                                .insert(ant_position)
+                                .remove::<InventoryItem>();
+                        } else {
+                            commands.entity(carried_entity).despawn();
+                        }
                     }
 
                     commands.entity(ant_entity).despawn_recursive();
                 }
             }
+            ExternalSimulationEvent::LandQueen(grid_position, _zone) => {
+                // Queens can only be shown where to land while they're still circling overhead.
+                if let Some((ant_entity, _, _, _, _, _)) =
+                    ants_query.iter().find(|(_, _, &role, _, ant_task, _)| {
+                        role == AntRole::Queen
+                            && matches!(ant_task.current(), Task::Flying(Flight::Circling(_)))
+                    })
+                {
+                    let nest_site_range = get_nest_site_range(&settings);
+                    let landing_x = grid_position
+                        .x
+                        .clamp(*nest_site_range.start(), *nest_site_range.end());
+
+                    commands.start_task(ant_entity, Task::Flying(Flight::Landing(landing_x)));
+                }
+            }
+            ExternalSimulationEvent::DesignateGranary(grid_position, zone) => {
+                // Granaries are underground chambers so only make sense in the nest.
+                if !zone.is_at_nest() {
+                    continue;
+                }
+
+                // Designating somewhere which is already a granary undoes it.
+                if let Some((granary_entity, _)) = granary_query
+                    .iter()
+                    .find(|(_, granary_position)| is_in_granary(&grid_position, granary_position))
+                {
+                    commands.entity(granary_entity).despawn();
+                    continue;
+                }
+
+                if is_in_chamber(grid_position, nest_query.single(), &grid_elements) {
+                    commands.spawn((Granary, grid_position, zone));
+                }
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ExternalSimulationEvent;
+    use crate::{
+        common::{
+            ant::{
+                death::CauseOfDeath, initiative::Initiative, Ant, AntInventory, Dead, InventoryItem,
+            },
+            position::Position,
+        },
+        nest_simulation::{brood::BroodStage, nest::AtNest},
+        test_support::TestSimulation,
+    };
+    use bevy::prelude::*;
+
+    // Confirm that despawning a worker which is carrying brood leaves the brood where the worker was.
+    #[test]
+    fn despawned_worker_sets_down_carried_brood() {
+        let mut simulation = TestSimulation::new(
+            "
+            ....
+            ####
+            #W.#
+            ####
+            ",
+        );
+        let ant_entity = simulation.nest_ant(Position::new(1, 2));
+        let brood_entity = simulation.spawn_brood(Position::new(1, 2), BroodStage::Egg);
+
+        let world = simulation.world_mut();
+        world
+            .entity_mut(brood_entity)
+            .remove::<Position>()
+            .insert(InventoryItem);
+        world.get_mut::<AntInventory>(ant_entity).unwrap().0 = Some(brood_entity);
+        world
+            .resource_mut::<Events<ExternalSimulationEvent<AtNest>>>()
+            .send(ExternalSimulationEvent::DespawnWorkerAnt(
+                Position::new(1, 2),
+                AtNest,
+            ));
+
+        simulation.tick(1);
+
+        let world = simulation.world_mut();
+        assert_eq!(
+            world.query_filtered::<(), With<Ant>>().iter(world).count(),
+            0
+        );
+        assert_eq!(
+            world.get::<Position>(brood_entity),
+            Some(&Position::new(1, 2))
+        );
+        assert!(world.get::<InventoryItem>(brood_entity).is_none());
+    }
+
+    // Confirm that killing a corpse doesn't overwrite how the ant actually died.
+    #[test]
+    fn killing_corpse_keeps_cause_of_death() {
+        let mut simulation = TestSimulation::new(
+            "
+            ....
+            ####
+            #W.#
+            ####
+            ",
+        );
+        let ant_entity = simulation.nest_ant(Position::new(1, 2));
+
+        let world = simulation.world_mut();
+        world
+            .entity_mut(ant_entity)
+            .insert((Dead, CauseOfDeath::OldAge))
+            .remove::<Initiative>();
+        world
+            .resource_mut::<Events<ExternalSimulationEvent<AtNest>>>()
+            .send(ExternalSimulationEvent::KillAnt(
+                Position::new(1, 2),
+                AtNest,
+            ));
+
+        simulation.tick(1);
+
+        assert_eq!(
+            simulation.world().get::<CauseOfDeath>(ant_entity),
+            Some(&CauseOfDeath::OldAge)
+        );
+    }
+}
@@ -3,9 +3,15 @@ pub mod common;
 pub mod crater_simulation;
 pub mod external_event;
 pub mod nest_simulation;
+pub mod replay;
 pub mod save;
+pub mod scenario;
 pub mod settings;
 pub mod story_time;
+#[cfg(test)]
+pub(crate) mod test_support;
+pub mod weather;
+pub mod world_state_hash;
 
 use self::{app_state::AppState, common::despawn_model, story_time::StoryPlaybackState};
 use bevy::prelude::*;
@@ -41,6 +47,15 @@ pub enum SimulationTickSet {
     Last,
 }
 
+/// Bevy doesn't run unordered systems in a consistent order between runs, not even when running single-threaded.
+/// Zones share GlobalRng so, for a seeded colony to be reproducible, their systems are ordered explicitly when spawning and simulating.
+#[derive(SystemSet, Debug, PartialEq, Eq, Clone, Hash)]
+pub enum SimulationZoneSet {
+    Common,
+    Nest,
+    Crater,
+}
+
 pub struct SimulationPlugin;
 
 impl Plugin for SimulationPlugin {
@@ -75,6 +90,30 @@ impl Plugin for SimulationPlugin {
                 .run_if(in_state(AppState::TellStory { ended: false })),
         );
 
+        app.configure_sets(
+            OnTransition {
+                exited: AppState::MainMenu,
+                entered: AppState::FinishSetup,
+            },
+            (
+                SimulationZoneSet::Common,
+                SimulationZoneSet::Nest,
+                SimulationZoneSet::Crater,
+            )
+                .chain(),
+        );
+
+        app.configure_sets(
+            FixedUpdate,
+            (
+                SimulationZoneSet::Common,
+                SimulationZoneSet::Nest,
+                SimulationZoneSet::Crater,
+            )
+                .chain()
+                .in_set(SimulationTickSet::SimulationTick),
+        );
+
         app.configure_sets(
             OnEnter(AppState::Cleanup),
             (
@@ -1,20 +1,17 @@
 use crate::{
     common::{
-        ant::{
-            commands::AntCommandsExt, initiative::Initiative, AntColor, AntInventory, AntName,
-            AntRole,
-        },
+        ant::{initiative::Initiative, AntColor},
         position::Position,
     },
-    nest_simulation::nest::AtNest,
+    nest_simulation::{brood::BroodBundle, nest::AtNest},
+    settings::Settings,
     story_time::DEFAULT_TICKS_PER_SECOND,
 };
 
 use bevy::prelude::*;
-use bevy_turborand::GlobalRng;
 use serde::{Deserialize, Serialize};
 
-use super::{NestAngle, NestFacing, NestOrientation};
+use super::NestOrientation;
 
 #[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
 #[reflect(Component)]
@@ -68,8 +65,8 @@ pub fn ants_birthing(
         ),
         With<AtNest>,
     >,
+    settings: Res<Settings>,
     mut commands: Commands,
-    mut rng: ResMut<GlobalRng>,
 ) {
     for (mut birthing, position, color, orientation, mut initiative) in
         ants_birthing_query.iter_mut()
@@ -84,25 +81,13 @@ pub fn ants_birthing(
         initiative.consume();
 
         if birthing.is_ready() {
-            // NOTE: As written, this could spawn directly into a piece of dirt/food/etc.
-            // This isn't going to cause the application to panic, but isn't visually appealing, either.
-            // Could introduce a custom command and prevent spawning if the tile is occupied and/or find nearest open tile
-            // but since ants can get covered by sand already (when it falls on them) its low priority.
-            // Spawn worker ant (TODO: egg instead)
-            commands.spawn_ant(
+            // NOTE: As written, this could lay an egg directly into a piece of dirt/food/etc.
+            // Brood share tiles with ants, rather than elements, so this isn't harmful, but isn't visually appealing, either.
+            commands.spawn(BroodBundle::new(
                 orientation.get_behind_position(position),
                 AntColor(color.0),
-                Some(NestOrientation::new(
-                    NestFacing::random(&mut rng.reborrow()),
-                    NestAngle::Zero,
-                )),
-                None,
-                AntInventory::default(),
-                AntRole::Worker,
-                AntName::random(&mut rng.reborrow()),
-                Initiative::new(&mut rng.reborrow()),
-                AtNest,
-            );
+                &settings,
+            ));
 
             birthing.reset();
         }
@@ -1,8 +1,12 @@
 use super::{birthing::Birthing, NestOrientation};
 use crate::{
     common::{
-        ant::{commands::AntCommandsExt, initiative::Initiative, AntInventory},
-        element::Element,
+        ant::{
+            commands::AntCommandsExt,
+            initiative::Initiative,
+            task::{AntTask, Task},
+            AntInventory,
+        },
         grid::{Grid, GridElements},
         pheromone::{
             commands::PheromoneCommandsExt, Pheromone, PheromoneEntityPositionCache,
@@ -15,11 +19,6 @@ use crate::{
 };
 use bevy::prelude::*;
 use bevy_turborand::{DelegatedRng, GlobalRng};
-use serde::{Deserialize, Serialize};
-
-#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
-#[reflect(Component)]
-pub struct Chambering(pub f32);
 
 /// If covered in Chamber pheromone then the following things need to occur:
 ///  1) Look forward in the direction the ant is facing - if something is diggable - dig it.
@@ -34,7 +33,7 @@ pub fn ants_chamber_pheromone_act(
             &Initiative,
             &Position,
             Entity,
-            &Chambering,
+            &AntTask,
         ),
         With<AtNest>,
     >,
@@ -44,9 +43,13 @@ pub fn ants_chamber_pheromone_act(
     settings: Res<Settings>,
     mut rng: ResMut<GlobalRng>,
 ) {
-    for (ant_orientation, inventory, initiative, ant_position, ant_entity, chambering) in
+    for (ant_orientation, inventory, initiative, ant_position, ant_entity, task) in
         ants_query.iter()
     {
+        let Task::Chambering(chambering) = *task.current() else {
+            continue;
+        };
+
         if !initiative.can_act() {
             continue;
         }
@@ -72,11 +75,11 @@ pub fn ants_chamber_pheromone_act(
             &mut commands,
         ) {
             // Subtract 1 because not placing pheromone at ant_position but instead placing it at a position adjacent
-            if chambering.0 - 1.0 > 0.0 {
+            if chambering - 1.0 > 0.0 {
                 commands.spawn_pheromone(
                     *position,
                     Pheromone::Chamber,
-                    PheromoneStrength::new(chambering.0 - 1.0, settings.chamber_size as f32),
+                    PheromoneStrength::new(chambering - 1.0, settings.chamber_size as f32),
                     AtNest,
                 );
             }
@@ -113,9 +116,7 @@ pub fn ants_add_chamber_pheromone(
                     pheromone_query.get(*pheromone_entity).unwrap();
 
                 if *pheromone == Pheromone::Chamber {
-                    commands
-                        .entity(ant_entity)
-                        .insert(Chambering(pheromone_strength.value()));
+                    commands.start_task(ant_entity, Task::Chambering(pheromone_strength.value()));
                 }
             }
         }
@@ -125,10 +126,14 @@ pub fn ants_add_chamber_pheromone(
 // TODO: This is kinda weird - maybe should just diminish naturally
 /// Whenever an ant takes a step it loses 1 Chambering pheromone.
 pub fn ants_fade_chamber_pheromone(
-    mut ants_query: Query<&mut Chambering, (With<AtNest>, Changed<Position>)>,
+    mut ants_query: Query<&mut AntTask, (With<AtNest>, Changed<Position>)>,
 ) {
-    for mut chambering in ants_query.iter_mut() {
-        chambering.0 -= 1.0;
+    for mut task in ants_query.iter_mut() {
+        let Task::Chambering(chambering) = *task.current() else {
+            continue;
+        };
+
+        *task.current_mut() = Task::Chambering(chambering - 1.0);
     }
 }
 
@@ -137,7 +142,7 @@ pub fn ants_fade_chamber_pheromone(
 /// Ants lose Chambering when they've exhausted their pheromone by taking sufficient steps.
 pub fn ants_remove_chamber_pheromone(
     mut ants_query: Query<
-        (Entity, &Position, &AntInventory, &Chambering),
+        (Entity, &Position, &AntInventory, &AntTask),
         (Or<(Changed<Position>, Changed<AntInventory>)>, With<AtNest>),
     >,
     mut commands: Commands,
@@ -145,13 +150,17 @@ pub fn ants_remove_chamber_pheromone(
 ) {
     let nest = nest_query.single();
 
-    for (entity, position, inventory, chambering) in ants_query.iter_mut() {
+    for (entity, position, inventory, task) in ants_query.iter_mut() {
+        let Task::Chambering(chambering) = *task.current() else {
+            continue;
+        };
+
         if inventory.0 != None {
-            commands.entity(entity).remove::<Chambering>();
+            commands.finish_task(entity, Task::Chambering(chambering));
         } else if nest.is_aboveground(position) {
-            commands.entity(entity).remove::<Chambering>();
-        } else if chambering.0 <= 0.0 {
-            commands.entity(entity).remove::<Chambering>();
+            commands.finish_task(entity, Task::Chambering(chambering));
+        } else if chambering <= 0.0 {
+            commands.finish_task(entity, Task::Chambering(chambering));
         }
     }
 }
@@ -170,7 +179,7 @@ fn try_dig(
     // Check if hitting a solid element and, if so, consider digging through it.
     let element_entity = grid_elements.entity(*dig_position);
     let element = grid_elements.element(*element_entity);
-    if *element == Element::Air {
+    if !element.is_diggable() {
         return false;
     }
 
@@ -11,7 +11,7 @@ use crate::{
 use bevy::prelude::*;
 use bevy_turborand::prelude::*;
 
-use super::NestOrientation;
+use super::{midden::is_in_midden, NestOrientation};
 
 pub fn ants_dig(
     ants_query: Query<
@@ -100,7 +100,7 @@ fn try_dig(
     }
 
     // NOTE: can remove this in the future when adding more elements
-    if *element != Element::Sand && *element != Element::Food {
+    if !matches!(*element, Element::Sand | Element::Food | Element::Mold) {
         return false;
     }
 
@@ -131,6 +131,9 @@ fn try_dig(
         // When underground, prioritize clearing out sand and allow for digging tunnels through dirt. Leave food underground.
         // It's OK for queen to pick up sand because sometimes it'll get in the way of nest building.
         dig = *element == Element::Sand && nest.is_underground(&dig_position);
+    } else if *element == Element::Mold && *ant_role == AntRole::Worker {
+        // Moldy food is refuse, just like corpses, so workers clear it out of the nest to the midden.
+        dig = !is_in_midden(&dig_position, nest);
     }
 
     if dig {
@@ -141,3 +144,73 @@ fn try_dig(
 
     return false;
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        common::{element::Element, position::Position},
+        nest_simulation::ant::NestFacing,
+        settings::{Probabilities, Settings},
+        test_support::TestSimulation,
+    };
+
+    // Confirm that a worker picks up sand which is in its way underground.
+    #[test]
+    fn worker_digs_sand_underground() {
+        let settings = Settings {
+            probabilities: Probabilities {
+                random_drop: 0.0,
+                ..Settings::default().probabilities
+            },
+            ..Settings::default()
+        };
+
+        let mut simulation = TestSimulation::with_settings(
+            "
+            ....
+            ####
+            #Ws#
+            ####
+            ",
+            settings,
+        );
+
+        let ant_entity = simulation.nest_ant(Position::new(1, 2));
+        simulation.face_nest_ant(ant_entity, NestFacing::Right);
+
+        simulation.tick(30);
+
+        assert_eq!(
+            simulation.inventory_element(ant_entity),
+            Some(Element::Sand)
+        );
+        assert!(!simulation.nest_map().contains('s'));
+    }
+
+    // Confirm that ants don't dig through dirt unless they're tunneling.
+    #[test]
+    fn worker_does_not_dig_dirt() {
+        let mut simulation = TestSimulation::new(
+            "
+            ...
+            ###
+            #W#
+            ###
+            ",
+        );
+
+        let ant_entity = simulation.nest_ant(Position::new(1, 2));
+
+        simulation.tick(30);
+
+        assert_eq!(simulation.inventory_element(ant_entity), None);
+        simulation.assert_nest(
+            "
+            ...
+            ###
+            #W#
+            ###
+            ",
+        );
+    }
+}
@@ -5,7 +5,11 @@ use crate::{
         grid::{Grid, GridElements},
         position::Position,
     },
-    nest_simulation::nest::{AtNest, Nest},
+    nest_simulation::{
+        ant::midden::is_in_midden,
+        granary::{is_in_any_granary, Granary},
+        nest::{AtNest, Nest},
+    },
     settings::Settings,
 };
 use bevy::prelude::*;
@@ -27,12 +31,14 @@ pub fn ants_drop(
     >,
     elements_query: Query<&Element>,
     nest_query: Query<(&Grid, &Nest)>,
+    granary_query: Query<&Position, (With<Granary>, With<AtNest>)>,
     grid_elements: GridElements<AtNest>,
     settings: Res<Settings>,
     mut rng: ResMut<GlobalRng>,
     mut commands: Commands,
 ) {
     let (grid, nest) = nest_query.single();
+    let granary_positions = granary_query.iter().copied().collect::<Vec<_>>();
 
     for (orientation, inventory, initiative, position, role, ant_entity) in ants_query.iter() {
         if !initiative.can_act() {
@@ -43,6 +49,11 @@ pub fn ants_drop(
             continue;
         }
 
+        // Brood are set down by `ants_set_down_brood` rather than dropped.
+        let Ok(inventory_item_element) = elements_query.get(inventory.0.unwrap()) else {
+            continue;
+        };
+
         let ahead_position = orientation.get_ahead_position(position);
         if !grid.is_within_bounds(&ahead_position) {
             continue;
@@ -68,8 +79,6 @@ pub fn ants_drop(
         }
 
         // There is an air gap directly ahead of the ant. Consider dropping inventory.
-        let inventory_item_element = elements_query.get(inventory.0.unwrap()).unwrap();
-
         // Prioritize dropping sand above ground and food below ground.
         let drop_sand = *inventory_item_element == Element::Sand
             && nest.is_aboveground(&ahead_position)
@@ -77,14 +86,20 @@ pub fn ants_drop(
 
         let mut drop_food = false;
         if *inventory_item_element == Element::Food {
-            if nest.is_underground(&ahead_position) {
+            // Once the colony has granaries, food is only stored in them rather than left wherever.
+            let is_granary_ahead = is_in_any_granary(&ahead_position, &granary_positions);
+
+            if nest.is_underground(&ahead_position)
+                && (granary_positions.is_empty() || is_granary_ahead)
+            {
                 // Don't let ants drop food in tunnels that don't have space for them to navigate around dropped food.
                 if grid_elements.is(
                     orientation.get_above_position(&ahead_position),
                     Element::Air,
                 ) && grid_elements.is(orientation.get_above_position(position), Element::Air)
                 {
-                    drop_food = rng.f32() < settings.probabilities.below_surface_food_drop;
+                    drop_food = is_granary_ahead
+                        || rng.f32() < settings.probabilities.below_surface_food_drop;
 
                     // If ant is adjacent to food then strongly consider dropping food (creates food piles)
                     let is_food_below =
@@ -96,14 +111,18 @@ pub fn ants_drop(
                         drop_food = true;
                     }
                 }
-            } else {
+            } else if nest.is_aboveground(&ahead_position) {
                 if *role == AntRole::Queen {
                     drop_food = rng.f32() < settings.probabilities.above_surface_queen_food_drop;
                 }
             }
         }
 
-        if drop_sand || drop_food {
+        // Moldy food is set down as soon as it's been carried out to the midden.
+        let drop_mold =
+            *inventory_item_element == Element::Mold && is_in_midden(&ahead_position, nest);
+
+        if drop_sand || drop_food || drop_mold {
             // Drop inventory in front of ant
             let target_element_entity = grid_elements.entity(ahead_position);
             commands.drop(ant_entity, ahead_position, *target_element_entity, AtNest);
@@ -111,3 +130,75 @@ pub fn ants_drop(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        common::{element::Element, position::Position},
+        settings::{Probabilities, Settings},
+        test_support::TestSimulation,
+    };
+
+    // Confirm that a worker carrying sand out of the nest drops it aboveground.
+    #[test]
+    fn worker_drops_sand_aboveground() {
+        let settings = Settings {
+            probabilities: Probabilities {
+                above_surface_sand_drop: 1.0,
+                ..Settings::default().probabilities
+            },
+            ..Settings::default()
+        };
+
+        let mut simulation = TestSimulation::with_settings(
+            "
+            .....
+            ..W..
+            #####
+            ",
+            settings,
+        );
+
+        let ant_entity = simulation.nest_ant(Position::new(2, 1));
+        simulation.give_nest_ant(ant_entity, Element::Sand);
+
+        simulation.tick(30);
+
+        assert_eq!(simulation.inventory_element(ant_entity), None);
+        assert_eq!(simulation.nest_map().matches('s').count(), 1);
+    }
+
+    // Confirm that a worker carrying sand underground holds onto it.
+    #[test]
+    fn worker_keeps_sand_underground() {
+        let settings = Settings {
+            probabilities: Probabilities {
+                random_drop: 0.0,
+                above_surface_sand_drop: 1.0,
+                ..Settings::default().probabilities
+            },
+            ..Settings::default()
+        };
+
+        let mut simulation = TestSimulation::with_settings(
+            "
+            ....
+            ####
+            #W.#
+            ####
+            ",
+            settings,
+        );
+
+        let ant_entity = simulation.nest_ant(Position::new(1, 2));
+        simulation.give_nest_ant(ant_entity, Element::Sand);
+
+        simulation.tick(30);
+
+        assert_eq!(
+            simulation.inventory_element(ant_entity),
+            Some(Element::Sand)
+        );
+        assert!(!simulation.nest_map().contains('s'));
+    }
+}
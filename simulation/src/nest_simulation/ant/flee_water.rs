@@ -0,0 +1,77 @@
+use crate::{
+    common::{
+        ant::initiative::Initiative, element::Element, grid::GridElements, position::Position,
+    },
+    nest_simulation::nest::AtNest,
+};
+use bevy::prelude::*;
+use bevy_turborand::{DelegatedRng, GlobalRng};
+
+/// Ants caught in water swim for safety before doing anything else. They head for the surface of the water and,
+/// if something solid is overhead, paddle sideways in search of a way up.
+pub fn ants_flee_water(
+    mut ants_query: Query<(&mut Initiative, &mut Position), With<AtNest>>,
+    grid_elements: GridElements<AtNest>,
+    mut rng: ResMut<GlobalRng>,
+) {
+    for (mut initiative, mut position) in ants_query.iter_mut() {
+        if !initiative.can_move() {
+            continue;
+        }
+
+        if !grid_elements.is(*position, Element::Water) {
+            continue;
+        }
+
+        let is_swimmable = |position: Position| {
+            grid_elements.is(position, Element::Air) || grid_elements.is(position, Element::Water)
+        };
+
+        let above_position = *position - Position::Y;
+
+        if is_swimmable(above_position) {
+            *position = above_position;
+        } else {
+            let side_positions = [*position + Position::NEG_X, *position + Position::X]
+                .into_iter()
+                .filter(|&position| is_swimmable(position))
+                .collect::<Vec<_>>();
+
+            if let Some(&side_position) = rng.sample(&side_positions) {
+                *position = side_position;
+            }
+        }
+
+        // Struggling in the water takes effort even when there's nowhere to go.
+        initiative.consume_movement();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        common::{element::Element, position::Position},
+        test_support::TestSimulation,
+    };
+
+    // Confirm that an ant caught in water swims up to the surface.
+    #[test]
+    fn ant_in_water_swims_to_surface() {
+        // The water above the worker floods in on the first tick and fills the bottom of the shaft.
+        let mut simulation = TestSimulation::new(
+            "
+            #..#
+            #ww#
+            #Ww#
+            ####
+            ",
+        );
+        let ant_entity = simulation.nest_ant(Position::new(1, 2));
+
+        simulation.tick(10);
+
+        let position = *simulation.world().get::<Position>(ant_entity).unwrap();
+        assert!(position.y < 2);
+        assert_ne!(simulation.nest_element(position), Element::Water);
+    }
+}
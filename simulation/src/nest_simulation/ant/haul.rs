@@ -0,0 +1,112 @@
+use super::NestOrientation;
+use crate::{
+    common::{
+        ant::{caste::Caste, initiative::Initiative, AntInventory, AntRole, InventoryItem},
+        position::Position,
+    },
+    nest_simulation::nest::AtNest,
+};
+use bevy::{ecs::query::QueryFilter, prelude::*};
+
+/// Workers pick up items, which are in front of them or underfoot, when the items aren't yet where they belong.
+/// Carried items are held in the ant's inventory, just like elements, so they lose their place in the world.
+/// Shared by every hauler so brood and corpses are carried the same way; `is_willing` lets a hauler weigh in the
+/// ant's caste once an item has been found.
+pub fn pick_up_stray_items<F: QueryFilter>(
+    ants_query: &mut Query<
+        (
+            &mut Initiative,
+            &mut AntInventory,
+            &Position,
+            &NestOrientation,
+            &AntRole,
+            Option<&Caste>,
+        ),
+        With<AtNest>,
+    >,
+    items_query: &Query<(Entity, &Position), F>,
+    is_at_destination: impl Fn(Position) -> bool,
+    mut is_willing: impl FnMut(Option<&Caste>) -> bool,
+    commands: &mut Commands,
+) {
+    // Two ants shouldn't pick up the same item in a single tick.
+    let mut picked_up_entities = vec![];
+
+    for (mut initiative, mut inventory, position, orientation, role, caste) in ants_query.iter_mut()
+    {
+        if *role != AntRole::Worker || !initiative.can_act() || inventory.0 != None {
+            continue;
+        }
+
+        let ahead_position = orientation.get_ahead_position(position);
+
+        let Some((item_entity, _)) = items_query.iter().find(|(item_entity, &item_position)| {
+            (item_position == ahead_position || item_position == *position)
+                && !picked_up_entities.contains(item_entity)
+                && !is_at_destination(item_position)
+        }) else {
+            continue;
+        };
+
+        if !is_willing(caste) {
+            continue;
+        }
+
+        commands
+            .entity(item_entity)
+            .remove::<Position>()
+            .insert(InventoryItem);
+
+        inventory.0 = Some(item_entity);
+        initiative.consume();
+
+        picked_up_entities.push(item_entity);
+    }
+}
+
+/// Workers carrying items set them down, underfoot, once they're standing rightside up at the items' destination.
+/// Items aren't piled on top of one another so they spread out across the destination.
+pub fn set_down_carried_items<F: QueryFilter>(
+    ants_query: &mut Query<
+        (
+            &mut Initiative,
+            &mut AntInventory,
+            &Position,
+            &NestOrientation,
+        ),
+        With<AtNest>,
+    >,
+    items_query: &Query<Option<&Position>, F>,
+    is_at_destination: impl Fn(Position) -> bool,
+    commands: &mut Commands,
+) {
+    let mut item_positions = items_query.iter().flatten().copied().collect::<Vec<_>>();
+
+    for (mut initiative, mut inventory, position, orientation) in ants_query.iter_mut() {
+        if !initiative.can_act() || !orientation.is_rightside_up() {
+            continue;
+        }
+
+        let Some(item_entity) = inventory.0 else {
+            continue;
+        };
+
+        if !items_query.contains(item_entity) {
+            continue;
+        }
+
+        if item_positions.contains(position) || !is_at_destination(*position) {
+            continue;
+        }
+
+        commands
+            .entity(item_entity)
+            .insert(*position)
+            .remove::<InventoryItem>();
+
+        inventory.0 = None;
+        initiative.consume();
+
+        item_positions.push(*position);
+    }
+}
@@ -0,0 +1,275 @@
+use super::{
+    haul::{pick_up_stray_items, set_down_carried_items},
+    NestOrientation,
+};
+use crate::{
+    common::{
+        ant::{
+            caste::Caste, hunger::Hunger, initiative::Initiative, Ant, AntInventory, AntRole, Dead,
+        },
+        element::Element,
+        grid::GridElements,
+        position::Position,
+    },
+    nest_simulation::nest::{AtNest, Nest},
+};
+use bevy::prelude::*;
+use bevy_turborand::{DelegatedRng, GlobalRng};
+
+/// How much of the colony's health is lost for each corpse left lying in the nest.
+const UNCOLLECTED_CORPSE_HEALTH_PENALTY: f32 = 10.0;
+
+/// The midden is the colony's refuse pile. Corpses are piled on the surface, out of the way, rather than in a chamber.
+pub fn is_in_midden(position: &Position, nest: &Nest) -> bool {
+    nest.is_aboveground(position)
+}
+
+/// Colony health, as a percentage, falls with each corpse which hasn't yet been carried out to the midden.
+pub fn get_colony_health(uncollected_corpse_count: usize) -> f32 {
+    (100.0 - uncollected_corpse_count as f32 * UNCOLLECTED_CORPSE_HEALTH_PENALTY).max(0.0)
+}
+
+/// Workers pick up corpses, which are in front of them or underfoot, when they've been left lying in the nest.
+/// Carried corpses are held in the ant's inventory, just like brood, so they lose their place in the world.
+pub fn ants_pick_up_corpse(
+    mut ants_query: Query<
+        (
+            &mut Initiative,
+            &mut AntInventory,
+            &Position,
+            &NestOrientation,
+            &AntRole,
+            Option<&Caste>,
+        ),
+        With<AtNest>,
+    >,
+    corpses_query: Query<(Entity, &Position), (With<Ant>, With<Dead>, With<AtNest>)>,
+    nest_query: Query<&Nest>,
+    mut commands: Commands,
+) {
+    let nest = nest_query.single();
+
+    pick_up_stray_items(
+        &mut ants_query,
+        &corpses_query,
+        |position| is_in_midden(&position, nest),
+        |_| true,
+        &mut commands,
+    );
+}
+
+/// Workers carrying corpses set them down, underfoot, once they've carried them out of the nest to the midden.
+/// Corpses aren't piled on top of one another so the midden spreads out across the surface.
+pub fn ants_set_down_corpse(
+    mut ants_query: Query<
+        (
+            &mut Initiative,
+            &mut AntInventory,
+            &Position,
+            &NestOrientation,
+        ),
+        With<AtNest>,
+    >,
+    corpses_query: Query<Option<&Position>, (With<Ant>, With<Dead>, With<AtNest>)>,
+    nest_query: Query<&Nest>,
+    grid_elements: GridElements<AtNest>,
+    mut commands: Commands,
+) {
+    let nest = nest_query.single();
+
+    set_down_carried_items(
+        &mut ants_query,
+        &corpses_query,
+        |position| is_in_midden(&position, nest) && grid_elements.is(position, Element::Air),
+        &mut commands,
+    );
+}
+
+/// Corpses left lying in the nest make the colony unhealthy. The less healthy the colony, the more likely its ants
+/// are to fall sick each tick, which makes them hungrier than they'd otherwise be.
+pub fn ants_sicken(
+    mut ants_query: Query<&mut Hunger, (With<Ant>, Without<Dead>, With<AtNest>)>,
+    corpses_query: Query<&Position, (With<Ant>, With<Dead>, With<AtNest>)>,
+    nest_query: Query<&Nest>,
+    mut rng: ResMut<GlobalRng>,
+) {
+    let nest = nest_query.single();
+    let uncollected_corpse_count = corpses_query
+        .iter()
+        .filter(|position| !is_in_midden(position, nest))
+        .count();
+
+    let colony_health = get_colony_health(uncollected_corpse_count);
+    if colony_health >= 100.0 {
+        return;
+    }
+
+    let sickness_chance = (100.0 - colony_health) / 100.0;
+
+    for mut hunger in ants_query.iter_mut() {
+        if rng.chance(sickness_chance.into()) {
+            hunger.tick();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::get_colony_health;
+    use crate::{
+        common::{
+            ant::{death::Decay, hunger::Hunger, initiative::Initiative, AntInventory, Dead},
+            position::Position,
+        },
+        nest_simulation::ant::NestFacing,
+        settings::Settings,
+        test_support::TestSimulation,
+    };
+    use bevy::prelude::*;
+
+    fn kill(simulation: &mut TestSimulation, position: Position) -> Entity {
+        let corpse_entity = simulation.nest_ant(position);
+        simulation
+            .world_mut()
+            .entity_mut(corpse_entity)
+            .insert(Dead)
+            .remove::<Initiative>();
+
+        corpse_entity
+    }
+
+    // Confirm that a worker picks up a corpse lying in the nest and sets it down once out on the surface.
+    #[test]
+    fn worker_carries_corpse_to_midden() {
+        let mut simulation = TestSimulation::new(
+            "
+            .......
+            .......
+            ###.###
+            ###.###
+            #WW.###
+            #######
+            ",
+        );
+        let ant_entity = simulation.nest_ant(Position::new(2, 4));
+        simulation.face_nest_ant(ant_entity, NestFacing::Left);
+        let corpse_entity = kill(&mut simulation, Position::new(1, 4));
+
+        simulation.tick(6);
+
+        assert_eq!(
+            simulation
+                .world()
+                .get::<AntInventory>(ant_entity)
+                .unwrap()
+                .0,
+            Some(corpse_entity)
+        );
+        assert!(simulation.world().get::<Position>(corpse_entity).is_none());
+
+        // Walk the ant out onto the surface.
+        *simulation
+            .world_mut()
+            .get_mut::<Position>(ant_entity)
+            .unwrap() = Position::new(5, 1);
+        simulation.face_nest_ant(ant_entity, NestFacing::Left);
+
+        simulation.tick(6);
+
+        assert_eq!(
+            simulation.world().get::<Position>(corpse_entity),
+            Some(&Position::new(5, 1))
+        );
+    }
+
+    // Confirm that corpses decay away, even while being carried, and free up their carrier's inventory.
+    #[test]
+    fn carried_corpse_decays() {
+        let settings = Settings {
+            max_corpse_decay_time: 1,
+            ..Settings::default()
+        };
+
+        let mut simulation = TestSimulation::with_settings(
+            "
+            .....
+            #####
+            #WW.#
+            #####
+            ",
+            settings,
+        );
+        let ant_entity = simulation.nest_ant(Position::new(2, 2));
+        simulation.face_nest_ant(ant_entity, NestFacing::Left);
+        let corpse_entity = kill(&mut simulation, Position::new(1, 2));
+
+        simulation.tick(6);
+        assert!(simulation.world().get::<Decay>(corpse_entity).is_some());
+        assert_eq!(
+            simulation
+                .world()
+                .get::<AntInventory>(ant_entity)
+                .unwrap()
+                .0,
+            Some(corpse_entity)
+        );
+
+        simulation.tick(10);
+
+        assert!(simulation.world().get_entity(corpse_entity).is_none());
+        assert_eq!(
+            simulation
+                .world()
+                .get::<AntInventory>(ant_entity)
+                .unwrap()
+                .0,
+            None
+        );
+    }
+
+    // Confirm that ants grow hungrier while corpses are left lying in the nest.
+    #[test]
+    fn uncollected_corpses_sicken_colony() {
+        assert_eq!(get_colony_health(0), 100.0);
+        assert_eq!(get_colony_health(3), 70.0);
+        assert_eq!(get_colony_health(20), 0.0);
+
+        let mut simulation = TestSimulation::new(
+            "
+            ......
+            ######
+            #WWWW#
+            ######
+            ",
+        );
+        let ant_entity = simulation.nest_ant(Position::new(4, 2));
+        for x in 1..=3 {
+            kill(&mut simulation, Position::new(x, 2));
+        }
+        // Keep the ant from collecting the corpses.
+        simulation
+            .world_mut()
+            .entity_mut(ant_entity)
+            .remove::<Initiative>();
+        let initial_hunger = simulation
+            .world()
+            .get::<Hunger>(ant_entity)
+            .unwrap()
+            .value();
+
+        simulation.tick(100);
+
+        // A healthy ant's hunger would only have ticked once per tick.
+        let mut healthy_hunger = Hunger::new(Settings::default().max_hunger_time);
+        for _ in 0..100 {
+            healthy_hunger.tick();
+        }
+
+        let hunger = simulation
+            .world()
+            .get::<Hunger>(ant_entity)
+            .unwrap()
+            .value();
+        assert!(hunger - initial_hunger > healthy_hunger.value());
+    }
+}
@@ -2,8 +2,14 @@ pub mod birthing;
 pub mod chambering;
 pub mod dig;
 pub mod drop;
+pub mod flee_water;
+pub mod haul;
+pub mod midden;
 pub mod nest_expansion;
 pub mod nesting;
+pub mod nuptial_flight;
+pub mod nursing;
+pub mod shelter;
 pub mod sleep;
 pub mod travel;
 pub mod tunneling;
@@ -13,26 +19,21 @@ use std::f32::consts::PI;
 
 use crate::common::position::Position;
 
-use self::{birthing::Birthing, chambering::Chambering, sleep::Asleep, tunneling::Tunneling};
+use self::birthing::Birthing;
 use bevy::prelude::*;
 use bevy_turborand::{DelegatedRng, GlobalRng};
 use serde::{Deserialize, Serialize};
 
 pub fn register_ant(app_type_registry: ResMut<AppTypeRegistry>) {
-    // TODO: This might be nest-specific, but maybe needs to be supported at crater just in case
-    app_type_registry.write().register::<Asleep>();
-
     app_type_registry.write().register::<NestOrientation>();
     app_type_registry.write().register::<NestFacing>();
     app_type_registry.write().register::<NestAngle>();
 
     // TODO: These seem nest-specific
     app_type_registry.write().register::<Birthing>();
-    app_type_registry.write().register::<Tunneling>();
-    app_type_registry.write().register::<Chambering>();
 }
 
-#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default, Hash)]
 pub enum NestFacing {
     #[default]
     Left,
@@ -49,7 +50,7 @@ impl NestFacing {
     }
 }
 
-#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default, Hash)]
 pub enum NestAngle {
     #[default]
     Zero,
@@ -81,7 +82,9 @@ impl NestAngle {
     }
 }
 
-#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[derive(
+    Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default, Hash,
+)]
 #[reflect(Component)]
 pub struct NestOrientation {
     facing: NestFacing,
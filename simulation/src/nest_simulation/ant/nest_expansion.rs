@@ -1,6 +1,8 @@
 use crate::{
     common::{
-        ant::{commands::AntCommandsExt, initiative::Initiative, AntInventory, AntRole},
+        ant::{
+            caste::Caste, commands::AntCommandsExt, initiative::Initiative, AntInventory, AntRole,
+        },
         element::Element,
         grid::GridElements,
         pheromone::{commands::PheromoneCommandsExt, Pheromone, PheromoneStrength},
@@ -18,6 +20,7 @@ use super::NestOrientation;
 ///     1) The ant must not be hungry. If the ant is hungry it's assumed that nest expansion isn't desirable because resources are scarce.
 ///     2) The ant must feel crowded. If the ant doesn't feel crowded then it's assumed that nest expansion isn't desirable because there's plenty of space.
 /// For now, crowding will be a really naive implementation where if an ant has at least two other ants adjacent to it then it is crowded.
+/// Diggers are the most eager to expand the nest.
 pub fn ants_nest_expansion(
     ants_query: Query<
         (
@@ -27,6 +30,7 @@ pub fn ants_nest_expansion(
             &Initiative,
             &Position,
             Entity,
+            Option<&Caste>,
         ),
         With<AtNest>,
     >,
@@ -40,10 +44,10 @@ pub fn ants_nest_expansion(
 
     let ant_entity_positions = ants_query
         .iter()
-        .map(|(_, _, _, _, position, entity)| (*position, entity))
+        .map(|(_, _, _, _, position, entity, _)| (*position, entity))
         .collect::<Vec<_>>();
 
-    for (ant_role, ant_orientation, inventory, initiative, ant_position, ant_entity) in
+    for (ant_role, ant_orientation, inventory, initiative, ant_position, ant_entity, caste) in
         ants_query.iter()
     {
         if !initiative.can_act() {
@@ -66,7 +70,9 @@ pub fn ants_nest_expansion(
             .count()
             >= 2;
 
-        if is_crowded && rng.f32() < settings.probabilities.expand_nest {
+        let digging_weight = caste.map_or(1.0, |caste| caste.weights().digging);
+
+        if is_crowded && rng.f32() < settings.probabilities.expand_nest * digging_weight {
             let dirt_position = ant_orientation.get_ahead_position(ant_position);
 
             if !grid_elements.is(dirt_position, Element::Dirt) {
@@ -1,14 +1,19 @@
 use super::{wander::get_turned_orientation, NestFacing, NestOrientation};
 use crate::{
     common::{
-        ant::{commands::AntCommandsExt, initiative::Initiative, AntInventory, AntRole},
+        ant::{
+            commands::AntCommandsExt,
+            initiative::Initiative,
+            task::{AntTask, Task},
+            AntInventory, AntRole,
+        },
         element::Element,
         grid::GridElements,
         pheromone::{commands::PheromoneCommandsExt, Pheromone, PheromoneStrength},
         position::Position,
     },
     nest_simulation::{
-        ant::birthing::Birthing,
+        ant::{birthing::Birthing, nuptial_flight::Winged},
         nest::{AtNest, Nest},
     },
     settings::Settings,
@@ -16,12 +21,16 @@ use crate::{
 use bevy::prelude::*;
 use bevy_turborand::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
 
-#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
-#[reflect(Component)]
+/// How far along the queen is in founding the nest. Tracked by `Task::Nesting`.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Serialize, Deserialize, Reflect, Default, Hash)]
 pub enum Nesting {
     #[default]
     NotStarted,
+    /// The queen has chosen where to dig, after landing from her nuptial flight, and is walking over to it.
+    /// She gives up on it, and starts over, if it stops being diggable before she gets there.
+    Seeking(Position),
     Started(Position),
 }
 
@@ -34,21 +43,35 @@ pub fn register_nesting(app_type_registry: ResMut<AppTypeRegistry>) {
     app_type_registry.write().register::<Nested>();
 }
 
+/// Nests are only dug away from the edges of the world. Returns the range of x positions a nest can be dug at.
+/// TODO:
+///     * Instead of arbitrarily checking if ant is near edge of the map, place immovable rocks which dissuade ant from digging.
+pub fn get_nest_site_range(settings: &Settings) -> RangeInclusive<isize> {
+    // If x position is within 20% of world edge then don't dig there
+    let offset = settings.nest_width / 5;
+
+    offset..=settings.nest_width - offset
+}
+
+/// Queens on their nuptial flight start nesting once they've landed.
 // TODO: perf - prefer to query directly for Queen rather than filtering through all workers
 pub fn ants_nesting_start(
-    ant_query: Query<(Entity, &AntRole), (With<AtNest>, Without<Nesting>, Without<Nested>)>,
+    ant_query: Query<
+        (Entity, &AntRole, &AntTask),
+        (With<AtNest>, Without<Nested>, Without<Winged>),
+    >,
     mut commands: Commands,
 ) {
-    for (ant_entity, ant_role) in ant_query.iter() {
-        if *ant_role == AntRole::Queen {
-            commands.entity(ant_entity).insert(Nesting::default());
+    for (ant_entity, ant_role, ant_task) in ant_query.iter() {
+        if *ant_role == AntRole::Queen && !ant_task.is_nesting() {
+            commands.start_task(ant_entity, Task::Nesting(Nesting::default()));
         }
     }
 }
 
-/// Ants that are building the initial nest (usually just a queen) should prioritize making it back to the nest
-/// quickly rather than wandering aimlessly on the surface. They still need to wait until they drop their inventory
-/// otherwise they won't walk away from the nest their excavated dirt.
+/// Ants that are building the initial nest (usually just a queen) should prioritize making it back to the nest, or
+/// over to where they've chosen to dig it, quickly rather than wandering aimlessly on the surface.
+/// They still need to wait until they drop their inventory otherwise they won't walk away from the nest their excavated dirt.
 pub fn ants_nesting_movement(
     mut ants_query: Query<
         (
@@ -56,7 +79,7 @@ pub fn ants_nesting_movement(
             &Position,
             &mut NestOrientation,
             &AntInventory,
-            &Nesting,
+            &AntTask,
         ),
         With<AtNest>,
     >,
@@ -66,7 +89,7 @@ pub fn ants_nesting_movement(
 ) {
     let nest = nest_query.single();
 
-    for (mut initiative, position, mut orientation, inventory, nesting) in ants_query.iter_mut() {
+    for (mut initiative, position, mut orientation, inventory, ant_task) in ants_query.iter_mut() {
         if !initiative.can_move() {
             continue;
         }
@@ -75,21 +98,23 @@ pub fn ants_nesting_movement(
             continue;
         }
 
-        if let Nesting::Started(nest_position) = nesting {
-            // Don't fuss with distance logic when close to the nest entrance because it's naive and edge cases can cause infinite loops
-            if position.distance(nest_position) <= 1 {
-                continue;
-            }
+        let Task::Nesting(Nesting::Seeking(nest_position) | Nesting::Started(nest_position)) =
+            ant_task.current()
+        else {
+            continue;
+        };
 
-            let ahead_position = match orientation.get_facing() {
-                NestFacing::Right => *position + Position::X,
-                NestFacing::Left => *position - Position::X,
-            };
+        // Don't fuss with distance logic when close to the nest entrance because it's naive and edge cases can cause infinite loops
+        if position.distance(nest_position) <= 1 {
+            continue;
+        }
 
-            if position.distance(nest_position) > ahead_position.distance(nest_position) {
-                continue;
-            }
-        } else {
+        let ahead_position = match orientation.get_facing() {
+            NestFacing::Right => *position + Position::X,
+            NestFacing::Left => *position - Position::X,
+        };
+
+        if position.distance(nest_position) > ahead_position.distance(nest_position) {
             continue;
         }
 
@@ -103,7 +128,7 @@ pub fn ants_nesting_movement(
 pub fn ants_nesting_action(
     mut ants_query: Query<
         (
-            &mut Nesting,
+            &mut AntTask,
             &NestOrientation,
             &AntInventory,
             &mut Initiative,
@@ -120,9 +145,21 @@ pub fn ants_nesting_action(
 ) {
     let nest = nest_query.single();
 
-    for (mut nesting, orientation, inventory, mut initiative, position, ant_entity) in
+    for (mut ant_task, orientation, inventory, mut initiative, position, ant_entity) in
         ants_query.iter_mut()
     {
+        let Task::Nesting(nesting) = *ant_task.current() else {
+            continue;
+        };
+
+        // The dig site may have been built over, e.g. with rock or water, since it was chosen so look elsewhere instead.
+        if let Nesting::Seeking(dig_site) = nesting {
+            if !is_dig_site_diggable(dig_site, &grid_elements) {
+                *ant_task.current_mut() = Task::Nesting(Nesting::NotStarted);
+                continue;
+            }
+        }
+
         if !initiative.can_act() {
             continue;
         }
@@ -141,7 +178,7 @@ pub fn ants_nesting_action(
                 &position,
                 &orientation,
                 ant_entity,
-                &mut nesting,
+                &mut ant_task,
                 &grid_elements,
                 &mut commands,
                 &settings,
@@ -154,6 +191,7 @@ pub fn ants_nesting_action(
                 &position,
                 &orientation,
                 ant_entity,
+                nesting,
                 &inventory,
                 &mut initiative,
                 &grid_elements,
@@ -165,16 +203,25 @@ pub fn ants_nesting_action(
     }
 }
 
+/// Returns true if the topmost element in the dig site's column, whatever has since piled up there, can still be dug.
+fn is_dig_site_diggable(dig_site: Position, grid_elements: &GridElements<AtNest>) -> bool {
+    (0..)
+        .map(|y| grid_elements.get_entity(Position::new(dig_site.x, y)))
+        .map_while(|element_entity| {
+            element_entity.and_then(|&entity| grid_elements.get_element(entity))
+        })
+        .find(|&&element| element != Element::Air)
+        .map_or(false, |element| element.is_diggable())
+}
+
 /// Returns true if ant is at a valid position to begin digging out a nest chamber.
 /// This requires six things:
 ///     1) The ant must not already be creating a nest.
 ///     2) The ant must not be carrying anything.
-///     3) The ant must want to dig a nest (based on chance).
+///     3) The ant must want to dig a nest (based on chance) or be standing over the dig site it chose.
 ///     4) The ant must be aboveground.
 ///     5) The ant must not be too close to the edge of the world.
 ///     6) The ant must be standing on a diggable element.
-/// TODO:
-///     * Instead of arbitrarily checking if ant is near edge of the map, place immovable rocks which dissuade ant from digging.
 fn can_start_nesting(
     nesting: &Nesting,
     rng: &mut ResMut<GlobalRng>,
@@ -185,27 +232,30 @@ fn can_start_nesting(
     grid_elements: &GridElements<AtNest>,
     settings: &Settings,
 ) -> bool {
-    let should_consider_digging = *nesting == Nesting::NotStarted
-        && rng.f32() < settings.probabilities.above_surface_queen_nest_dig
-        && inventory.0 == None;
+    let dig_position = ant_orientation.get_below_position(ant_position);
+
+    let should_consider_digging = match *nesting {
+        Nesting::NotStarted => {
+            rng.f32() < settings.probabilities.above_surface_queen_nest_dig && inventory.0 == None
+        }
+        // Sand may have since piled up over the dig site so only its column needs to match.
+        Nesting::Seeking(dig_site) => dig_position.x == dig_site.x && inventory.0 == None,
+        Nesting::Started(_) => false,
+    };
 
     if !should_consider_digging {
         return false;
     }
 
-    // If x position is within 20% of world edge then don't dig there
-    let offset = settings.nest_width / 5;
-    let is_too_near_world_edge =
-        ant_position.x < offset || ant_position.x > settings.nest_width - offset;
+    let is_too_near_world_edge = !get_nest_site_range(settings).contains(&ant_position.x);
 
     let has_valid_dig_site = nest.is_aboveground(&ant_position) && !is_too_near_world_edge;
 
-    let dig_position = ant_orientation.get_below_position(ant_position);
     let dig_target_entity = grid_elements.entity(dig_position);
 
     let is_element_diggable = grid_elements
         .get_element(*dig_target_entity)
-        .map_or(false, |element| *element != Element::Air);
+        .map_or(false, |element| element.is_diggable());
 
     has_valid_dig_site && is_element_diggable
 }
@@ -218,7 +268,7 @@ fn start_digging_nest(
     ant_position: &Position,
     ant_orientation: &NestOrientation,
     ant_entity: Entity,
-    nesting: &mut Nesting,
+    ant_task: &mut AntTask,
     grid_elements: &GridElements<AtNest>,
     commands: &mut Commands,
     settings: &Settings,
@@ -228,7 +278,7 @@ fn start_digging_nest(
     let dig_target_entity = grid_elements.entity(dig_position);
     commands.dig(ant_entity, dig_position, *dig_target_entity, AtNest);
 
-    *nesting = Nesting::Started(dig_position);
+    *ant_task.current_mut() = Task::Nesting(Nesting::Started(dig_position));
     commands.spawn_pheromone(
         dig_position,
         Pheromone::Tunnel,
@@ -290,22 +340,23 @@ fn can_finish_nesting(
     true
 }
 
-/// Finish digging a nest by removing the Nesting instinct and adding the Birthing instinct.
+/// Finish digging a nest by finishing the Nesting task and adding the Birthing instinct.
+/// Finishing the task marks the ant as having Nested so that it doesn't try to found another nest.
 /// Also, drop anything the ant is carrying so that they can eat food later.
 fn finish_digging_nest(
     ant_position: &Position,
     ant_orientation: &NestOrientation,
     ant_entity: Entity,
+    nesting: Nesting,
     ant_inventory: &AntInventory,
     initiative: &mut Initiative,
     grid_elements: &GridElements<AtNest>,
     commands: &mut Commands,
     settings: &Res<Settings>,
 ) {
+    commands.finish_task(ant_entity, Task::Nesting(nesting));
     commands
         .entity(ant_entity)
-        .remove::<Nesting>()
-        .insert(Nested)
         .insert(Birthing::new(settings.max_birthing_time));
 
     if ant_inventory.0 != None {
@@ -318,3 +369,48 @@ fn finish_digging_nest(
         initiative.consume();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Nesting;
+    use crate::{
+        common::{
+            ant::task::{AntTask, Task},
+            position::Position,
+        },
+        test_support::TestSimulation,
+    };
+
+    // Confirm that a queen gives up on a dig site which can no longer be dug and looks for somewhere else.
+    #[test]
+    fn seeking_queen_abandons_undiggable_dig_site() {
+        let mut simulation = TestSimulation::new(
+            "
+            ..........
+            ..........
+            ..Q.......
+            ######R###
+            ##########
+            ##########
+            ",
+        );
+        let ant_entity = simulation.nest_ant(Position::new(2, 2));
+        simulation
+            .world_mut()
+            .entity_mut(ant_entity)
+            .insert(AntTask::new(Task::Nesting(Nesting::Seeking(
+                Position::new(6, 2),
+            ))));
+
+        simulation.tick(1);
+
+        assert_eq!(
+            simulation
+                .world()
+                .get::<AntTask>(ant_entity)
+                .unwrap()
+                .current(),
+            &Task::Nesting(Nesting::NotStarted)
+        );
+    }
+}
@@ -0,0 +1,322 @@
+use super::{
+    nesting::{get_nest_site_range, Nesting},
+    NestAngle, NestFacing, NestOrientation,
+};
+use crate::{
+    common::{
+        ant::{
+            commands::AntCommandsExt,
+            initiative::Initiative,
+            task::{AntTask, Task},
+        },
+        element::{Element, Hardness},
+        grid::GridElements,
+        position::Position,
+    },
+    nest_simulation::nest::{AtNest, Nest},
+    settings::Settings,
+};
+use bevy::prelude::*;
+use bevy_turborand::{DelegatedRng, GlobalRng};
+use serde::{Deserialize, Serialize};
+
+/// How far, to either side of where she lands, the queen looks for somewhere to dig.
+const DIG_SITE_SEARCH_RADIUS: isize = 10;
+/// How many elements, beneath the surface, the queen considers when judging how easy a dig site is to dig.
+const DIG_SITE_SEARCH_DEPTH: isize = 6;
+
+/// Queens arrive at the nest on their nuptial flight. They're unaffected by gravity until they land and shed their wings.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Component)]
+pub struct Winged;
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Serialize, Deserialize, Reflect, Hash)]
+pub enum Flight {
+    /// Circling above the nest. The value is how many ticks remain before the queen picks somewhere to land on her own.
+    Circling(isize),
+    /// Descending towards the surface. The value is the x position being landed at.
+    Landing(isize),
+}
+
+pub fn register_nuptial_flight(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<Winged>();
+    app_type_registry.write().register::<Flight>();
+}
+
+/// Winged queens circle above the nest until they're shown where to land, or grow tired of waiting, and then descend.
+/// Once on the ground, they shed their wings, look around for the easiest ground to dig into, and set off to found the nest there.
+pub fn ants_nuptial_flight(
+    mut ants_query: Query<
+        (
+            Entity,
+            &mut Initiative,
+            &mut Position,
+            &mut NestOrientation,
+            &mut AntTask,
+        ),
+        (With<Winged>, With<AtNest>),
+    >,
+    nest_query: Query<&Nest>,
+    grid_elements: GridElements<AtNest>,
+    hardness_query: Query<&Hardness>,
+    settings: Res<Settings>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    let nest = nest_query.single();
+    let nest_site_range = get_nest_site_range(&settings);
+
+    for (ant_entity, mut initiative, mut position, mut orientation, mut ant_task) in
+        ants_query.iter_mut()
+    {
+        let Task::Flying(flight) = *ant_task.current() else {
+            continue;
+        };
+
+        if let Flight::Circling(ticks) = flight {
+            *ant_task.current_mut() = if ticks > 0 {
+                Task::Flying(Flight::Circling(ticks - 1))
+            } else {
+                Task::Flying(Flight::Landing(position.x))
+            };
+        }
+
+        if !initiative.can_act() {
+            continue;
+        }
+
+        match flight {
+            Flight::Circling(_) => {
+                let ahead_position = orientation.get_ahead_position(&position);
+
+                // Stay above the nest site, wheeling around when reaching its edges.
+                if !nest_site_range.contains(&ahead_position.x)
+                    || !grid_elements.is(ahead_position, Element::Air)
+                    || rng.chance(settings.probabilities.random_nest_turn.into())
+                {
+                    *orientation = orientation.turn_around();
+                } else {
+                    *position = ahead_position;
+                }
+            }
+            Flight::Landing(landing_x) => {
+                if position.x != landing_x {
+                    let facing = if landing_x > position.x {
+                        NestFacing::Right
+                    } else {
+                        NestFacing::Left
+                    };
+                    *orientation = NestOrientation::new(facing, NestAngle::Zero);
+
+                    // Climb over anything in the way.
+                    let ahead_position = orientation.get_ahead_position(&position);
+                    if grid_elements.is(ahead_position, Element::Air) {
+                        *position = ahead_position;
+                    } else if position.y > 0 {
+                        *position = *position - Position::Y;
+                    } else {
+                        // There's no climbing any higher so land here rather than hovering in place forever.
+                        *ant_task.current_mut() = Task::Flying(Flight::Landing(position.x));
+                    }
+                } else if grid_elements.is(*position + Position::Y, Element::Air) {
+                    *position = *position + Position::Y;
+                } else {
+                    let dig_site = choose_dig_site(
+                        *position,
+                        nest,
+                        &grid_elements,
+                        &hardness_query,
+                        &settings,
+                    );
+
+                    // Landing sheds the queen's wings.
+                    commands.start_task(
+                        ant_entity,
+                        Task::Nesting(dig_site.map_or(Nesting::NotStarted, Nesting::Seeking)),
+                    );
+                }
+            }
+        }
+
+        initiative.consume();
+    }
+}
+
+/// Looks along the surface, around where the queen landed, for the column which is easiest to dig a nest into.
+/// Loose sand tends to cave in and hard-packed dirt takes a long time to dig, while rock and water can't be dug at all.
+/// Nearby sites are preferred so the queen doesn't wander far. Returns the surface position above the chosen column.
+pub fn choose_dig_site(
+    landing_position: Position,
+    nest: &Nest,
+    grid_elements: &GridElements<AtNest>,
+    hardness_query: &Query<&Hardness>,
+    settings: &Settings,
+) -> Option<Position> {
+    let nest_site_range = get_nest_site_range(settings);
+
+    ((landing_position.x - DIG_SITE_SEARCH_RADIUS)..=(landing_position.x + DIG_SITE_SEARCH_RADIUS))
+        .filter(|x| nest_site_range.contains(x))
+        .filter_map(|x| {
+            let ground_y = (1..settings.nest_height)
+                .find(|&y| !grid_elements.is(Position::new(x, y), Element::Air))?;
+            let surface_position = Position::new(x, ground_y - 1);
+
+            if !nest.is_aboveground(&surface_position) {
+                return None;
+            }
+
+            let digging_cost =
+                get_digging_cost(Position::new(x, ground_y), grid_elements, hardness_query)?;
+            let distance = (x - landing_position.x).abs();
+
+            Some(((digging_cost + distance / 2, distance), surface_position))
+        })
+        .min_by_key(|&(cost, _)| cost)
+        .map(|(_, surface_position)| surface_position)
+}
+
+/// How much effort it takes to dig straight down from `position`, or None if something in the way can't be dug.
+fn get_digging_cost(
+    position: Position,
+    grid_elements: &GridElements<AtNest>,
+    hardness_query: &Query<&Hardness>,
+) -> Option<isize> {
+    let mut digging_cost = 0;
+
+    for y in 0..DIG_SITE_SEARCH_DEPTH {
+        let Some(&element_entity) = grid_elements.get_entity(position + Position::new(0, y)) else {
+            break;
+        };
+
+        digging_cost += match grid_elements.get_element(element_entity)? {
+            Element::Air | Element::Food | Element::Mold => 0,
+            Element::Dirt => {
+                1 + hardness_query
+                    .get(element_entity)
+                    .map_or(0, |hardness| hardness.0)
+            }
+            Element::Sand => 2,
+            Element::Rock | Element::Water | Element::Mud => return None,
+        };
+    }
+
+    Some(digging_cost)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Flight, Winged};
+    use crate::{
+        common::{
+            ant::task::{AntTask, Task},
+            position::Position,
+        },
+        nest_simulation::ant::nesting::Nesting,
+        test_support::TestSimulation,
+    };
+    use bevy::prelude::*;
+
+    fn take_flight(simulation: &mut TestSimulation, position: Position, flight: Flight) -> Entity {
+        let ant_entity = simulation.nest_ant(position);
+        simulation
+            .world_mut()
+            .entity_mut(ant_entity)
+            .insert((Winged, AntTask::new(Task::Flying(flight))));
+
+        ant_entity
+    }
+
+    /// Tick until the queen has landed, which takes a few ticks per step of her descent.
+    fn land(simulation: &mut TestSimulation, ant_entity: Entity) -> Task {
+        for _ in 0..50 {
+            simulation.tick(1);
+
+            if simulation.world().get::<Winged>(ant_entity).is_none() {
+                break;
+            }
+        }
+
+        *simulation
+            .world()
+            .get::<AntTask>(ant_entity)
+            .unwrap()
+            .current()
+    }
+
+    // Confirm that a landing queen descends to the surface, sheds her wings, and chooses somewhere to dig.
+    #[test]
+    fn landing_queen_sheds_wings_and_seeks_dig_site() {
+        let mut simulation = TestSimulation::new(
+            "
+            .....Q....
+            ..........
+            ..........
+            ##########
+            ##########
+            ##########
+            ##########
+            ##########
+            ##########
+            ",
+        );
+        let ant_entity = take_flight(&mut simulation, Position::new(5, 0), Flight::Landing(5));
+
+        let task = land(&mut simulation, ant_entity);
+
+        assert!(simulation.world().get::<Winged>(ant_entity).is_none());
+        assert_eq!(
+            simulation.world().get::<Position>(ant_entity),
+            Some(&Position::new(5, 2))
+        );
+        assert_eq!(task, Task::Nesting(Nesting::Seeking(Position::new(5, 2))));
+    }
+
+    // Confirm that a landing queen who can't climb over something in her way lands where she is instead.
+    #[test]
+    fn landing_queen_blocked_at_top_lands_where_she_is() {
+        let mut simulation = TestSimulation::new(
+            "
+            ..Q.R.....
+            ..........
+            ..........
+            ##########
+            ##########
+            ##########
+            ##########
+            ##########
+            ##########
+            ",
+        );
+        let ant_entity = take_flight(&mut simulation, Position::new(2, 0), Flight::Landing(6));
+
+        let task = land(&mut simulation, ant_entity);
+
+        assert!(simulation.world().get::<Winged>(ant_entity).is_none());
+        assert!(matches!(task, Task::Nesting(_)));
+        assert_eq!(simulation.world().get::<Position>(ant_entity).unwrap().x, 3);
+    }
+
+    // Confirm that the queen won't choose to dig where there's rock beneath the surface.
+    #[test]
+    fn dig_site_avoids_rock() {
+        let mut simulation = TestSimulation::new(
+            "
+            .....Q....
+            ..........
+            ..........
+            ##########
+            #####R####
+            ##########
+            ##########
+            ##########
+            ##########
+            ",
+        );
+        let ant_entity = take_flight(&mut simulation, Position::new(5, 0), Flight::Landing(5));
+
+        let Task::Nesting(Nesting::Seeking(dig_site)) = land(&mut simulation, ant_entity) else {
+            panic!("Queen didn't choose a dig site.");
+        };
+        assert_ne!(dig_site.x, 5);
+    }
+}
@@ -0,0 +1,249 @@
+use super::{
+    haul::{pick_up_stray_items, set_down_carried_items},
+    NestOrientation,
+};
+use crate::{
+    common::{
+        ant::{
+            caste::Caste,
+            digestion::Digestion,
+            hunger::{get_regurgitation_amount, Hunger},
+            initiative::Initiative,
+            AntInventory, AntRole,
+        },
+        element::Element,
+        grid::GridElements,
+        position::Position,
+        Zone,
+    },
+    nest_simulation::{
+        brood::Brood,
+        nest::{AtNest, Nest},
+    },
+};
+use bevy::prelude::*;
+use bevy_turborand::{DelegatedRng, GlobalRng};
+
+/// Brood are kept in chambers rather than in tunnels, where they'd be in the way, or on the surface, where they'd be
+/// exposed. A chamber is anywhere that's mostly open space.
+pub fn is_in_chamber<Z: Zone>(
+    position: Position,
+    nest: &Nest,
+    grid_elements: &GridElements<Z>,
+) -> bool {
+    if !nest.is_underground(&position) {
+        return false;
+    }
+
+    let air_count = (-2..=2)
+        .flat_map(|y| (-2..=2).map(move |x| position + Position::new(x, y)))
+        .filter(|&position| grid_elements.is(position, Element::Air))
+        .count();
+
+    air_count >= 14
+}
+
+/// Ants feed hungry larvae, which are in front of them or underfoot, by regurgitating food to them.
+/// This works just like ants sharing food with one another, but larvae only accept food once they've digested their last meal.
+/// Nurses are the most attentive to larvae.
+pub fn ants_feed_larvae(
+    mut ants_query: Query<
+        (
+            &mut Initiative,
+            &mut Digestion,
+            &Hunger,
+            &Position,
+            &NestOrientation,
+            &AntInventory,
+            Option<&Caste>,
+        ),
+        With<AtNest>,
+    >,
+    mut larvae_query: Query<
+        (&Position, &Hunger, &mut Digestion),
+        (With<Brood>, Without<Initiative>, With<AtNest>),
+    >,
+    mut rng: ResMut<GlobalRng>,
+) {
+    for (mut initiative, mut digestion, hunger, position, orientation, inventory, caste) in
+        ants_query.iter_mut()
+    {
+        if !initiative.can_act() || inventory.0 != None || hunger.is_hungry() {
+            continue;
+        }
+
+        let ahead_position = orientation.get_ahead_position(position);
+
+        let Some((_, _, mut larva_digestion)) =
+            larvae_query
+                .iter_mut()
+                .find(|(&larva_position, larva_hunger, larva_digestion)| {
+                    (larva_position == ahead_position || larva_position == *position)
+                        && larva_hunger.is_peckish()
+                        && !larva_digestion.is_digesting()
+                })
+        else {
+            continue;
+        };
+
+        let digestion_transfer_amount = get_regurgitation_amount(&digestion);
+        if digestion_transfer_amount <= 0.0 {
+            continue;
+        }
+
+        let nursing_weight = caste.map_or(1.0, |caste| caste.weights().nursing);
+        if !rng.chance(nursing_weight.into()) {
+            continue;
+        }
+
+        digestion.regurgitate(&mut larva_digestion, digestion_transfer_amount);
+
+        initiative.consume();
+    }
+}
+
+/// Workers pick up brood, which are in front of them or underfoot, when the brood have been left outside of a chamber.
+/// Nurses are the most likely to notice stray brood.
+pub fn ants_pick_up_brood(
+    mut ants_query: Query<
+        (
+            &mut Initiative,
+            &mut AntInventory,
+            &Position,
+            &NestOrientation,
+            &AntRole,
+            Option<&Caste>,
+        ),
+        With<AtNest>,
+    >,
+    brood_query: Query<(Entity, &Position), (With<Brood>, Without<Initiative>, With<AtNest>)>,
+    nest_query: Query<&Nest>,
+    grid_elements: GridElements<AtNest>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    let nest = nest_query.single();
+
+    pick_up_stray_items(
+        &mut ants_query,
+        &brood_query,
+        |position| is_in_chamber(position, nest, &grid_elements),
+        |caste| {
+            let nursing_weight = caste.map_or(1.0, |caste| caste.weights().nursing);
+            rng.chance(nursing_weight.into())
+        },
+        &mut commands,
+    );
+}
+
+/// Workers carrying brood set them down, underfoot, once they're standing on the floor of a chamber.
+/// Brood aren't piled on top of one another so they spread out across the floor.
+pub fn ants_set_down_brood(
+    mut ants_query: Query<
+        (
+            &mut Initiative,
+            &mut AntInventory,
+            &Position,
+            &NestOrientation,
+        ),
+        With<AtNest>,
+    >,
+    brood_query: Query<Option<&Position>, (With<Brood>, Without<Initiative>, With<AtNest>)>,
+    nest_query: Query<&Nest>,
+    grid_elements: GridElements<AtNest>,
+    mut commands: Commands,
+) {
+    let nest = nest_query.single();
+
+    set_down_carried_items(
+        &mut ants_query,
+        &brood_query,
+        |position| is_in_chamber(position, nest, &grid_elements),
+        &mut commands,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        common::{
+            ant::{digestion::Digestion, hunger::Hunger, AntInventory},
+            position::Position,
+        },
+        nest_simulation::{ant::NestFacing, brood::BroodStage},
+        test_support::TestSimulation,
+    };
+
+    // Confirm that a worker feeds a hungry larva in front of it.
+    #[test]
+    fn worker_feeds_hungry_larva() {
+        let mut simulation = TestSimulation::new(
+            "
+            ....
+            ####
+            #W.#
+            ####
+            ",
+        );
+        let ant_entity = simulation.nest_ant(Position::new(1, 2));
+        simulation.face_nest_ant(ant_entity, NestFacing::Right);
+        let larva_entity = simulation.spawn_brood(Position::new(2, 2), BroodStage::Larva);
+        simulation
+            .world_mut()
+            .get_mut::<Hunger>(larva_entity)
+            .unwrap()
+            .set_value(60.0);
+
+        simulation.tick(6);
+
+        assert!(simulation
+            .world()
+            .get::<Digestion>(larva_entity)
+            .unwrap()
+            .is_digesting());
+    }
+
+    // Confirm that a worker picks up an egg left in a tunnel and sets it down in a chamber.
+    #[test]
+    fn worker_carries_egg_into_chamber() {
+        let mut simulation = TestSimulation::new(
+            "
+            .........
+            #########
+            #.......#
+            #.......#
+            #.......#
+            #W.######
+            #########
+            ",
+        );
+        let ant_entity = simulation.nest_ant(Position::new(1, 5));
+        simulation.face_nest_ant(ant_entity, NestFacing::Right);
+        let egg_entity = simulation.spawn_brood(Position::new(2, 5), BroodStage::Egg);
+
+        simulation.tick(6);
+
+        assert_eq!(
+            simulation
+                .world()
+                .get::<AntInventory>(ant_entity)
+                .unwrap()
+                .0,
+            Some(egg_entity)
+        );
+        assert!(simulation.world().get::<Position>(egg_entity).is_none());
+
+        // Walk the ant out into the middle of the chamber.
+        *simulation
+            .world_mut()
+            .get_mut::<Position>(ant_entity)
+            .unwrap() = Position::new(4, 4);
+
+        simulation.tick(6);
+
+        assert_eq!(
+            simulation.world().get::<Position>(egg_entity),
+            Some(&Position::new(4, 4))
+        );
+    }
+}
@@ -0,0 +1,104 @@
+use super::{NestAngle, NestFacing, NestOrientation};
+use crate::{
+    common::{
+        ant::initiative::Initiative,
+        element::Element,
+        grid::{Grid, GridElements},
+        position::Position,
+    },
+    nest_simulation::nest::{AtNest, Nest},
+    weather::Weather,
+};
+use bevy::prelude::*;
+
+/// Ants shelter from the rain. Ants caught on the surface make for the nearest nest entrance, and ants underground
+/// turn back rather than climb out into the open.
+pub fn ants_seek_shelter(
+    weather: Res<Weather>,
+    mut ants_query: Query<(&mut Initiative, &Position, &mut NestOrientation), With<AtNest>>,
+    nest_query: Query<(&Grid, &Nest), With<AtNest>>,
+    grid_elements: GridElements<AtNest>,
+) {
+    if !weather.is_raining() {
+        return;
+    }
+
+    let (grid, nest) = nest_query.single();
+
+    // An entrance is anywhere an ant standing on the surface could step down into the nest.
+    let entrance_x_positions = (0..grid.width())
+        .filter(|&x| grid_elements.is(Position::new(x, nest.surface_level() + 1), Element::Air))
+        .collect::<Vec<_>>();
+
+    for (mut initiative, position, mut orientation) in ants_query.iter_mut() {
+        if !initiative.can_move() {
+            continue;
+        }
+
+        if nest.is_underground(position) {
+            if nest.is_aboveground(&orientation.get_ahead_position(position)) {
+                *orientation = orientation.turn_around();
+                initiative.consume_movement();
+            }
+
+            continue;
+        }
+
+        if !orientation.is_rightside_up() {
+            continue;
+        }
+
+        let Some(entrance_x) = entrance_x_positions
+            .iter()
+            .min_by_key(|&&x| (x - position.x).abs())
+        else {
+            continue;
+        };
+
+        // Only turn to face the entrance - walking there is left to wandering.
+        let facing = match entrance_x.cmp(&position.x) {
+            std::cmp::Ordering::Less => NestFacing::Left,
+            std::cmp::Ordering::Greater => NestFacing::Right,
+            std::cmp::Ordering::Equal => continue,
+        };
+
+        if orientation.get_facing() != facing {
+            *orientation = NestOrientation::new(facing, NestAngle::Zero);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        common::position::Position, nest_simulation::ant::NestFacing, test_support::TestSimulation,
+        weather::Weather,
+    };
+
+    // Confirm that an ant caught on the surface while it's raining turns around and takes shelter in the nest.
+    #[test]
+    fn ant_on_surface_shelters_in_nest_from_rain() {
+        let mut simulation = TestSimulation::new(
+            "
+            .......
+            .....W.
+            ##.####
+            ##.####
+            ##.####
+            ",
+        );
+        let ant_entity = simulation.nest_ant(Position::new(5, 1));
+        // Face away from the entrance.
+        simulation.face_nest_ant(ant_entity, NestFacing::Right);
+        simulation
+            .world_mut()
+            .resource_mut::<Weather>()
+            .rain_until(isize::MAX);
+
+        simulation.tick(30);
+
+        let position = *simulation.world().get::<Position>(ant_entity).unwrap();
+        assert_eq!(position.x, 2);
+        assert!(position.y > 1);
+    }
+}
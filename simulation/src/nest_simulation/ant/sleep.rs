@@ -1,10 +1,13 @@
 use bevy::prelude::*;
-use bevy_turborand::GlobalRng;
-use serde::{Deserialize, Serialize};
 
 use crate::{
     common::{
-        ant::{initiative::Initiative, AntInventory},
+        ant::{
+            commands::AntCommandsExt,
+            initiative::Initiative,
+            task::{AntTask, Task},
+            AntInventory,
+        },
         position::Position,
     },
     nest_simulation::nest::{AtNest, Nest},
@@ -13,10 +16,6 @@ use crate::{
 
 use super::NestOrientation;
 
-#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
-#[reflect(Component)]
-pub struct Asleep;
-
 pub fn ants_sleep(
     ants_query: Query<
         (Entity, &Position, &NestOrientation, &AntInventory),
@@ -37,28 +36,25 @@ pub fn ants_sleep(
             && ant_orientation.is_rightside_up()
             && ant_inventory.0 == None
         {
-            commands
-                .entity(ant_entity)
-                .insert(Asleep)
-                .remove::<Initiative>();
+            // Falling asleep removes the ant's Initiative so it doesn't move or act until it wakes.
+            commands.start_task(ant_entity, Task::Asleep);
         }
     }
 }
 
 pub fn ants_wake(
-    ants_query: Query<Entity, (With<Asleep>, With<AtNest>)>,
+    ants_query: Query<(Entity, &AntTask), With<AtNest>>,
     mut commands: Commands,
     story_time: Res<StoryTime>,
-    mut rng: ResMut<GlobalRng>,
 ) {
     if story_time.is_nighttime() {
         return;
     }
 
-    for ant_entity in ants_query.iter() {
-        commands
-            .entity(ant_entity)
-            .remove::<Asleep>()
-            .insert(Initiative::new(&mut rng.reborrow()));
+    for (ant_entity, ant_task) in ants_query.iter() {
+        if ant_task.is_asleep() {
+            // Waking up restores the ant's Initiative and resumes whatever it was doing before it fell asleep.
+            commands.finish_task(ant_entity, Task::Asleep);
+        }
     }
 }
@@ -3,24 +3,25 @@ use bevy_turborand::{DelegatedRng, GlobalRng};
 
 use crate::{
     common::{
-        ant::{initiative::Initiative, AntInventory, AntRole},
+        ant::{
+            caste::Caste, commands::AntCommandsExt, initiative::Initiative, task::Task,
+            AntInventory, AntRole,
+        },
         grid::Grid,
         position::Position,
     },
-    crater_simulation::{
-        ant::{emit_pheromone::LeavingNest, CraterOrientation},
-        crater::AtCrater,
-    },
+    crater_simulation::{ant::CraterOrientation, crater::AtCrater},
     nest_simulation::nest::{AtNest, Nest},
     settings::Settings,
+    weather::Weather,
 };
 
-use super::{chambering::Chambering, tunneling::Tunneling, NestFacing, NestOrientation};
+use super::{NestFacing, NestOrientation};
 
 // TODO: Maybe put this in common since it relies on knowledge of AtCrater and AtNest
 
 /// If an ant is on the surface, and it's facing the edge of the nest, and it's not carrying anything
-/// then it is able to leave the nest and go out into the crater.
+/// then it is able to leave the nest and go out into the crater. Foragers are the most likely to venture out.
 pub fn ants_travel_to_crater(
     mut ants_query: Query<
         (
@@ -30,6 +31,7 @@ pub fn ants_travel_to_crater(
             &NestOrientation,
             &AntInventory,
             &AntRole,
+            Option<&Caste>,
         ),
         With<AtNest>,
     >,
@@ -37,10 +39,16 @@ pub fn ants_travel_to_crater(
     mut rng: ResMut<GlobalRng>,
     mut commands: Commands,
     settings: Res<Settings>,
+    weather: Res<Weather>,
 ) {
+    // Ants stay home while it's raining.
+    if weather.is_raining() {
+        return;
+    }
+
     let (grid, nest) = nest_query.single();
 
-    for (ant_entity, mut initiative, position, orientation, inventory, role) in
+    for (ant_entity, mut initiative, position, orientation, inventory, role, caste) in
         ants_query.iter_mut()
     {
         if !initiative.can_move() {
@@ -71,7 +79,8 @@ pub fn ants_travel_to_crater(
 
         // TODO: Adjust probability and read from settings
         // Must be attempting to walk outside the bounds of the nest - consider leaving
-        if rng.chance(0.5) {
+        let foraging_weight = caste.map_or(1.0, |caste| caste.weights().foraging);
+        if !rng.chance((0.5 * foraging_weight).into()) {
             continue;
         }
 
@@ -85,11 +94,8 @@ pub fn ants_travel_to_crater(
         commands
             .entity(ant_entity)
             .remove::<AtNest>()
-            .remove::<Tunneling>()
-            .remove::<Chambering>()
             .remove::<NestOrientation>()
             .insert(AtCrater)
-            .insert(LeavingNest(50.0))
             .insert(Position::new(
                 // TODO: Express this more clearly - trying to not have it appear ontop of the nest sprite
                 (settings.crater_width / 2) + 1,
@@ -97,6 +103,9 @@ pub fn ants_travel_to_crater(
             ))
             .insert(crater_orientation);
 
+        // Any digging left unfinished in the nest is abandoned.
+        commands.start_task(ant_entity, Task::LeavingNest(50.0));
+
         initiative.consume();
     }
 }
@@ -1,7 +1,12 @@
 use super::{birthing::Birthing, NestOrientation};
 use crate::{
     common::{
-        ant::{commands::AntCommandsExt, initiative::Initiative, AntInventory},
+        ant::{
+            commands::AntCommandsExt,
+            initiative::Initiative,
+            task::{AntTask, Task},
+            AntInventory,
+        },
         element::Element,
         grid::{Grid, GridElements},
         pheromone::{
@@ -18,12 +23,6 @@ use crate::{
 };
 use bevy::prelude::*;
 use bevy_turborand::GlobalRng;
-use serde::{Deserialize, Serialize};
-
-// TODO: Consider replacing this with something more like a "CurrentAction" enum to reflect that an Ant shouldn't perform multiple actions at once.
-#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
-#[reflect(Component)]
-pub struct Tunneling(pub f32);
 
 // "Whenever ant walks over tile with nesting pheromone, they gain "Nesting: 8". Then, they attempt to take a step forward and decrement Nesting to 7. If they end up digging, nesting is "forgotten" and they shift back to hauling dirt
 // so they haul it out, then walk back, hit the pheromone again, and repeatedly try to take 8 steps
@@ -32,8 +31,13 @@ pub struct Tunneling(pub f32);
 // if they do not see any adjacent dirt, they clean up the pheromone and, in the case of the queen, shift to giving birth
 pub fn ants_tunnel_pheromone_move(
     mut ants_query: Query<
-        (&mut NestOrientation, &mut Initiative, &mut Position),
-        (With<Tunneling>, With<AtNest>),
+        (
+            &mut NestOrientation,
+            &mut Initiative,
+            &mut Position,
+            &AntTask,
+        ),
+        With<AtNest>,
     >,
     nest_query: Query<&Nest>,
     mut rng: ResMut<GlobalRng>,
@@ -41,8 +45,8 @@ pub fn ants_tunnel_pheromone_move(
 ) {
     let nest = nest_query.single();
 
-    for (mut orientation, mut initiative, mut ant_position) in ants_query.iter_mut() {
-        if !initiative.can_move() {
+    for (mut orientation, mut initiative, mut ant_position, task) in ants_query.iter_mut() {
+        if !matches!(task.current(), Task::Tunneling(_)) || !initiative.can_move() {
             continue;
         }
 
@@ -115,7 +119,7 @@ pub fn ants_tunnel_pheromone_act(
             &Initiative,
             &Position,
             Entity,
-            &Tunneling,
+            &AntTask,
         ),
         With<AtNest>,
     >,
@@ -126,7 +130,11 @@ pub fn ants_tunnel_pheromone_act(
 ) {
     let grid = grid_query.single();
 
-    for (orientation, inventory, initiative, position, ant_entity, tunneling) in ants_query.iter() {
+    for (orientation, inventory, initiative, position, ant_entity, task) in ants_query.iter() {
+        let Task::Tunneling(tunneling) = *task.current() else {
+            continue;
+        };
+
         if !initiative.can_act() {
             continue;
         }
@@ -151,7 +159,8 @@ pub fn ants_tunnel_pheromone_act(
         let entity = grid_elements.entity(ahead_position);
         let element = grid_elements.element(*entity);
 
-        if *element == Element::Air {
+        // Rock can't be dug so the ant defers to its default action and wanders around it.
+        if !element.is_diggable() {
             continue;
         }
 
@@ -162,11 +171,11 @@ pub fn ants_tunnel_pheromone_act(
         // Reduce PheromoneStrength by 1 because not digging at ant_position, but ant_position + 1.
         // If this didn't occur then either the ant would need to apply strength-1 to itself when stepping onto a tile, or
         // PheromoneStrength would never reduce.
-        if tunneling.0 - 1.0 > 0.0 {
+        if tunneling - 1.0 > 0.0 {
             commands.spawn_pheromone(
                 dig_position,
                 Pheromone::Tunnel,
-                PheromoneStrength::new(tunneling.0 - 1.0, settings.tunnel_length as f32),
+                PheromoneStrength::new(tunneling - 1.0, settings.tunnel_length as f32),
                 AtNest,
             );
         }
@@ -206,9 +215,7 @@ pub fn ants_add_tunnel_pheromone(
                     pheromone_query.get(*pheromone_entity).unwrap();
 
                 if *pheromone == Pheromone::Tunnel {
-                    commands
-                        .entity(ant_entity)
-                        .insert(Tunneling(pheromone_strength.value()));
+                    commands.start_task(ant_entity, Task::Tunneling(pheromone_strength.value()));
                 }
             }
         }
@@ -217,10 +224,14 @@ pub fn ants_add_tunnel_pheromone(
 
 /// Whenever an ant takes a step it loses 1 Tunneling pheromone.
 pub fn ants_fade_tunnel_pheromone(
-    mut ants_query: Query<&mut Tunneling, (Changed<Position>, With<AtNest>)>,
+    mut ants_query: Query<&mut AntTask, (Changed<Position>, With<AtNest>)>,
 ) {
-    for mut tunneling in ants_query.iter_mut() {
-        tunneling.0 -= 1.0;
+    for mut task in ants_query.iter_mut() {
+        let Task::Tunneling(tunneling) = *task.current() else {
+            continue;
+        };
+
+        *task.current_mut() = Task::Tunneling(tunneling - 1.0);
     }
 }
 
@@ -229,7 +240,7 @@ pub fn ants_fade_tunnel_pheromone(
 /// Ants lose Tunneling when they've exhausted their pheromone by taking sufficient steps.
 pub fn ants_remove_tunnel_pheromone(
     mut ants_query: Query<
-        (Entity, &Position, &AntInventory, &Tunneling),
+        (Entity, &Position, &AntInventory, &AntTask),
         (Or<(Changed<Position>, Changed<AntInventory>)>, With<AtNest>),
     >,
     pheromone_query: Query<(&Pheromone, &PheromoneStrength)>,
@@ -241,13 +252,17 @@ pub fn ants_remove_tunnel_pheromone(
 ) {
     let nest = nest_query.single();
 
-    for (ant_entity, ant_position, inventory, tunneling) in ants_query.iter_mut() {
+    for (ant_entity, ant_position, inventory, task) in ants_query.iter_mut() {
+        let Task::Tunneling(tunneling) = *task.current() else {
+            continue;
+        };
+
         if inventory.0 != None {
-            commands.entity(ant_entity).remove::<Tunneling>();
+            commands.finish_task(ant_entity, Task::Tunneling(tunneling));
         } else if nest.is_aboveground(ant_position) {
-            commands.entity(ant_entity).remove::<Tunneling>();
-        } else if tunneling.0 <= 0.0 {
-            commands.entity(ant_entity).remove::<Tunneling>();
+            commands.finish_task(ant_entity, Task::Tunneling(tunneling));
+        } else if tunneling <= 0.0 {
+            commands.finish_task(ant_entity, Task::Tunneling(tunneling));
 
             let adjacent_positions = ant_position.get_adjacent_positions();
 
@@ -294,3 +309,143 @@ pub fn ants_remove_tunnel_pheromone(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        common::{
+            ant::task::{AntTask, Task},
+            element::{Element, Hardness},
+            pheromone::{Pheromone, PheromoneEntityPositionCache},
+            position::Position,
+        },
+        nest_simulation::{ant::NestFacing, nest::AtNest},
+        settings::{Probabilities, Settings},
+        test_support::TestSimulation,
+    };
+
+    fn setup_tunneling_ant() -> TestSimulation {
+        setup_tunneling_ant_in_nest(
+            "
+            ....
+            ####
+            W###
+            ####
+            ",
+        )
+    }
+
+    /// The ant must be at (0, 2) and is faced right, towards (1, 2).
+    fn setup_tunneling_ant_in_nest(nest_map: &str) -> TestSimulation {
+        let settings = Settings {
+            probabilities: Probabilities {
+                random_drop: 0.0,
+                ..Settings::default().probabilities
+            },
+            ..Settings::default()
+        };
+
+        let mut simulation = TestSimulation::with_settings(nest_map, settings);
+
+        let ant_entity = simulation.nest_ant(Position::new(0, 2));
+        simulation.face_nest_ant(ant_entity, NestFacing::Right);
+        simulation
+            .world_mut()
+            .entity_mut(ant_entity)
+            .insert(AntTask::new(Task::Tunneling(8.0)));
+
+        simulation
+    }
+
+    // Confirm that a tunneling ant digs through the dirt in front of it.
+    #[test]
+    fn tunneling_ant_digs_dirt() {
+        let mut simulation = setup_tunneling_ant();
+        let ant_entity = simulation.nest_ant(Position::new(0, 2));
+
+        simulation.tick(10);
+
+        // Dirt is loosened into sand when it's dug.
+        assert_eq!(
+            simulation.inventory_element(ant_entity),
+            Some(Element::Sand)
+        );
+        // Tunneling is forgotten once the ant has something to carry.
+        assert!(!matches!(
+            simulation
+                .world()
+                .get::<AntTask>(ant_entity)
+                .unwrap()
+                .current(),
+            Task::Tunneling(_)
+        ));
+        assert_eq!(simulation.nest_element(Position::new(1, 2)), Element::Air);
+    }
+
+    // Confirm that tunneling leaves a weaker pheromone behind so the tunnel is continued by the next ant.
+    #[test]
+    fn tunneling_ant_extends_tunnel_pheromone() {
+        let mut simulation = setup_tunneling_ant();
+
+        simulation.tick(10);
+
+        let world = simulation.world_mut();
+        let pheromone_entities = world
+            .resource::<PheromoneEntityPositionCache<AtNest>>()
+            .get(&Position::new(1, 2))
+            .cloned()
+            .unwrap_or_default();
+
+        assert!(pheromone_entities
+            .iter()
+            .any(|&entity| *world.get::<Pheromone>(entity).unwrap() == Pheromone::Tunnel));
+    }
+
+    // Confirm that hard dirt takes several digs to excavate.
+    #[test]
+    fn tunneling_ant_chips_away_hard_dirt() {
+        let mut simulation = setup_tunneling_ant();
+        let ant_entity = simulation.nest_ant(Position::new(0, 2));
+        let dirt_entity = simulation.nest_element_entity(Position::new(1, 2));
+        simulation
+            .world_mut()
+            .entity_mut(dirt_entity)
+            .insert(Hardness(2));
+
+        simulation.tick(10);
+
+        assert_eq!(simulation.inventory_element(ant_entity), None);
+        assert_eq!(simulation.nest_element(Position::new(1, 2)), Element::Dirt);
+        assert!(simulation.world().get::<Hardness>(dirt_entity).unwrap().0 < 2);
+
+        simulation.tick(50);
+
+        assert_eq!(
+            simulation.inventory_element(ant_entity),
+            Some(Element::Sand)
+        );
+        assert_eq!(simulation.nest_element(Position::new(1, 2)), Element::Air);
+    }
+
+    // Confirm that a tunneling ant can't dig through rock, but instead turns and digs the dirt around it.
+    #[test]
+    fn tunneling_ant_does_not_dig_rock() {
+        let mut simulation = setup_tunneling_ant_in_nest(
+            "
+            ....
+            ####
+            WR##
+            ####
+            ",
+        );
+        let ant_entity = simulation.nest_ant(Position::new(0, 2));
+
+        simulation.tick(50);
+
+        assert_eq!(simulation.nest_element(Position::new(1, 2)), Element::Rock);
+        assert_eq!(
+            simulation.inventory_element(ant_entity),
+            Some(Element::Sand)
+        );
+    }
+}
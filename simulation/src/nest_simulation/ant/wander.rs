@@ -143,7 +143,7 @@ pub fn get_turned_orientation(
     all_orientations[rng.usize(0..all_orientations.len())]
 }
 
-fn is_valid_location(
+pub fn is_valid_location(
     orientation: NestOrientation,
     position: Position,
     nest: &Nest,
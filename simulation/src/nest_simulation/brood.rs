@@ -0,0 +1,356 @@
+use super::{
+    ant::{NestAngle, NestFacing, NestOrientation},
+    nest::AtNest,
+};
+use crate::{
+    common::{
+        ant::{
+            commands::AntCommandsExt, digestion::Digestion, hunger::Hunger, initiative::Initiative,
+            AntColor, AntInventory, AntName, AntRole,
+        },
+        element::FoodKind,
+        position::Position,
+    },
+    settings::Settings,
+    story_time::DEFAULT_TICKS_PER_SECOND,
+};
+use bevy::prelude::*;
+use bevy_turborand::GlobalRng;
+use serde::{Deserialize, Serialize};
+
+/// Brood are the colony's young. They're laid by the queen as eggs, hatch into larvae which must be fed,
+/// pupate, and then emerge as adult workers. Brood can't move on their own, but workers carry them into chambers.
+/// While carried, brood are held in an ant's inventory and have no Position.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Component)]
+pub struct Brood;
+
+#[derive(
+    Component, Debug, PartialEq, Eq, Copy, Clone, Serialize, Deserialize, Reflect, Default, Hash,
+)]
+#[reflect(Component)]
+pub enum BroodStage {
+    #[default]
+    Egg,
+    Larva,
+    Pupa,
+}
+
+impl BroodStage {
+    /// The kind of food this stage grows best on. Only larvae feed, and they need protein to grow.
+    pub fn preferred_food_kind(&self) -> Option<FoodKind> {
+        match self {
+            BroodStage::Larva => Some(FoodKind::Protein),
+            BroodStage::Egg | BroodStage::Pupa => None,
+        }
+    }
+}
+
+/// How far along brood is in developing into its next stage.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Component)]
+pub struct Development {
+    value: f32,
+    max: f32,
+    rate: f32,
+}
+
+impl Development {
+    pub fn new(max_time_seconds: isize) -> Self {
+        let max = 100.0;
+        let rate = max / (max_time_seconds * DEFAULT_TICKS_PER_SECOND) as f32;
+
+        Self {
+            value: 0.0,
+            max,
+            rate,
+        }
+    }
+
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    pub fn tick(&mut self) {
+        self.value = (self.value + self.rate).min(self.max);
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.value >= self.max
+    }
+}
+
+#[derive(Bundle)]
+pub struct BroodBundle {
+    brood: Brood,
+    stage: BroodStage,
+    development: Development,
+    color: AntColor,
+    position: Position,
+    zone: AtNest,
+}
+
+impl BroodBundle {
+    /// A freshly laid egg. Brood inherit their color from the queen who laid them.
+    pub fn new(position: Position, color: AntColor, settings: &Settings) -> Self {
+        Self {
+            brood: Brood,
+            stage: BroodStage::Egg,
+            development: Development::new(settings.max_egg_time),
+            color,
+            position,
+            zone: AtNest,
+        }
+    }
+}
+
+pub fn register_brood(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<Brood>();
+    app_type_registry.write().register::<BroodStage>();
+    app_type_registry.write().register::<Development>();
+}
+
+/// Brood develop over time. Eggs and pupae develop on their own, but larvae only grow while they're well fed.
+/// Larvae are given Hunger, just like ants, so that workers can feed them by regurgitation. Larvae which are digesting
+/// the kind of food they prefer grow twice as quickly.
+pub fn brood_develop(
+    mut brood_query: Query<
+        (
+            Entity,
+            &mut BroodStage,
+            &mut Development,
+            Option<&Hunger>,
+            Option<&Digestion>,
+            Option<&Position>,
+            &AntColor,
+        ),
+        (With<Brood>, With<AtNest>),
+    >,
+    settings: Res<Settings>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    for (brood_entity, mut stage, mut development, hunger, digestion, position, color) in
+        brood_query.iter_mut()
+    {
+        if hunger.map_or(true, |hunger| !hunger.is_hungry()) {
+            development.tick();
+
+            let is_digesting_preferred_food = digestion.map_or(false, |digestion| {
+                digestion.is_digesting()
+                    && stage.preferred_food_kind() == Some(digestion.food_kind())
+            });
+
+            if is_digesting_preferred_food {
+                development.tick();
+            }
+        }
+
+        if !development.is_ready() {
+            continue;
+        }
+
+        match *stage {
+            BroodStage::Egg => {
+                *stage = BroodStage::Larva;
+                *development = Development::new(settings.max_larva_time);
+
+                commands.entity(brood_entity).insert((
+                    Hunger::new(settings.max_hunger_time),
+                    Digestion::new(settings.max_digestion_time),
+                ));
+            }
+            BroodStage::Larva => {
+                *stage = BroodStage::Pupa;
+                *development = Development::new(settings.max_pupa_time);
+
+                commands
+                    .entity(brood_entity)
+                    .remove::<(Hunger, Digestion)>();
+            }
+            BroodStage::Pupa => {
+                // Adults can't emerge while being carried. They'll emerge once they've been set down.
+                let Some(&position) = position else {
+                    continue;
+                };
+
+                commands.spawn_ant(
+                    position,
+                    *color,
+                    Some(NestOrientation::new(
+                        NestFacing::random(&mut rng.reborrow()),
+                        NestAngle::Zero,
+                    )),
+                    None,
+                    AntInventory::default(),
+                    AntRole::Worker,
+                    AntName::random(&mut rng.reborrow()),
+                    Initiative::new(&mut rng.reborrow()),
+                    AtNest,
+                );
+
+                commands.entity(brood_entity).despawn();
+            }
+        }
+    }
+}
+
+/// Larvae which go unfed for too long starve. Brood don't leave a body behind.
+pub fn brood_starve(
+    brood_query: Query<(Entity, &Hunger), (With<Brood>, With<AtNest>)>,
+    mut commands: Commands,
+) {
+    for (brood_entity, hunger) in brood_query.iter() {
+        if hunger.is_starved() {
+            commands.entity(brood_entity).despawn();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Brood, BroodStage, Development};
+    use crate::{
+        common::{
+            ant::{digestion::Digestion, hunger::Hunger, Ant},
+            element::FoodKind,
+            position::Position,
+        },
+        nest_simulation::ant::birthing::Birthing,
+        settings::Settings,
+        test_support::TestSimulation,
+    };
+    use bevy::prelude::*;
+
+    fn count<T: Component>(simulation: &mut TestSimulation) -> usize {
+        simulation
+            .world_mut()
+            .query_filtered::<(), With<T>>()
+            .iter(simulation.world())
+            .count()
+    }
+
+    // Confirm that the queen lays an egg, rather than giving birth to an adult worker.
+    #[test]
+    fn queen_lays_egg() {
+        let mut simulation = TestSimulation::new(
+            "
+            .....
+            #####
+            #Q..#
+            #####
+            ",
+        );
+        let queen_entity = simulation.nest_ant(Position::new(1, 2));
+        simulation
+            .world_mut()
+            .entity_mut(queen_entity)
+            .insert(Birthing::new(1));
+
+        simulation.tick(16);
+
+        assert_eq!(count::<Brood>(&mut simulation), 1);
+        assert_eq!(count::<Ant>(&mut simulation), 1);
+    }
+
+    // Confirm that a well fed egg grows through each stage and emerges as an adult worker.
+    #[test]
+    fn brood_develops_into_worker() {
+        let settings = Settings {
+            max_egg_time: 1,
+            max_larva_time: 1,
+            max_pupa_time: 1,
+            ..Settings::default()
+        };
+
+        let mut simulation = TestSimulation::with_settings(
+            "
+            .....
+            #####
+            #...#
+            #####
+            ",
+            settings,
+        );
+        let brood_entity = simulation.spawn_brood(Position::new(2, 2), BroodStage::Egg);
+
+        simulation.tick(11);
+        assert_eq!(
+            simulation.world().get::<BroodStage>(brood_entity),
+            Some(&BroodStage::Larva)
+        );
+        assert!(simulation.world().get::<Hunger>(brood_entity).is_some());
+
+        simulation.tick(10);
+        assert_eq!(
+            simulation.world().get::<BroodStage>(brood_entity),
+            Some(&BroodStage::Pupa)
+        );
+        assert!(simulation.world().get::<Hunger>(brood_entity).is_none());
+
+        simulation.tick(10);
+        assert_eq!(count::<Brood>(&mut simulation), 0);
+        simulation.nest_ant(Position::new(2, 2));
+    }
+
+    // Confirm that a larva which isn't fed stops growing and starves.
+    #[test]
+    fn unfed_larva_starves() {
+        let settings = Settings {
+            max_egg_time: 1,
+            max_hunger_time: 1,
+            ..Settings::default()
+        };
+
+        let mut simulation = TestSimulation::with_settings(
+            "
+            .....
+            #####
+            #...#
+            #####
+            ",
+            settings,
+        );
+        simulation.spawn_brood(Position::new(2, 2), BroodStage::Egg);
+
+        simulation.tick(25);
+
+        assert_eq!(count::<Brood>(&mut simulation), 0);
+        assert_eq!(count::<Ant>(&mut simulation), 0);
+    }
+
+    // Confirm that a larva fed the food it prefers grows more quickly than one fed something else.
+    #[test]
+    fn larva_grows_faster_on_preferred_food() {
+        let mut simulation = TestSimulation::new(
+            "
+            .....
+            #####
+            #...#
+            #####
+            ",
+        );
+
+        let larva_entities = [FoodKind::Protein, FoodKind::Seed].map(|food_kind| {
+            let larva_entity = simulation.spawn_brood(Position::new(2, 2), BroodStage::Larva);
+            simulation
+                .world_mut()
+                .get_mut::<Digestion>(larva_entity)
+                .unwrap()
+                .eat(food_kind);
+
+            larva_entity
+        });
+
+        simulation.tick(10);
+
+        let [protein_development, seed_development] = larva_entities.map(|larva_entity| {
+            simulation
+                .world()
+                .get::<Development>(larva_entity)
+                .unwrap()
+                .value()
+        });
+        assert!(protein_development > seed_development);
+    }
+}
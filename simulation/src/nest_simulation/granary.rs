@@ -0,0 +1,287 @@
+use super::{
+    ant::{wander::is_valid_location, NestOrientation},
+    nest::{AtNest, Nest},
+};
+use crate::{
+    common::{
+        ant::{
+            hunger::Hunger,
+            initiative::Initiative,
+            task::{AntTask, Task},
+            AntInventory, AntRole,
+        },
+        element::{Element, Food},
+        grid::GridElements,
+        position::Position,
+    },
+    settings::Settings,
+    story_time::SECONDS_PER_DAY,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+/// How far a granary extends from where it was designated. This matches the area considered when judging whether
+/// somewhere is a chamber, so a granary fills the chamber it was designated in.
+pub const GRANARY_RADIUS: isize = 2;
+
+/// A chamber set aside for storing food. Workers bring food here rather than dropping it wherever, and hungry workers
+/// come here to eat.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Component)]
+pub struct Granary;
+
+pub fn register_granary(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<Granary>();
+}
+
+pub fn is_in_granary(position: &Position, granary_position: &Position) -> bool {
+    (position.x - granary_position.x).abs() <= GRANARY_RADIUS
+        && (position.y - granary_position.y).abs() <= GRANARY_RADIUS
+}
+
+pub fn is_in_any_granary(position: &Position, granary_positions: &[Position]) -> bool {
+    granary_positions
+        .iter()
+        .any(|granary_position| is_in_granary(position, granary_position))
+}
+
+/// How many days the food stored across all granaries would feed `ant_count` ants. `stored_nutrition` is the total
+/// nutrition of the stored food, which varies by FoodKind, and each point of nutrition relieves a full stomach's Hunger.
+pub fn get_stored_food_days(stored_nutrition: f32, ant_count: usize, settings: &Settings) -> f32 {
+    if ant_count == 0 {
+        return f32::INFINITY;
+    }
+
+    let hunger_per_ant_per_day = 100.0 * SECONDS_PER_DAY as f32 / settings.max_hunger_time as f32;
+
+    stored_nutrition * 100.0 / (ant_count as f32 * hunger_per_ant_per_day)
+}
+
+/// How many steps it takes to reach a granary from each open position in the nest.
+fn get_granary_distances(
+    granary_positions: &[Position],
+    grid_elements: &GridElements<AtNest>,
+) -> HashMap<Position, isize> {
+    let mut distances = HashMap::new();
+    let mut queue = VecDeque::new();
+
+    for granary_position in granary_positions {
+        for y in -GRANARY_RADIUS..=GRANARY_RADIUS {
+            for x in -GRANARY_RADIUS..=GRANARY_RADIUS {
+                let position = *granary_position + Position::new(x, y);
+
+                if grid_elements.is(position, Element::Air) && !distances.contains_key(&position) {
+                    distances.insert(position, 0);
+                    queue.push_back(position);
+                }
+            }
+        }
+    }
+
+    while let Some(position) = queue.pop_front() {
+        let distance = distances[&position];
+
+        for offset in [Position::X, Position::NEG_X, Position::Y, Position::NEG_Y] {
+            let adjacent_position = position + offset;
+
+            if grid_elements.is(adjacent_position, Element::Air)
+                && !distances.contains_key(&adjacent_position)
+            {
+                distances.insert(adjacent_position, distance + 1);
+                queue.push_back(adjacent_position);
+            }
+        }
+    }
+
+    distances
+}
+
+/// Idle workers carrying food make for the nearest granary to store it. Hungry workers do the same, to eat, so long as
+/// there's food stored. Like sheltering from the rain, ants only turn to face the way there - walking is left to wandering.
+pub fn ants_seek_granary(
+    mut ants_query: Query<
+        (
+            &mut Initiative,
+            &Position,
+            &mut NestOrientation,
+            &AntInventory,
+            &AntRole,
+            &Hunger,
+            &AntTask,
+        ),
+        With<AtNest>,
+    >,
+    granary_query: Query<&Position, (With<Granary>, With<AtNest>)>,
+    food_query: Query<&Position, (With<Food>, With<AtNest>)>,
+    elements_query: Query<&Element>,
+    nest_query: Query<&Nest>,
+    grid_elements: GridElements<AtNest>,
+) {
+    let granary_positions = granary_query.iter().copied().collect::<Vec<_>>();
+    if granary_positions.is_empty() {
+        return;
+    }
+
+    let nest = nest_query.single();
+    let has_stored_food = food_query
+        .iter()
+        .any(|position| is_in_any_granary(position, &granary_positions));
+
+    // Finding the way to the granaries is relatively expensive so it's only done when an ant needs it.
+    let mut granary_distances = None;
+
+    for (mut initiative, position, mut orientation, inventory, role, hunger, ant_task) in
+        ants_query.iter_mut()
+    {
+        if *role != AntRole::Worker || !initiative.can_move() || *ant_task.current() != Task::Idle {
+            continue;
+        }
+
+        if is_in_any_granary(position, &granary_positions) {
+            continue;
+        }
+
+        let is_carrying_food = inventory.0.map_or(false, |item_entity| {
+            elements_query.get(item_entity) == Ok(&Element::Food)
+        });
+        let is_seeking_food = inventory.0 == None && hunger.is_peckish() && has_stored_food;
+
+        if !is_carrying_food && !is_seeking_food {
+            continue;
+        }
+
+        let distances = granary_distances
+            .get_or_insert_with(|| get_granary_distances(&granary_positions, &grid_elements));
+
+        let Some(&distance) = distances.get(position) else {
+            continue;
+        };
+
+        let Some((_, next_orientation)) = NestOrientation::all_orientations()
+            .into_iter()
+            .filter(|&next_orientation| {
+                is_valid_location(next_orientation, *position, nest, &grid_elements)
+            })
+            .filter_map(|next_orientation| {
+                distances
+                    .get(&next_orientation.get_ahead_position(position))
+                    .map(|&next_distance| (next_distance, next_orientation))
+            })
+            .filter(|&(next_distance, _)| next_distance < distance)
+            .min_by_key(|&(next_distance, _)| next_distance)
+        else {
+            continue;
+        };
+
+        if *orientation != next_orientation {
+            *orientation = next_orientation;
+            initiative.consume_movement();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_stored_food_days, is_in_granary, Granary};
+    use crate::{
+        common::{
+            element::{Element, FoodKind},
+            position::Position,
+        },
+        nest_simulation::{ant::NestFacing, nest::AtNest},
+        settings::{Probabilities, Settings},
+        test_support::TestSimulation,
+    };
+
+    // Confirm that a day's worth of food for one ant is five seeds, since each relieves a fifth of its Hunger,
+    // and that more nutritious food lasts longer.
+    #[test]
+    fn stored_food_days() {
+        let settings = Settings::default();
+        let seed_nutrition = FoodKind::Seed.nutrition();
+
+        assert!((get_stored_food_days(5.0 * seed_nutrition, 1, &settings) - 1.0).abs() < 0.001);
+        assert!((get_stored_food_days(20.0 * seed_nutrition, 2, &settings) - 2.0).abs() < 0.001);
+        assert!(
+            get_stored_food_days(5.0 * FoodKind::Protein.nutrition(), 1, &settings)
+                > get_stored_food_days(5.0 * seed_nutrition, 1, &settings)
+        );
+    }
+
+    // Confirm that a worker carrying food along a tunnel, away from the granary, turns back and stores it there.
+    #[test]
+    fn worker_stores_food_in_granary() {
+        let settings = Settings {
+            probabilities: Probabilities {
+                random_drop: 0.0,
+                ..Settings::default().probabilities
+            },
+            ..Settings::default()
+        };
+
+        let mut simulation = TestSimulation::with_settings(
+            "
+            ..............
+            ##############
+            #.....########
+            #.....########
+            #.........W..#
+            ##############
+            ",
+            settings,
+        );
+        simulation
+            .world_mut()
+            .spawn((Granary, Position::new(3, 3), AtNest));
+        let ant_entity = simulation.nest_ant(Position::new(10, 4));
+        simulation.face_nest_ant(ant_entity, NestFacing::Right);
+        simulation.give_nest_ant(ant_entity, Element::Food);
+
+        simulation.tick(300);
+
+        assert_eq!(simulation.inventory_element(ant_entity), None);
+        let food_position = (1..=5)
+            .flat_map(|y| (1..=12).map(move |x| Position::new(x, y)))
+            .find(|&position| simulation.nest_element(position) == Element::Food)
+            .unwrap();
+        assert!(is_in_granary(&food_position, &Position::new(3, 3)));
+    }
+
+    // Confirm that, once there's a granary, workers only store food inside of it.
+    #[test]
+    fn worker_only_drops_food_in_granary() {
+        let settings = Settings {
+            probabilities: Probabilities {
+                random_drop: 0.0,
+                below_surface_food_drop: 1.0,
+                ..Settings::default().probabilities
+            },
+            ..Settings::default()
+        };
+
+        let mut simulation = TestSimulation::with_settings(
+            "
+            ..............
+            ##############
+            #.....########
+            #.....#......#
+            #.....#....W.#
+            ##############
+            ",
+            settings,
+        );
+        simulation
+            .world_mut()
+            .spawn((Granary, Position::new(3, 3), AtNest));
+        let ant_entity = simulation.nest_ant(Position::new(11, 4));
+        simulation.give_nest_ant(ant_entity, Element::Food);
+
+        simulation.tick(100);
+
+        assert_eq!(
+            simulation.inventory_element(ant_entity),
+            Some(Element::Food)
+        );
+    }
+}
@@ -1,17 +1,21 @@
-use super::{ant::NestOrientation, nest::AtNest};
+use super::{
+    ant::{nuptial_flight::Winged, NestOrientation},
+    brood::Brood,
+    nest::AtNest,
+};
 use crate::{
     common::{
         ant::{initiative::Initiative, Dead},
-        element::{commands::ElementCommandsExt, Air, Element},
+        element::{commands::ElementCommandsExt, Air, Element, Hardness, Rock},
         grid::{Grid, GridElements, GridElementsMut},
         position::Position,
     },
     nest_simulation::nest::Nest,
     settings::Settings,
 };
-use bevy::{prelude::*, utils::HashSet};
+use bevy::prelude::*;
 use bevy_turborand::{DelegatedRng, GlobalRng};
-use std::marker::PhantomData;
+use std::{collections::BTreeSet, marker::PhantomData};
 
 // Sand becomes unstable temporarily when falling or adjacent to falling sand
 #[derive(Component, Reflect, Default)]
@@ -77,7 +81,8 @@ fn get_element_fall_position(
 
 pub fn gravity_elements(
     mut element_position_queries: ParamSet<(
-        Query<&Position, (With<Element>, With<Unstable>, With<AtNest>)>,
+        // Rock is never moved by gravity, no matter what's beneath it.
+        Query<&Position, (With<Element>, With<Unstable>, Without<Rock>, With<AtNest>)>,
         Query<&mut Position, (With<Element>, With<AtNest>)>,
     )>,
     mut rng: ResMut<GlobalRng>,
@@ -128,6 +133,7 @@ pub fn gravity_ants(
             &mut Position,
             Option<&mut Initiative>,
             Option<&Dead>,
+            Option<&Winged>,
         ),
         With<AtNest>,
     >,
@@ -138,7 +144,12 @@ pub fn gravity_ants(
 ) {
     let (grid, nest) = nest_query.single();
 
-    for (orientation, mut position, initiative, dead) in ants_query.iter_mut() {
+    for (orientation, mut position, initiative, dead, winged) in ants_query.iter_mut() {
+        // Queens on their nuptial flight stay aloft, unless they die mid-flight.
+        if winged.is_some() && dead.is_none() {
+            continue;
+        }
+
         // Figure out foot direction
         let below_position = orientation.get_below_position(&position);
 
@@ -179,6 +190,20 @@ pub fn gravity_ants(
     }
 }
 
+/// Brood can't cling to walls like ants can, so they fall whenever there's air beneath them.
+pub fn gravity_brood(
+    mut brood_query: Query<&mut Position, (With<Brood>, With<AtNest>)>,
+    grid_elements: GridElements<AtNest>,
+) {
+    for mut position in brood_query.iter_mut() {
+        let below_position = *position + Position::Y;
+
+        if grid_elements.is(below_position, Element::Air) {
+            *position = below_position;
+        }
+    }
+}
+
 // If an air gap appears on the grid (either through spawning or movement of air) then mark adjacent elements as unstable.
 pub fn gravity_mark_unstable(
     air_query: Query<&Position, (With<Air>, Changed<Position>, With<AtNest>)>,
@@ -186,7 +211,9 @@ pub fn gravity_mark_unstable(
     nest_query: Query<&Nest>,
     grid_elements: GridElements<AtNest>,
 ) {
-    let mut positions = HashSet::new();
+    // Use an ordered set because the order in which elements are marked unstable affects the order in which they fall.
+    // bevy's HashSet isn't seeded consistently between runs, so iterating it would make seeded colonies nondeterministic.
+    let mut positions = BTreeSet::new();
 
     for &position in air_query.iter() {
         positions.insert(position + Position::new(-1, -1));
@@ -197,13 +224,14 @@ pub fn gravity_mark_unstable(
     let nest = nest_query.single();
 
     for &position in &positions {
-        // If the current position contains a sand or food element, mark it as unstable
+        // If the current position contains a sand, food, or mold element, mark it as unstable
         if let Some(entity) = grid_elements.get_entity(position) {
             let element = grid_elements.element(*entity);
 
             // Special Case - dirt aboveground doesn't have "background" supporting dirt to keep it stable - so it falls.
-            if matches!(*element, Element::Sand | Element::Food)
-                || *element == Element::Dirt && nest.is_aboveground(&position)
+            if matches!(*element, Element::Sand | Element::Food | Element::Mold)
+                || matches!(*element, Element::Dirt | Element::Mud)
+                    && nest.is_aboveground(&position)
             {
                 commands.toggle_element_command(
                     *entity,
@@ -225,6 +253,76 @@ pub fn gravity_mark_unstable(
     }
 }
 
+// Measure how far a ceiling tile, directly above the given open position, is from the nearest wall supporting it.
+// Walls are found by walking the open row beneath the ceiling. The edge of the grid counts as a wall.
+fn get_ceiling_support_distance(position: Position, grid_elements: &GridElements<AtNest>) -> isize {
+    let is_open = |position: Position| {
+        grid_elements.is(position, Element::Air) || grid_elements.is(position, Element::Water)
+    };
+
+    let left_distance = (1..)
+        .find(|&distance| !is_open(position - Position::new(distance, 0)))
+        .unwrap();
+    let right_distance = (1..)
+        .find(|&distance| !is_open(position + Position::new(distance, 0)))
+        .unwrap();
+
+    left_distance.min(right_distance)
+}
+
+/// Dirt holds itself up underground, but only so far. Ceiling dirt further than `Settings::ceiling_support_distance`
+/// from the nearest wall has a chance of cracking and collapsing into falling sand. The further it is from a wall,
+/// the likelier it is to collapse, while hard, compacted dirt resists and soft, wet mud gives way readily.
+/// Collapsing from the middle outward carves wide ceilings into arches, which stop collapsing once they're narrow
+/// enough to support themselves, so chambers which are too wide shed sand until they're domed.
+pub fn gravity_collapse_dirt(
+    air_query: Query<&Position, (With<Air>, With<AtNest>)>,
+    hardness_query: Query<&Hardness>,
+    nest_query: Query<&Nest>,
+    grid_elements: GridElements<AtNest>,
+    settings: Res<Settings>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    let nest = nest_query.single();
+
+    for &air_position in air_query.iter() {
+        let ceiling_position = air_position - Position::Y;
+
+        // Dirt aboveground has nothing to hold it up and is already subject to gravity.
+        if !nest.is_underground(&ceiling_position) {
+            continue;
+        }
+
+        let Some(&ceiling_entity) = grid_elements.get_entity(ceiling_position) else {
+            continue;
+        };
+
+        let collapse_probability = match grid_elements.element(ceiling_entity) {
+            Element::Dirt => settings.probabilities.dirt_collapse,
+            Element::Mud => settings.probabilities.mud_collapse,
+            _ => continue,
+        };
+
+        let support_distance = get_ceiling_support_distance(air_position, &grid_elements);
+        let unsupported_distance = support_distance - settings.ceiling_support_distance;
+        if unsupported_distance <= 0 {
+            continue;
+        }
+
+        let hardness = hardness_query
+            .get(ceiling_entity)
+            .map_or(0, |hardness| hardness.0);
+
+        let probability =
+            collapse_probability * unsupported_distance as f32 / (1 + hardness) as f32;
+
+        if rng.chance(probability.min(1.0).into()) {
+            commands.replace_element(ceiling_position, Element::Sand, ceiling_entity, AtNest);
+        }
+    }
+}
+
 /// Elements which were Unstable, but didn't move this frame, are marked Stable by removing their Unstable marker.
 /// FIXME: floating column of sand can result in sand being marked stable while in the air due to having sand directly beneath.
 pub fn gravity_mark_stable(
@@ -266,7 +364,7 @@ pub fn gravity_set_stability(
             Element::Air => {
                 commands.entity(entity).insert(Stable);
             }
-            Element::Dirt => {
+            Element::Dirt | Element::Mud => {
                 // Dirt that spawns below surface level is not unstable but dirt that is above is unstable.
                 if nest.is_underground(position) {
                     commands.entity(entity).insert(Stable);
@@ -278,452 +376,226 @@ pub fn gravity_set_stability(
             Element::Sand => {
                 commands.entity(entity).insert(Unstable);
             }
-            Element::Food => {
+            Element::Food | Element::Mold => {
                 commands.entity(entity).insert(Unstable);
             }
+            // Water is moved by `water_flow` rather than by gravity.
+            Element::Rock | Element::Water => {
+                commands.entity(entity).insert(Stable);
+            }
         }
     }
 }
 
-// #[cfg(test)]
-// pub mod ant_gravity_tests {
-//     use crate::save::WorldSaveState;
-
-//     use super::*;
-//     use bevy::{log::LogPlugin, utils::HashMap};
-//     use rand::{rngs::StdRng, SeedableRng};
-//     use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
-
-//     wasm_bindgen_test_configure!(run_in_browser);
-
-//     fn setup(element_grid: Vec<Vec<Element>>) -> App {
-//         let mut app = App::new();
-//         app.add_plugin(LogPlugin::default());
-//         app.add_systems(ant_gravity);
-
-//         let seed = 42069; // ayy lmao
-//         let world_rng = Rng {
-//             rng: StdRng::seed_from_u64(seed),
-//         };
-
-//         app.insert_resource(world_rng);
-
-//         // TODO: probably reuse setup function between gravity and ant tests - maybe all tests
-//         let spawned_elements: HashMap<_, _> = element_grid
-//             .iter()
-//             .enumerate()
-//             .map(|(y, row)| {
-//                 row.iter()
-//                     .enumerate()
-//                     .map(|(x, element)| {
-//                         let position = Position::new(x as isize, y as isize);
-//                         let entity = app
-//                             .world
-//                             .spawn(ElementBundle::create(*element, position))
-//                             .id();
-
-//                         (position, entity)
-//                     })
-//                     .collect::<Vec<_>>()
-//             })
-//             .flatten()
-//             .collect();
-
-//         let height = element_grid.len() as isize;
-//         let width = element_grid.first().map_or(0, |row| row.len()) as isize;
-//         let nest = Nest::new(
-//             width,
-//             height,
-//             0.0,
-//             WorldSaveState::default(),
-//             Some(spawned_elements),
-//         );
-//         app.insert_resource(nest);
-//         app.insert_resource(Settings {
-//             nest_width: width,
-//             nest_height: height,
-//             ..default()
-//         });
-
-//         app
-//     }
-
-//     #[wasm_bindgen_test]
-//     fn upright_ant_over_air_falls_down() {
-//         // Arrange
-//         // let element_grid = vec![vec![Element::Air], vec![Element::Air]];
-//         // let mut app = setup(element_grid);
-//     }
-
-//     fn upright_ant_over_dirt_stays_put() {}
-
-//     fn sideways_left_ant_standing_dirt_over_air_stays_put() {}
-
-//     fn sideways_left_ant_standing_air_over_air_falls_down() {}
-
-//     fn sideways_right_ant_standing_dirt_over_air_stays_put() {}
-
-//     fn sideways_right_ant_standing_air_over_air_falls_down() {}
-
-//     // TODO: This is sus. A sideways ant is able to cling to dirt, but if it starts falling, it should probably keep falling
-//     // rather than exhibiting a super-ant ability to cling to dirt mid-fall.
-//     fn sideways_falling_ant_grabs_dirt() {}
-// }
-
-// TODO: confirm elements are despawned not just that grid is correct
-// #[cfg(test)]
-// pub mod sand_gravity_tests {
-//     use crate::{
-//         element::{ElementBundle, SandElementBundle},
-//         grid::WorldSaveState,
-//     };
-
-//     use super::*;
-//     use bevy::{log::LogPlugin, utils::HashMap};
-//     use rand::{rngs::StdRng, SeedableRng};
-//     use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
-
-//     wasm_bindgen_test_configure!(run_in_browser);
-
-//     // Create a new application to be used for testing the gravity system.
-//     // Map and flatten a grid of elements and spawn associated elements into the world.
-//     fn setup(element_grid: Vec<Vec<Element>>, seed: Option<u64>) -> App {
-//         let mut app = App::new();
-//         app.add_plugin(LogPlugin::default());
-//         app.add_systems(sand_gravity);
-
-//         let seed = seed.unwrap_or(42069); // ayy lmao
-//         let world_rng = Rng(StdRng::seed_from_u64(seed));
-
-//         app.insert_resource(world_rng);
-
-//         let spawned_elements: HashMap<_, _> = element_grid
-//             .iter()
-//             .enumerate()
-//             .map(|(y, row)| {
-//                 row.iter()
-//                     .enumerate()
-//                     .map(|(x, element)| {
-//                         let position = Position::new(x as isize, y as isize);
-
-//                         let entity = match element {
-//                             Element::Air => app.world.spawn(ElementBundle::new(Element::Air, position)).id(),
-//                             Element::Dirt => app.world.spawn(ElementBundle::new(Element::Dirt, position)).id(),
-//                             Element::Sand => app.world.spawn(ElementBundle::new(Element::Sand, position)).id(),
-//                         };
-
-//                         (position, entity)
-//                     })
-//                     .collect::<Vec<_>>()
-//             })
-//             .flatten()
-//             .collect();
-
-//         let height = element_grid.len() as isize;
-//         let width = element_grid.first().map_or(0, |row| row.len()) as isize;
-//         let nest = Nest::new(
-//             width,
-//             height,
-//             0,
-//             WorldSaveState::default(),
-//             Some(spawned_elements),
-//         );
-//         app.insert_resource(nest);
-//         app.insert_resource(Settings {
-//             nest_width: width,
-//             nest_height: height,
-//             ..default()
-//         });
-
-//         app
-//     }
-
-//     // Confirm that sand successfully falls downward through multiple tiles of air.
-//     #[wasm_bindgen_test]
-//     fn did_sand_fall_down() {
-//         // Arrange
-//         let element_grid = vec![vec![Element::Sand], vec![Element::Air], vec![Element::Air]];
-//         let mut app = setup(element_grid, None);
-
-//         // Act
-//         app.update();
-//         app.update();
-
-//         // Assert
-//         let Some(nest) = app.world.get_resource::<Nest>() else { panic!() };
-
-//         assert_eq!(
-//             app.world
-//                 .get::<Element>(nest.elements[&Position::ZERO]),
-//             Some(&Element::Air)
-//         );
-//         assert_eq!(
-//             app.world.get::<Element>(nest.elements[&Position::Y]),
-//             Some(&Element::Air)
-//         );
-//         assert_eq!(
-//             app.world
-//                 .get::<Element>(nest.elements[&Position::new(0, 2)]),
-//             Some(&Element::Sand)
-//         );
-//     }
-
-//     // Confirm that sand ontop of non-air stays put
-//     #[wasm_bindgen_test]
-//     fn did_sand_not_fall_down() {
-//         // Arrange
-//         let element_grid = vec![vec![Element::Sand], vec![Element::Dirt]];
-//         let mut app = setup(element_grid, None);
-
-//         // Act
-//         app.update();
-
-//         // Assert
-//         let Some(nest) = app.world.get_resource::<Nest>() else { panic!() };
-
-//         assert_eq!(
-//             app.world
-//                 .get::<Element>(nest.elements[&Position::ZERO]),
-//             Some(&Element::Sand)
-//         );
-//         assert_eq!(
-//             app.world.get::<Element>(nest.elements[&Position::Y]),
-//             Some(&Element::Dirt)
-//         );
-//     }
-
-//     // Confirm that sand falls properly to the left
-//     #[wasm_bindgen_test]
-//     fn did_sand_fall_left() {
-//         // Arrange
-//         let element_grid = vec![
-//             vec![Element::Air, Element::Sand],
-//             vec![Element::Air, Element::Dirt],
-//         ];
-//         let mut app = setup(element_grid, None);
-
-//         // Act
-//         app.update();
-
-//         // Assert
-//         let Some(nest) = app.world.get_resource::<Nest>() else { panic!() };
-
-//         assert_eq!(
-//             app.world.get::<Element>(nest.elements[&Position::X]),
-//             Some(&Element::Air)
-//         );
-//         assert_eq!(
-//             app.world.get::<Element>(nest.elements[&Position::Y]),
-//             Some(&Element::Sand)
-//         );
-//     }
-
-//     // Confirm that sand falls properly to the right
-//     #[wasm_bindgen_test]
-//     fn did_sand_fall_right() {
-//         // Arrange
-//         let element_grid = vec![
-//             vec![Element::Sand, Element::Air],
-//             vec![Element::Dirt, Element::Air],
-//         ];
-//         let mut app = setup(element_grid, None);
-
-//         // Act
-//         app.update();
-
-//         // Assert
-//         let Some(nest) = app.world.get_resource::<Nest>() else { panic!() };
-
-//         assert_eq!(
-//             app.world
-//                 .get::<Element>(nest.elements[&Position::ZERO]),
-//             Some(&Element::Air)
-//         );
-//         assert_eq!(
-//             app.world.get::<Element>(nest.elements[&Position::ONE]),
-//             Some(&Element::Sand)
-//         );
-//     }
-
-//     // Confirm that sand falls to the left on a tie between l/r when given an appropriate random seed
-//     #[wasm_bindgen_test]
-//     fn did_sand_fall_left_by_chance() {
-//         // Arrange
-//         let element_grid = vec![
-//             vec![Element::Air, Element::Sand, Element::Air],
-//             vec![Element::Air, Element::Dirt, Element::Air],
-//         ];
-//         let mut app = setup(element_grid, Some(3));
-
-//         // Act
-//         app.update();
-
-//         // Assert
-//         let Some(nest) = app.world.get_resource::<Nest>() else { panic!() };
-
-//         assert_eq!(
-//             app.world.get::<Element>(nest.elements[&Position::Y]),
-//             Some(&Element::Sand)
-//         );
-//     }
-
-//     // Confirm that sand falls to the right on a tie between l/r when given an appropriate random seed
-//     #[wasm_bindgen_test]
-//     fn did_sand_fall_right_by_chance() {
-//         // Arrange
-//         let element_grid = vec![
-//             vec![Element::Air, Element::Sand, Element::Air],
-//             vec![Element::Air, Element::Dirt, Element::Air],
-//         ];
-//         let mut app = setup(element_grid, Some(1));
-
-//         // Act
-//         app.update();
-
-//         // Assert
-//         let Some(nest) = app.world.get_resource::<Nest>() else { panic!() };
-
-//         assert_eq!(
-//             app.world
-//                 .get::<Element>(nest.elements[&Position::new(2, 1)]),
-//             Some(&Element::Sand)
-//         );
-//     }
-
-//     // Confirm that sand does not fall to the left if blocked to its upper-left
-//     #[wasm_bindgen_test]
-//     fn did_sand_not_fall_upper_left() {
-//         // Arrange
-//         let element_grid = vec![
-//             vec![Element::Dirt, Element::Sand],
-//             vec![Element::Air, Element::Dirt],
-//         ];
-//         let mut app = setup(element_grid, None);
-
-//         // Act
-//         app.update();
-
-//         // Assert
-//         let Some(nest) = app.world.get_resource::<Nest>() else { panic!() };
-
-//         assert_eq!(
-//             app.world.get::<Element>(nest.elements[&Position::X]),
-//             Some(&Element::Sand)
-//         );
-//     }
-
-//     // Confirm that sand does not fall to the left if blocked to its lower-left
-//     #[wasm_bindgen_test]
-//     fn did_sand_not_fall_lower_left() {
-//         // Arrange
-//         let element_grid = vec![
-//             vec![Element::Air, Element::Sand],
-//             vec![Element::Dirt, Element::Dirt],
-//         ];
-//         let mut app = setup(element_grid, None);
-
-//         // Act
-//         app.update();
-
-//         // Assert
-//         let Some(nest) = app.world.get_resource::<Nest>() else { panic!() };
-
-//         assert_eq!(
-//             app.world.get::<Element>(nest.elements[&Position::X]),
-//             Some(&Element::Sand)
-//         );
-//     }
-
-//     // Confirm that sand does not fall to the right if blocked to its upper-right
-//     #[wasm_bindgen_test]
-//     fn did_sand_not_fall_upper_right() {
-//         // Arrange
-//         let element_grid = vec![
-//             vec![Element::Sand, Element::Dirt],
-//             vec![Element::Dirt, Element::Air],
-//         ];
-//         let mut app = setup(element_grid, None);
-
-//         // Act
-//         app.update();
-
-//         // Assert
-//         let Some(nest) = app.world.get_resource::<Nest>() else { panic!() };
-
-//         assert_eq!(
-//             app.world
-//                 .get::<Element>(nest.elements[&Position::ZERO]),
-//             Some(&Element::Sand)
-//         );
-//     }
-
-//     // Confirm that sand does not fall to the right if blocked to its lower-right
-//     #[wasm_bindgen_test]
-//     fn did_sand_not_fall_lower_right() {
-//         // Arrange
-//         let element_grid = vec![
-//             vec![Element::Sand, Element::Air],
-//             vec![Element::Dirt, Element::Dirt],
-//         ];
-//         let mut app = setup(element_grid, None);
-
-//         // Act
-//         app.update();
-
-//         // Assert
-//         let Some(nest) = app.world.get_resource::<Nest>() else { panic!() };
-
-//         assert_eq!(
-//             app.world
-//                 .get::<Element>(nest.elements[&Position::ZERO]),
-//             Some(&Element::Sand)
-//         );
-//     }
-
-//     // Confirm that a pillar of sand will compact the bottom into dirt
-//     #[wasm_bindgen_test]
-//     fn did_sand_column_compact() {
-//         // Arrange
-//         let element_grid = vec![vec![Element::Sand]; 16];
-//         let mut app = setup(element_grid, None);
-
-//         // Act
-//         app.update();
-
-//         // Assert
-//         let Some(nest) = app.world.get_resource::<Nest>() else { panic!() };
-
-//         assert_eq!(
-//             app.world
-//                 .get::<Element>(nest.elements[&Position::new(0, 15)]),
-//             Some(&Element::Dirt)
-//         );
-//     }
-
-//     // Confirm that a pillar of floating sand falls downward instead of compacting into dirt
-//     #[wasm_bindgen_test]
-//     fn did_floating_sand_column_not_compact() {
-//         // Arrange
-//         let mut element_grid = vec![vec![Element::Sand]; 16];
-//         element_grid.push(vec![Element::Air]);
-//         let mut app = setup(element_grid, None);
-
-//         // Act
-//         app.update();
-
-//         // Assert
-//         let Some(nest) = app.world.get_resource::<Nest>() else { panic!() };
-
-//         assert_eq!(
-//             app.world
-//                 .get::<Element>(nest.elements[&Position::new(0, 15)]),
-//             Some(&Element::Air)
-//         );
-
-//         assert_eq!(
-//             app.world
-//                 .get::<Element>(nest.elements[&Position::new(0, 16)]),
-//             Some(&Element::Sand)
-//         );
-//     }
-// }
+#[cfg(test)]
+mod tests {
+    use crate::{
+        settings::{Probabilities, Settings},
+        test_support::TestSimulation,
+    };
+
+    // Confirm that sand successfully falls downward through multiple tiles of air.
+    #[test]
+    fn sand_falls_down() {
+        let mut simulation = TestSimulation::new(
+            "
+            s
+            .
+            .
+            #
+            ",
+        );
+
+        simulation.tick(3);
+
+        simulation.assert_nest(
+            "
+            .
+            .
+            s
+            #
+            ",
+        );
+    }
+
+    // Confirm that sand ontop of non-air stays put.
+    #[test]
+    fn sand_on_dirt_stays_put() {
+        let mut simulation = TestSimulation::new(
+            "
+            s
+            #
+            ",
+        );
+
+        simulation.tick(3);
+
+        simulation.assert_nest(
+            "
+            s
+            #
+            ",
+        );
+    }
+
+    // Confirm that sand does not fall diagonally when blocked to its side.
+    #[test]
+    fn sand_blocked_to_side_stays_put() {
+        let mut simulation = TestSimulation::new(
+            "
+            #s
+            .#
+            ##
+            ",
+        );
+
+        simulation.tick(3);
+
+        simulation.assert_nest(
+            "
+            #s
+            .#
+            ##
+            ",
+        );
+    }
+
+    // Confirm that food falls just like sand.
+    #[test]
+    fn food_falls_down() {
+        let mut simulation = TestSimulation::new(
+            "
+            f
+            .
+            #
+            ",
+        );
+
+        simulation.tick(2);
+
+        simulation.assert_nest(
+            "
+            .
+            f
+            #
+            ",
+        );
+    }
+
+    // Confirm that rock hangs in place, without support, and supports whatever rests on it.
+    #[test]
+    fn rock_never_falls() {
+        let mut simulation = TestSimulation::new(
+            "
+            s.
+            R.
+            ..
+            ##
+            ",
+        );
+
+        simulation.tick(3);
+
+        simulation.assert_nest(
+            "
+            s.
+            R.
+            ..
+            ##
+            ",
+        );
+    }
+
+    // Confirm that a wide, unsupported ceiling collapses into sand from the middle until it's arched.
+    #[test]
+    fn wide_ceiling_collapses_into_arch() {
+        let settings = Settings {
+            probabilities: Probabilities {
+                dirt_collapse: 1.0,
+                ..Settings::default().probabilities
+            },
+            ceiling_support_distance: 2,
+            ..Settings::default()
+        };
+
+        let mut simulation = TestSimulation::with_settings(
+            "
+            #########
+            #########
+            #.......#
+            #########
+            ",
+            settings,
+        );
+
+        simulation.tick(10);
+
+        simulation.assert_nest(
+            "
+            #########
+            ###...###
+            #..sss..#
+            #########
+            ",
+        );
+    }
+
+    // Confirm that a ceiling which is narrow enough to support itself never collapses.
+    #[test]
+    fn narrow_ceiling_stays_put() {
+        let settings = Settings {
+            probabilities: Probabilities {
+                dirt_collapse: 1.0,
+                mud_collapse: 1.0,
+                ..Settings::default().probabilities
+            },
+            ceiling_support_distance: 2,
+            ..Settings::default()
+        };
+
+        let mut simulation = TestSimulation::with_settings(
+            "
+            ##m##
+            #...#
+            #####
+            ",
+            settings,
+        );
+
+        simulation.tick(10);
+
+        simulation.assert_nest(
+            "
+            ##m##
+            #...#
+            #####
+            ",
+        );
+    }
+
+    // Confirm that an upright ant over air falls down until it lands.
+    #[test]
+    fn ant_over_air_falls_down() {
+        let mut simulation = TestSimulation::new(
+            "
+            ...
+            .W.
+            ...
+            ...
+            ###
+            ",
+        );
+
+        simulation.tick(3);
+
+        simulation.assert_nest(
+            "
+            ...
+            ...
+            ...
+            .W.
+            ###
+            ",
+        );
+    }
+}
@@ -1,6 +1,11 @@
 pub mod ant;
+pub mod brood;
+pub mod granary;
 pub mod gravity;
 pub mod nest;
+pub mod rain;
+pub mod spoilage;
+pub mod water;
 
 use crate::common::{
     ant::Ant,
@@ -21,11 +26,16 @@ use self::{
         },
         dig::ants_dig,
         drop::ants_drop,
+        flee_water::ants_flee_water,
+        midden::{ants_pick_up_corpse, ants_set_down_corpse, ants_sicken},
         nest_expansion::ants_nest_expansion,
         nesting::{
             ants_nesting_action, ants_nesting_movement, ants_nesting_start, register_nesting,
         },
+        nuptial_flight::{ants_nuptial_flight, register_nuptial_flight},
+        nursing::{ants_feed_larvae, ants_pick_up_brood, ants_set_down_brood},
         register_ant,
+        shelter::ants_seek_shelter,
         sleep::{ants_sleep, ants_wake},
         travel::ants_travel_to_crater,
         tunneling::{
@@ -34,18 +44,22 @@ use self::{
         },
         wander::{ants_stabilize_footing_movement, ants_wander},
     },
+    brood::{brood_develop, brood_starve, register_brood, Brood},
+    granary::{ants_seek_granary, register_granary, Granary},
     gravity::{
-        gravity_ants, gravity_elements, gravity_mark_stable, gravity_mark_unstable,
-        gravity_set_stability, register_gravity,
+        gravity_ants, gravity_brood, gravity_collapse_dirt, gravity_elements, gravity_mark_stable,
+        gravity_mark_unstable, gravity_set_stability, register_gravity,
     },
     nest::{
         insert_nest_grid, register_nest, spawn_nest, spawn_nest_ants, spawn_nest_elements, AtNest,
         Nest,
     },
+    rain::{rain_fall, rain_wash_sand, water_evaporate},
+    spoilage::{food_spoil, mold_spread},
+    water::{water_flow, water_soak_dirt},
 };
 use super::{
-    despawn_model, settings::initialize_settings_resources, AppState, CleanupSet, FinishSetupSet,
-    SimulationTickSet, StoryPlaybackState,
+    despawn_model, AppState, CleanupSet, FinishSetupSet, SimulationZoneSet, StoryPlaybackState,
 };
 use bevy::prelude::*;
 
@@ -57,7 +71,10 @@ impl Plugin for NestSimulationPlugin {
             Startup,
             (
                 register_nesting,
+                register_nuptial_flight,
                 register_birthing,
+                register_brood,
+                register_granary,
                 register_gravity,
                 register_ant,
                 register_nest,
@@ -65,16 +82,19 @@ impl Plugin for NestSimulationPlugin {
         );
 
         app.add_systems(
-            OnExit(AppState::MainMenu),
+            OnTransition {
+                exited: AppState::MainMenu,
+                entered: AppState::FinishSetup,
+            },
             (
                 // Call `apply_deferred` to ensure Settings (via `initialize_settings_resources`) is available for use.
                 apply_deferred,
                 spawn_nest,
                 apply_deferred,
-                (spawn_nest_elements, spawn_nest_ants),
+                (spawn_nest_elements, spawn_nest_ants).chain(),
             )
                 .chain()
-                .after(initialize_settings_resources),
+                .in_set(SimulationZoneSet::Nest),
         );
 
         app.add_systems(
@@ -99,17 +119,40 @@ impl Plugin for NestSimulationPlugin {
                     // It's helpful to apply gravity first because position updates are applied instantly and are seen by subsequent systems.
                     // Thus, ant actions can take into consideration where an element is this frame rather than where it was last frame.
                     gravity_elements,
+                    water_flow,
                     gravity_ants,
+                    gravity_brood,
                     // Gravity side-effects can run whenever with little difference.
                     gravity_mark_stable,
                     gravity_mark_unstable,
+                    gravity_collapse_dirt,
+                    water_soak_dirt,
+                    // Weather side-effects are applied alongside gravity so that rain and washed sand fall next tick.
+                    rain_fall,
+                    rain_wash_sand,
+                    water_evaporate,
                     apply_deferred,
                 )
                     .chain(),
                 (
                     // Apply specific ant actions in priority order because ants take a maximum of one action per tick.
                     // An ant should not starve to hunger due to continually choosing to dig a tunnel, etc.
+                    // A queen on her nuptial flight is above it all, so nothing else applies to her until she's landed.
+                    (ants_nuptial_flight, apply_deferred).chain(),
+                    // Ants caught in water are in danger so fleeing it comes before anything else.
+                    ants_flee_water,
+                    ants_seek_shelter,
                     ants_stabilize_footing_movement,
+                    // Nursing comes before birthing because the queen, who is always busy birthing, feeds her first larvae.
+                    (
+                        ants_feed_larvae,
+                        ants_set_down_brood,
+                        ants_pick_up_brood,
+                        apply_deferred,
+                    )
+                        .chain(),
+                    // Corpses are carried out to the midden so they don't make the colony sick.
+                    (ants_set_down_corpse, ants_pick_up_corpse, apply_deferred).chain(),
                     (ants_birthing, apply_deferred).chain(),
                     (ants_sleep, ants_wake, apply_deferred).chain(),
                     (
@@ -157,6 +200,7 @@ impl Plugin for NestSimulationPlugin {
                     (
                         ants_travel_to_crater,
                         apply_deferred,
+                        ants_seek_granary,
                         ants_wander,
                         ants_dig,
                         apply_deferred,
@@ -166,19 +210,24 @@ impl Plugin for NestSimulationPlugin {
                         .chain(),
                 )
                     .chain(),
+                (brood_develop, brood_starve, apply_deferred).chain(),
+                (food_spoil, apply_deferred, mold_spread, apply_deferred).chain(),
+                ants_sicken,
             )
                 .run_if(
                     in_state(AppState::TellStory { ended: false })
                         .and_then(not(in_state(StoryPlaybackState::Paused))),
                 )
                 .chain()
-                .in_set(SimulationTickSet::SimulationTick),
+                .in_set(SimulationZoneSet::Nest),
         );
 
         app.add_systems(
             OnEnter(AppState::Cleanup),
             (
                 despawn_model::<Ant, AtNest>,
+                despawn_model::<Brood, AtNest>,
+                despawn_model::<Granary, AtNest>,
                 despawn_model::<Element, AtNest>,
                 despawn_model::<ElementEntityPositionCache, AtNest>,
                 despawn_model::<Pheromone, AtNest>,
@@ -1,21 +1,35 @@
+pub mod terrain;
+
 use crate::{
     common::{
         ant::{
-            digestion::Digestion, hunger::Hunger, initiative::Initiative, AntBundle, AntColor,
-            AntInventory, AntName, AntRole,
+            age::Age,
+            digestion::Digestion,
+            hunger::Hunger,
+            initiative::Initiative,
+            task::{AntTask, Task},
+            thirst::Thirst,
+            AntBundle, AntColor, AntInventory, AntName, AntRole,
         },
-        element::{Element, ElementBundle},
+        element::{Element, ElementBundle, Hardness},
         grid::{ElementEntityPositionCache, Grid},
         position::Position,
         Zone,
     },
+    scenario::Scenario,
     settings::Settings,
+    story_time::DEFAULT_TICKS_PER_SECOND,
 };
 use bevy::prelude::*;
 use bevy_turborand::{DelegatedRng, GlobalRng};
 use serde::{Deserialize, Serialize};
 
-use super::ant::{NestAngle, NestFacing, NestOrientation};
+use self::terrain::generate_nest_terrain;
+use super::ant::{
+    nesting::get_nest_site_range,
+    nuptial_flight::{Flight, Winged},
+    NestAngle, NestFacing, NestOrientation,
+};
 
 #[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
 #[reflect(Component)]
@@ -60,10 +74,22 @@ pub fn register_nest(app_type_registry: ResMut<AppTypeRegistry>) {
     app_type_registry.write().register::<AtNest>();
 }
 
-pub fn spawn_nest(settings: Res<Settings>, mut commands: Commands) {
-    let surface_level = (settings.nest_height as f32
-        - (settings.nest_height as f32 * settings.initial_dirt_percent))
-        as isize;
+pub fn spawn_nest(
+    settings: Res<Settings>,
+    scenario: Option<Res<Scenario>>,
+    mut commands: Commands,
+) {
+    let surface_level = match scenario
+        .as_ref()
+        .and_then(|scenario| scenario.nest.as_ref())
+    {
+        Some(nest_map) => nest_map.surface_level(),
+        None => {
+            (settings.nest_height as f32
+                - (settings.nest_height as f32 * settings.initial_dirt_percent))
+                as isize
+        }
+    };
 
     commands.spawn((Nest::new(surface_level), AtNest));
 }
@@ -78,18 +104,32 @@ pub fn spawn_nest(settings: Res<Settings>, mut commands: Commands) {
 pub fn spawn_nest_elements(
     nest_query: Query<&Nest>,
     settings: Res<Settings>,
+    scenario: Option<Res<Scenario>>,
+    mut rng: ResMut<GlobalRng>,
     mut commands: Commands,
 ) {
+    if let Some(nest_map) = scenario
+        .as_ref()
+        .and_then(|scenario| scenario.nest.as_ref())
+    {
+        for &(position, element) in nest_map.elements.iter() {
+            commands.spawn(ElementBundle::new(element, position, AtNest));
+        }
+
+        return;
+    }
+
     let nest = nest_query.single();
+    let terrain = generate_nest_terrain(&settings, nest.surface_level, &mut rng.reborrow());
 
-    for y in 0..settings.nest_height {
-        for x in 0..settings.nest_width {
-            let position = Position::new(x, y);
+    for (y, row) in terrain.into_iter().enumerate() {
+        for (x, tile) in row.into_iter().enumerate() {
+            let position = Position::new(x as isize, y as isize);
+            let mut element_entity_commands =
+                commands.spawn(ElementBundle::new(tile.element, position, AtNest));
 
-            if y <= nest.surface_level {
-                commands.spawn(ElementBundle::new(Element::Air, position, AtNest));
-            } else {
-                commands.spawn(ElementBundle::new(Element::Dirt, position, AtNest));
+            if tile.hardness > 0 {
+                element_entity_commands.insert(Hardness(tile.hardness));
             }
         }
     }
@@ -98,15 +138,52 @@ pub fn spawn_nest_elements(
 pub fn spawn_nest_ants(
     nest_query: Query<&Nest>,
     settings: Res<Settings>,
+    scenario: Option<Res<Scenario>>,
     mut rng: ResMut<GlobalRng>,
     mut commands: Commands,
 ) {
     let nest = nest_query.single();
     let mut rng = rng.reborrow();
 
+    // Authored maps place every ant themselves.
+    if let Some(nest_map) = scenario
+        .as_ref()
+        .and_then(|scenario| scenario.nest.as_ref())
+    {
+        for &(position, ant_role) in nest_map.ants.iter() {
+            let name = match ant_role {
+                AntRole::Queen => AntName(String::from("Queen")),
+                AntRole::Worker => AntName::random(&mut rng),
+            };
+
+            let ant_entity_id = commands
+                .spawn(AntBundle::new(
+                    position,
+                    AntColor(settings.ant_color),
+                    AntInventory::default(),
+                    ant_role,
+                    name,
+                    Initiative::new(&mut rng),
+                    AtNest,
+                    Hunger::new(settings.max_hunger_time),
+                    Digestion::new(settings.max_digestion_time),
+                    Thirst::new(settings.max_thirst_time),
+                    Age::for_role(ant_role, &settings),
+                ))
+                .id();
+
+            commands.entity(ant_entity_id).insert(NestOrientation::new(
+                NestFacing::random(&mut rng),
+                NestAngle::Zero,
+            ));
+        }
+
+        return;
+    }
+
     let queen_ant_bundle = AntBundle::new(
-        // Queen always spawns in the center. She'll fall from the sky in the future.
-        Position::new(settings.nest_width / 2, nest.surface_level),
+        // Queen arrives on her nuptial flight, high above the nest, and lands once she's chosen where to settle.
+        Position::new(rng.isize(get_nest_site_range(&settings)), 0),
         AntColor(settings.ant_color),
         AntInventory::default(),
         AntRole::Queen,
@@ -115,16 +192,19 @@ pub fn spawn_nest_ants(
         AtNest,
         Hunger::new(settings.max_hunger_time),
         Digestion::new(settings.max_digestion_time),
+        Thirst::new(settings.max_thirst_time),
+        Age::for_role(AntRole::Queen, &settings),
     );
 
     let queen_ant_entity_id = commands.spawn(queen_ant_bundle).id();
 
-    commands
-        .entity(queen_ant_entity_id)
-        .insert(NestOrientation::new(
-            NestFacing::random(&mut rng),
-            NestAngle::Zero,
-        ));
+    commands.entity(queen_ant_entity_id).insert((
+        NestOrientation::new(NestFacing::random(&mut rng), NestAngle::Zero),
+        Winged,
+        AntTask::new(Task::Flying(Flight::Circling(
+            settings.max_nuptial_flight_time * DEFAULT_TICKS_PER_SECOND,
+        ))),
+    ));
 
     let worker_ant_entity_ids = (0..settings.initial_ant_worker_count)
         .map(|_| {
@@ -143,6 +223,8 @@ pub fn spawn_nest_ants(
                     AtNest,
                     Hunger::new(settings.max_hunger_time),
                     Digestion::new(settings.max_digestion_time),
+                    Thirst::new(settings.max_thirst_time),
+                    Age::for_role(AntRole::Worker, &settings),
                 ))
                 .id()
         })
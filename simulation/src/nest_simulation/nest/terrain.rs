@@ -0,0 +1,294 @@
+use crate::{
+    common::{element::Element, position::Position},
+    settings::{NestTerrain, Settings},
+};
+use bevy::prelude::*;
+use bevy_turborand::{DelegatedRng, GlobalRng};
+
+/// A tile of generated terrain before it's spawned into the world.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TerrainTile {
+    pub element: Element,
+    pub hardness: isize,
+}
+
+/// Generate the nest's underground: layers of increasingly hard dirt with pockets of air, food, and water, and veins of
+/// rock, buried within.
+/// Everything at, or above, the surface is Air. Returns one row of tiles per row of the nest.
+///
+/// All randomness comes from GlobalRng so a seeded colony always generates the same terrain.
+pub fn generate_nest_terrain(
+    settings: &Settings,
+    surface_level: isize,
+    rng: &mut Mut<GlobalRng>,
+) -> Vec<Vec<TerrainTile>> {
+    let terrain = settings.nest_terrain;
+    let strata_depths = generate_strata_depths(settings, surface_level, rng);
+
+    let mut tiles = (0..settings.nest_height)
+        .map(|y| {
+            (0..settings.nest_width)
+                .map(|x| {
+                    if y <= surface_level {
+                        return TerrainTile {
+                            element: Element::Air,
+                            hardness: 0,
+                        };
+                    }
+
+                    // Strata are numbered from the surface down.
+                    let stratum = strata_depths
+                        .iter()
+                        .filter(|depths| y > depths[x as usize])
+                        .count() as isize;
+
+                    TerrainTile {
+                        element: Element::Dirt,
+                        hardness: get_stratum_hardness(&terrain, stratum),
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    // Cavities are wider than they are tall, like chambers, while food is buried in rounder clumps.
+    for _ in 0..terrain.cavity_count {
+        if let Some((center, radius)) =
+            get_random_pocket(settings, surface_level, terrain.max_cavity_radius, rng)
+        {
+            carve_pocket(&mut tiles, center, radius * 2, radius, Element::Air);
+        }
+    }
+
+    for _ in 0..terrain.food_pocket_count {
+        if let Some((center, radius)) =
+            get_random_pocket(settings, surface_level, terrain.max_food_pocket_radius, rng)
+        {
+            carve_pocket(&mut tiles, center, radius, radius, Element::Food);
+        }
+    }
+
+    // Groundwater is trapped in round pockets, like food, but it will flow out as soon as it's dug into.
+    for _ in 0..terrain.water_pocket_count {
+        if let Some((center, radius)) = get_random_pocket(
+            settings,
+            surface_level,
+            terrain.max_water_pocket_radius,
+            rng,
+        ) {
+            carve_pocket(&mut tiles, center, radius, radius, Element::Water);
+        }
+    }
+
+    // Rock is kept out of the top stratum so it never blocks the queen from starting her nest.
+    if let Some(top_stratum_depths) = strata_depths.first() {
+        for _ in 0..terrain.rock_vein_count {
+            lay_rock_vein(
+                &mut tiles,
+                top_stratum_depths,
+                terrain.max_rock_vein_length,
+                rng,
+            );
+        }
+    }
+
+    tiles
+}
+
+/// The top stratum is always soft, so the queen can start her nest, and the bottom stratum is the hardest.
+fn get_stratum_hardness(terrain: &NestTerrain, stratum: isize) -> isize {
+    if terrain.strata_count <= 1 {
+        return 0;
+    }
+
+    stratum * terrain.max_dirt_hardness / (terrain.strata_count - 1)
+}
+
+/// For each boundary between two strata, the depth of the boundary in every column of the nest.
+/// Boundaries start evenly spaced and then wander by up to `strata_roughness` tiles.
+fn generate_strata_depths(
+    settings: &Settings,
+    surface_level: isize,
+    rng: &mut Mut<GlobalRng>,
+) -> Vec<Vec<isize>> {
+    let terrain = settings.nest_terrain;
+    let underground_height = settings.nest_height - surface_level - 1;
+
+    (1..terrain.strata_count.max(1))
+        .map(|stratum| {
+            let base_depth = surface_level + stratum * underground_height / terrain.strata_count;
+            let mut offset = 0;
+
+            (0..settings.nest_width)
+                .map(|_| {
+                    offset = (offset + rng.isize(-1..2))
+                        .clamp(-terrain.strata_roughness, terrain.strata_roughness);
+
+                    base_depth + offset
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Pick the center and radius of a pocket which is buried entirely underground and within the nest.
+/// Returns None if the nest is too shallow to bury a pocket.
+fn get_random_pocket(
+    settings: &Settings,
+    surface_level: isize,
+    max_radius: isize,
+    rng: &mut Mut<GlobalRng>,
+) -> Option<(Position, isize)> {
+    if max_radius < 1 {
+        return None;
+    }
+
+    let radius = rng.isize(1..max_radius + 1);
+
+    // Leave at least a tile of dirt between a pocket and the surface so the surface isn't breached.
+    let min_y = surface_level + radius + 2;
+    let max_y = settings.nest_height - radius - 1;
+    let min_x = radius;
+    let max_x = settings.nest_width - radius - 1;
+
+    if min_y > max_y || min_x > max_x {
+        return None;
+    }
+
+    Some((
+        Position::new(rng.isize(min_x..max_x + 1), rng.isize(min_y..max_y + 1)),
+        radius,
+    ))
+}
+
+/// Fill an ellipse of dirt with the given element. Anything that isn't dirt, e.g. an overlapping cavity, is left alone.
+fn carve_pocket(
+    tiles: &mut [Vec<TerrainTile>],
+    center: Position,
+    radius_x: isize,
+    radius_y: isize,
+    element: Element,
+) {
+    for y in center.y - radius_y..=center.y + radius_y {
+        for x in center.x - radius_x..=center.x + radius_x {
+            let tile = match tiles
+                .get_mut(y as usize)
+                .and_then(|row| row.get_mut(x as usize))
+            {
+                Some(tile) => tile,
+                None => continue,
+            };
+
+            let dx = (x - center.x) as f32 / radius_x as f32;
+            let dy = (y - center.y) as f32 / radius_y as f32;
+
+            if tile.element == Element::Dirt && dx * dx + dy * dy <= 1.0 {
+                *tile = TerrainTile {
+                    element,
+                    hardness: 0,
+                };
+            }
+        }
+    }
+}
+
+/// Wander from a random point beneath the top stratum, turning the dirt along the way into rock.
+/// Veins drift sideways more than up or down so they resemble seams rather than pillars.
+fn lay_rock_vein(
+    tiles: &mut [Vec<TerrainTile>],
+    top_stratum_depths: &[isize],
+    max_length: isize,
+    rng: &mut Mut<GlobalRng>,
+) {
+    if max_length < 1 || tiles.is_empty() {
+        return;
+    }
+
+    let width = top_stratum_depths.len() as isize;
+    let height = tiles.len() as isize;
+
+    let x = rng.isize(0..width);
+    let min_y = top_stratum_depths[x as usize] + 1;
+    if min_y >= height {
+        return;
+    }
+
+    let mut position = Position::new(x, rng.isize(min_y..height));
+
+    for _ in 0..rng.isize(1..max_length + 1) {
+        let is_below_top_stratum = position.x >= 0
+            && position.x < width
+            && position.y < height
+            && position.y > top_stratum_depths[position.x as usize];
+
+        if !is_below_top_stratum {
+            return;
+        }
+
+        let tile = &mut tiles[position.y as usize][position.x as usize];
+        if tile.element == Element::Dirt {
+            *tile = TerrainTile {
+                element: Element::Rock,
+                hardness: 0,
+            };
+        }
+
+        position = if rng.chance(0.75) {
+            position + Position::new(rng.isize(-1..2), 0)
+        } else {
+            position + Position::new(0, rng.isize(-1..2))
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generate(rng_seed: u64) -> Vec<Vec<TerrainTile>> {
+        let settings = Settings {
+            nest_width: 40,
+            nest_height: 40,
+            ..Settings::default()
+        };
+
+        let mut world = World::new();
+        world.insert_resource(GlobalRng::with_seed(rng_seed));
+
+        generate_nest_terrain(&settings, 9, &mut world.resource_mut::<GlobalRng>())
+    }
+
+    // Confirm that the same seed always generates the same terrain, and that different seeds don't.
+    #[test]
+    fn terrain_is_reproducible_from_seed() {
+        assert_eq!(generate(1), generate(1));
+        assert_ne!(generate(1), generate(2));
+    }
+
+    // Confirm that rock is only buried beneath the surface, and that dirt grows harder the deeper it lies.
+    #[test]
+    fn terrain_is_buried_and_harder_with_depth() {
+        let terrain = generate(1);
+
+        // Nothing is buried above ground and the surface is never breached.
+        assert!(terrain[..=10]
+            .iter()
+            .flatten()
+            .all(|tile| tile.element == Element::Air || tile.element == Element::Dirt));
+        assert!(terrain[10]
+            .iter()
+            .all(|tile| tile.element == Element::Dirt && tile.hardness == 0));
+
+        // Rock is buried beneath the top stratum.
+        assert!(terrain[11..]
+            .iter()
+            .flatten()
+            .any(|tile| tile.element == Element::Rock));
+
+        let max_dirt_hardness = Settings::default().nest_terrain.max_dirt_hardness;
+        assert!(terrain[39]
+            .iter()
+            .filter(|tile| tile.element == Element::Dirt)
+            .all(|tile| tile.hardness == max_dirt_hardness));
+    }
+}
@@ -0,0 +1,218 @@
+use super::nest::{AtNest, Nest};
+use crate::{
+    common::{
+        element::{commands::ElementCommandsExt, Element, Sand, Water},
+        grid::{Grid, GridElements},
+        position::Position,
+    },
+    settings::Settings,
+    weather::Weather,
+};
+use bevy::prelude::*;
+use bevy_turborand::{DelegatedRng, GlobalRng};
+
+/// While it's raining, drops of water appear along the top of the sky. `water_flow` carries them down to the surface
+/// where they puddle, run into the nest's entrances, and soak into the dirt.
+pub fn rain_fall(
+    weather: Res<Weather>,
+    grid_query: Query<&Grid, With<AtNest>>,
+    grid_elements: GridElements<AtNest>,
+    settings: Res<Settings>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    if !weather.is_raining() {
+        return;
+    }
+
+    let grid = grid_query.single();
+
+    for x in 0..grid.width() {
+        if !rng.chance(settings.probabilities.rain_drop.into()) {
+            continue;
+        }
+
+        let position = Position::new(x, 0);
+        if grid_elements.is(position, Element::Air) {
+            let entity = grid_elements.entity(position);
+            commands.replace_element(position, Element::Water, *entity, AtNest);
+        }
+    }
+}
+
+/// Rain washes loose sand, which is exposed to the sky, sideways. Gravity then tumbles washed sand downhill so that,
+/// over the course of a storm, sand hills flatten and sand collects in hollows - closing any nest entrance it falls into.
+pub fn rain_wash_sand(
+    weather: Res<Weather>,
+    sand_query: Query<(Entity, &Position), (With<Sand>, With<AtNest>)>,
+    nest_query: Query<&Nest>,
+    grid_elements: GridElements<AtNest>,
+    settings: Res<Settings>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    if !weather.is_raining() {
+        return;
+    }
+
+    let nest = nest_query.single();
+    // Two grains of sand shouldn't be washed into the same place in a single tick.
+    let mut washed_positions = vec![];
+
+    for (sand_entity, &sand_position) in sand_query.iter() {
+        if !nest.is_aboveground(&sand_position) {
+            continue;
+        }
+
+        let above_position = sand_position - Position::Y;
+        let is_exposed = grid_elements.is(above_position, Element::Air)
+            || grid_elements.is(above_position, Element::Water);
+
+        if !is_exposed || !rng.chance(settings.probabilities.rain_wash_sand.into()) {
+            continue;
+        }
+
+        let side_positions = [sand_position + Position::NEG_X, sand_position + Position::X]
+            .into_iter()
+            .filter(|&position| {
+                grid_elements.is(position, Element::Air) && !washed_positions.contains(&position)
+            })
+            .collect::<Vec<_>>();
+
+        let Some(&side_position) = rng.sample(&side_positions) else {
+            continue;
+        };
+
+        let air_entity = *grid_elements.entity(side_position);
+        commands.replace_element(side_position, Element::Sand, air_entity, AtNest);
+        commands.replace_element(sand_position, Element::Air, sand_entity, AtNest);
+
+        washed_positions.push(side_position);
+    }
+}
+
+/// Once the rain stops, water left on the surface slowly evaporates. Water underground is sheltered from the sun.
+pub fn water_evaporate(
+    weather: Res<Weather>,
+    water_query: Query<(Entity, &Position), (With<Water>, With<AtNest>)>,
+    nest_query: Query<&Nest>,
+    settings: Res<Settings>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    if weather.is_raining() {
+        return;
+    }
+
+    let nest = nest_query.single();
+
+    for (water_entity, &water_position) in water_query.iter() {
+        if !nest.is_aboveground(&water_position) {
+            continue;
+        }
+
+        if rng.chance(settings.probabilities.water_evaporate.into()) {
+            commands.replace_element(water_position, Element::Air, water_entity, AtNest);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        common::{element::Element, position::Position},
+        settings::{Probabilities, Settings},
+        test_support::TestSimulation,
+        weather::Weather,
+    };
+
+    fn settings(probabilities: Probabilities) -> Settings {
+        Settings {
+            probabilities,
+            ..Settings::default()
+        }
+    }
+
+    fn start_rain(simulation: &mut TestSimulation) {
+        simulation
+            .world_mut()
+            .resource_mut::<Weather>()
+            .rain_until(isize::MAX);
+    }
+
+    // Confirm that rain falls from the sky and puddles on the surface.
+    #[test]
+    fn rain_puddles_on_surface() {
+        let mut simulation = TestSimulation::with_settings(
+            "
+            ...
+            ...
+            ...
+            ###
+            ",
+            settings(Probabilities {
+                rain_drop: 1.0,
+                water_soak: 0.0,
+                ..Settings::default().probabilities
+            }),
+        );
+        start_rain(&mut simulation);
+
+        simulation.tick(3);
+
+        assert_eq!(simulation.nest_element(Position::new(1, 2)), Element::Water);
+    }
+
+    // Confirm that rain washes sand off a hill and into a nest entrance, closing it.
+    #[test]
+    fn rain_washes_sand_into_entrance() {
+        let mut simulation = TestSimulation::with_settings(
+            "
+            .....
+            .....
+            ..s..
+            ##.##
+            ##.##
+            ",
+            settings(Probabilities {
+                rain_drop: 0.0,
+                rain_wash_sand: 1.0,
+                ..Settings::default().probabilities
+            }),
+        );
+        start_rain(&mut simulation);
+
+        simulation.tick(30);
+
+        simulation.assert_nest(
+            "
+            .....
+            .....
+            .....
+            ##.##
+            ##s##
+            ",
+        );
+    }
+
+    // Confirm that puddles on the surface dry up once the rain stops.
+    #[test]
+    fn surface_water_evaporates_after_rain() {
+        let mut simulation = TestSimulation::with_settings(
+            "
+            ...
+            .w.
+            ###
+            ",
+            settings(Probabilities {
+                water_evaporate: 1.0,
+                water_soak: 0.0,
+                ..Settings::default().probabilities
+            }),
+        );
+
+        simulation.tick(2);
+
+        assert!(!simulation.nest_map().contains('w'));
+    }
+}
@@ -0,0 +1,182 @@
+use super::nest::AtNest;
+use crate::{
+    common::{
+        ant::{AntInventory, InventoryItemBundle},
+        element::{commands::ElementCommandsExt, Element, Food, Freshness},
+        grid::GridElements,
+        position::Position,
+    },
+    settings::Settings,
+};
+use bevy::prelude::*;
+use bevy_turborand::{DelegatedRng, GlobalRng};
+
+/// Food in the nest goes off over time, wherever it is, and spoils into mold once it's no longer fresh.
+/// Food which doesn't have Freshness, because it was just brought into the nest or was loaded from a save which
+/// predates it, starts going off now.
+pub fn food_spoil(
+    mut food_query: Query<
+        (Entity, Option<&mut Freshness>, Option<&Position>),
+        (With<Food>, With<AtNest>),
+    >,
+    mut ants_query: Query<&mut AntInventory, With<AtNest>>,
+    settings: Res<Settings>,
+    mut commands: Commands,
+) {
+    for (food_entity, freshness, position) in food_query.iter_mut() {
+        let Some(mut freshness) = freshness else {
+            commands
+                .entity(food_entity)
+                .insert(Freshness::new(settings.max_food_freshness_time));
+            continue;
+        };
+
+        freshness.tick();
+
+        if !freshness.is_spoiled() {
+            continue;
+        }
+
+        if let Some(position) = position {
+            commands.replace_element(*position, Element::Mold, food_entity, AtNest);
+        } else if let Some(mut inventory) = ants_query
+            .iter_mut()
+            .find(|inventory| inventory.0 == Some(food_entity))
+        {
+            // Food which spoils while it's being carried is swapped out so its carrier is seen holding mold.
+            let mold_entity = commands
+                .spawn(InventoryItemBundle::new(Element::Mold, AtNest))
+                .id();
+
+            inventory.0 = Some(mold_entity);
+            commands.entity(food_entity).despawn();
+        }
+    }
+}
+
+/// Mold spreads to food lying beside it, so spoiled food left in a granary ruins the rest of the store.
+pub fn mold_spread(
+    food_query: Query<(Entity, &Position), (With<Food>, With<AtNest>)>,
+    grid_elements: GridElements<AtNest>,
+    settings: Res<Settings>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    for (food_entity, position) in food_query.iter() {
+        let is_beside_mold = [Position::X, Position::NEG_X, Position::Y, Position::NEG_Y]
+            .iter()
+            .any(|&offset| grid_elements.is(*position + offset, Element::Mold));
+
+        if is_beside_mold && rng.chance(settings.probabilities.mold_spread.into()) {
+            commands.replace_element(*position, Element::Mold, food_entity, AtNest);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        common::{element::Element, position::Position},
+        nest_simulation::ant::NestFacing,
+        settings::{Probabilities, Settings},
+        test_support::TestSimulation,
+    };
+
+    // Confirm that food spoils into mold, whether it's lying in the nest or being carried.
+    #[test]
+    fn food_spoils_into_mold() {
+        let settings = Settings {
+            max_food_freshness_time: 1,
+            probabilities: Probabilities {
+                random_drop: 0.0,
+                ..Settings::default().probabilities
+            },
+            ..Settings::default()
+        };
+
+        let mut simulation = TestSimulation::with_settings(
+            "
+            .....
+            #####
+            #f.W#
+            #####
+            ",
+            settings,
+        );
+        let ant_entity = simulation.nest_ant(Position::new(3, 2));
+        simulation.give_nest_ant(ant_entity, Element::Food);
+
+        simulation.tick(15);
+
+        assert_eq!(simulation.nest_element(Position::new(1, 2)), Element::Mold);
+        assert_eq!(
+            simulation.inventory_element(ant_entity),
+            Some(Element::Mold)
+        );
+    }
+
+    // Confirm that mold spreads to adjacent food.
+    #[test]
+    fn mold_spreads_to_adjacent_food() {
+        let settings = Settings {
+            probabilities: Probabilities {
+                mold_spread: 1.0,
+                ..Settings::default().probabilities
+            },
+            ..Settings::default()
+        };
+
+        let mut simulation = TestSimulation::with_settings(
+            "
+            ......
+            ######
+            #off.#
+            ######
+            ",
+            settings,
+        );
+
+        simulation.tick(3);
+
+        simulation.assert_nest(
+            "
+            ......
+            ######
+            #ooo.#
+            ######
+            ",
+        );
+    }
+
+    // Confirm that a worker hauls mold out of the nest rather than eating it.
+    #[test]
+    fn worker_picks_up_mold() {
+        let settings = Settings {
+            probabilities: Probabilities {
+                random_drop: 0.0,
+                ..Settings::default().probabilities
+            },
+            ..Settings::default()
+        };
+
+        let mut simulation = TestSimulation::with_settings(
+            "
+            .....
+            #####
+            #oW.#
+            #####
+            ",
+            settings,
+        );
+        let ant_entity = simulation.nest_ant(Position::new(2, 2));
+        simulation.face_nest_ant(ant_entity, NestFacing::Left);
+
+        simulation.tick(100);
+
+        assert_eq!(
+            simulation.inventory_element(ant_entity),
+            Some(Element::Mold)
+        );
+        assert_eq!(simulation.nest_element(Position::new(1, 2)), Element::Air);
+    }
+}
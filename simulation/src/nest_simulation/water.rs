@@ -0,0 +1,209 @@
+use super::nest::AtNest;
+use crate::{
+    common::{
+        element::{commands::ElementCommandsExt, Element, Water},
+        grid::{GridElements, GridElementsMut},
+        position::Position,
+    },
+    settings::Settings,
+};
+use bevy::prelude::*;
+use bevy_turborand::{DelegatedRng, GlobalRng};
+
+// Search for a position for water to flow into. Water falls straight down when it can and otherwise spreads
+// sideways, so it pools in the lowest space available. Unlike sand, it never rests on a ledge with air beside it.
+fn get_water_flow_position(
+    position: Position,
+    grid_elements: &GridElements<AtNest>,
+    rng: &mut Mut<GlobalRng>,
+) -> Option<Position> {
+    let below_position = position + Position::Y;
+    if grid_elements.is(below_position, Element::Air) {
+        return Some(below_position);
+    }
+
+    let left_position = position + Position::NEG_X;
+    let right_position = position + Position::X;
+
+    let can_go_left = grid_elements.is(left_position, Element::Air);
+    let can_go_right = grid_elements.is(right_position, Element::Air);
+
+    match (can_go_left, can_go_right) {
+        (true, true) if rng.bool() => Some(left_position),
+        (true, true) => Some(right_position),
+        (true, false) => Some(left_position),
+        (false, true) => Some(right_position),
+        (false, false) => None,
+    }
+}
+
+/// Flow water a tile at a time. This is water's equivalent of `gravity_elements`.
+/// Water is flowed from the bottom up so that, when a column of water falls, the whole column falls together.
+pub fn water_flow(
+    mut water_query: Query<(Entity, &mut Position), (With<Water>, With<AtNest>)>,
+    mut element_position_query: Query<&mut Position, (With<Element>, Without<Water>, With<AtNest>)>,
+    mut grid_elements_queries: ParamSet<(GridElements<AtNest>, GridElementsMut<AtNest>)>,
+    mut rng: ResMut<GlobalRng>,
+) {
+    let mut waters = water_query
+        .iter()
+        .map(|(entity, &position)| (entity, position))
+        .collect::<Vec<_>>();
+
+    waters.sort_by_key(|(_, position)| (-position.y, position.x));
+
+    for (water_entity, water_position) in waters {
+        let Some(air_position) = get_water_flow_position(
+            water_position,
+            &grid_elements_queries.p0(),
+            &mut rng.reborrow(),
+        ) else {
+            continue;
+        };
+
+        let Some(&air_entity) = grid_elements_queries.p0().get_entity(air_position) else {
+            continue;
+        };
+
+        // Swap water/air positions and update the grid immediately so water flowed later this tick sees the change.
+        let (Ok(mut air_position_component), Ok((_, mut water_position_component))) = (
+            element_position_query.get_mut(air_entity),
+            water_query.get_mut(water_entity),
+        ) else {
+            continue;
+        };
+
+        *air_position_component = water_position;
+        *water_position_component = air_position;
+
+        grid_elements_queries.p1().set(air_position, water_entity);
+        grid_elements_queries.p1().set(water_position, air_entity);
+    }
+}
+
+/// Water slowly soaks into the dirt it touches, turning it into mud. The water is absorbed in the process.
+/// Mud is freshly loosened so any Hardness the dirt had is lost.
+pub fn water_soak_dirt(
+    water_query: Query<(Entity, &Position), (With<Water>, With<AtNest>)>,
+    grid_elements: GridElements<AtNest>,
+    settings: Res<Settings>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    // Two drops of water shouldn't soak into the same dirt in a single tick.
+    let mut soaked_positions = vec![];
+
+    for (water_entity, &water_position) in water_query.iter() {
+        if !rng.chance(settings.probabilities.water_soak.into()) {
+            continue;
+        }
+
+        let dirt_positions = [
+            water_position + Position::Y,
+            water_position + Position::NEG_X,
+            water_position + Position::X,
+        ]
+        .into_iter()
+        .filter(|&position| {
+            grid_elements.is(position, Element::Dirt) && !soaked_positions.contains(&position)
+        })
+        .collect::<Vec<_>>();
+
+        let Some(&dirt_position) = rng.sample(&dirt_positions) else {
+            continue;
+        };
+
+        let dirt_entity = *grid_elements.entity(dirt_position);
+        commands.replace_element(dirt_position, Element::Mud, dirt_entity, AtNest);
+        commands.replace_element(water_position, Element::Air, water_entity, AtNest);
+
+        soaked_positions.push(dirt_position);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        settings::{Probabilities, Settings},
+        test_support::TestSimulation,
+    };
+
+    // Confirm that water falls through air and spreads across the floor until it's level.
+    #[test]
+    fn water_falls_and_spreads() {
+        let mut simulation = TestSimulation::new(
+            "
+            #w.#
+            #w.#
+            #..#
+            ####
+            ",
+        );
+
+        simulation.tick(5);
+
+        simulation.assert_nest(
+            "
+            #..#
+            #..#
+            #ww#
+            ####
+            ",
+        );
+    }
+
+    // Confirm that water runs off a ledge and pools in the chamber below it.
+    #[test]
+    fn water_pools_in_chamber() {
+        let mut simulation = TestSimulation::new(
+            "
+            www.
+            ###.
+            #...
+            ####
+            ",
+        );
+
+        simulation.tick(30);
+
+        simulation.assert_nest(
+            "
+            ....
+            ###.
+            #www
+            ####
+            ",
+        );
+    }
+
+    // Confirm that water soaks into the dirt beneath it and is absorbed.
+    #[test]
+    fn water_soaks_dirt_into_mud() {
+        let settings = Settings {
+            probabilities: Probabilities {
+                water_soak: 1.0,
+                ..Settings::default().probabilities
+            },
+            ..Settings::default()
+        };
+
+        let mut simulation = TestSimulation::with_settings(
+            "
+            RwR
+            R#R
+            RRR
+            ",
+            settings,
+        );
+
+        simulation.tick(1);
+
+        simulation.assert_nest(
+            "
+            R.R
+            RmR
+            RRR
+            ",
+        );
+    }
+}
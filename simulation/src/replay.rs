@@ -0,0 +1,166 @@
+use crate::{
+    crater_simulation::crater::AtCrater,
+    external_event::ExternalSimulationEvent,
+    nest_simulation::nest::AtNest,
+    save::{export_file, get_export_file_name},
+    settings::Settings,
+    story_time::StoryTime,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+pub const REPLAY_FILE_EXTENSION: &str = "replay";
+/// Bump this whenever a change to `Replay`, `Settings`, or `ExternalSimulationEvent` would prevent older replays from
+/// being read. Unlike saves, replays aren't migrated because they're only expected to be useful for a short while.
+const REPLAY_FORMAT_VERSION: u32 = 14;
+
+#[derive(PartialEq, Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum ReplayExternalSimulationEvent {
+    Nest(ExternalSimulationEvent<AtNest>),
+    Crater(ExternalSimulationEvent<AtCrater>),
+}
+
+impl From<ExternalSimulationEvent<AtNest>> for ReplayExternalSimulationEvent {
+    fn from(event: ExternalSimulationEvent<AtNest>) -> Self {
+        ReplayExternalSimulationEvent::Nest(event)
+    }
+}
+
+impl From<ExternalSimulationEvent<AtCrater>> for ReplayExternalSimulationEvent {
+    fn from(event: ExternalSimulationEvent<AtCrater>) -> Self {
+        ReplayExternalSimulationEvent::Crater(event)
+    }
+}
+
+/// An external event along with the tick at which it was processed by the simulation.
+#[derive(PartialEq, Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct ReplayEvent {
+    pub tick: isize,
+    pub event: ReplayExternalSimulationEvent,
+}
+
+/// Everything needed to reproduce a colony exactly: the Settings, including RNG seed, it was created with and every external
+/// event the simulation processed since. A seeded simulation is deterministic so replaying the events at the same ticks
+/// recreates the colony tick for tick.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Replay {
+    version: u32,
+    pub settings: Settings,
+    pub events: Vec<ReplayEvent>,
+    /// How many ticks had elapsed when the replay was written. Replaying this many ticks recreates the colony as it was then.
+    pub elapsed_ticks: isize,
+}
+
+impl Replay {
+    pub fn new(settings: Settings) -> Self {
+        Self {
+            version: REPLAY_FORMAT_VERSION,
+            settings,
+            events: vec![],
+            elapsed_ticks: 0,
+        }
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<Self, String> {
+        let replay = rmp_serde::from_slice::<Replay>(data)
+            .map_err(|error| format!("Failed to read replay: {}", error))?;
+
+        if replay.version != REPLAY_FORMAT_VERSION {
+            return Err(format!(
+                "This replay was recorded with replay format v{} and can't be played by this version (replay format v{}).",
+                replay.version, REPLAY_FORMAT_VERSION
+            ));
+        }
+
+        Ok(replay)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        // Serializing plain data into a Vec can't fail.
+        rmp_serde::to_vec(self).unwrap()
+    }
+}
+
+/// Records every external event processed while telling a story. Only colonies created this session are recorded
+/// because GlobalRng isn't persisted so a loaded colony can't be reproduced from its seed.
+#[derive(Resource)]
+pub struct ReplayRecorder(Replay);
+
+impl ReplayRecorder {
+    pub fn record(&mut self, tick: isize, event: impl Into<ReplayExternalSimulationEvent>) {
+        self.0.events.push(ReplayEvent {
+            tick,
+            event: event.into(),
+        });
+    }
+
+    pub fn to_replay(&self, elapsed_ticks: isize) -> Replay {
+        Replay {
+            elapsed_ticks,
+            ..self.0.clone()
+        }
+    }
+
+    /// Write the recording to a replay file. Returns a message describing where the file was written.
+    pub fn export(&self, name: &str, elapsed_ticks: isize) -> Result<String, String> {
+        export_file(
+            &get_export_file_name(name, REPLAY_FILE_EXTENSION),
+            self.to_replay(elapsed_ticks).to_bytes(),
+        )
+    }
+}
+
+/// Re-injects a replay's external events at the ticks they were originally processed.
+#[derive(Resource)]
+pub struct ReplayPlayer {
+    events: VecDeque<ReplayEvent>,
+}
+
+impl ReplayPlayer {
+    pub fn new(replay: &Replay) -> Self {
+        Self {
+            events: replay.events.iter().copied().collect(),
+        }
+    }
+
+    /// Whether every recorded event has been re-injected.
+    pub fn is_finished(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+/// Start recording when a new colony is created. Settings must already be initialized.
+pub fn start_replay_recording(settings: Res<Settings>, mut commands: Commands) {
+    commands.insert_resource(ReplayRecorder(Replay::new(*settings)));
+}
+
+pub fn remove_replay_resources(mut commands: Commands) {
+    commands.remove_resource::<ReplayRecorder>();
+    commands.remove_resource::<ReplayPlayer>();
+}
+
+/// Send replayed events before external events are processed so they're processed at the same tick they were recorded.
+pub fn play_replay_events(
+    mut replay_player: ResMut<ReplayPlayer>,
+    story_time: Res<StoryTime>,
+    mut nest_events: ResMut<Events<ExternalSimulationEvent<AtNest>>>,
+    mut crater_events: ResMut<Events<ExternalSimulationEvent<AtCrater>>>,
+) {
+    while let Some(replay_event) = replay_player.events.front() {
+        if replay_event.tick > story_time.elapsed_ticks() {
+            break;
+        }
+
+        match replay_event.event {
+            ReplayExternalSimulationEvent::Nest(event) => {
+                nest_events.send(event);
+            }
+            ReplayExternalSimulationEvent::Crater(event) => {
+                crater_events.send(event);
+            }
+        }
+
+        replay_player.events.pop_front();
+    }
+}
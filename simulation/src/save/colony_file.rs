@@ -0,0 +1,57 @@
+use bevy::prelude::*;
+
+use super::{decompress_save_data, migration::migrate_save_data};
+
+/// Colonies are exported as files containing the same brotli-compressed snapshot that's written when saving.
+pub const COLONY_FILE_EXTENSION: &str = "colony";
+
+/// A colony which has been read from a file and is waiting to be imported into a save slot.
+pub struct ColonyFile {
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+impl ColonyFile {
+    pub fn new(file_name: &str, data: Vec<u8>) -> Self {
+        let name = file_name
+            .strip_suffix(&format!(".{}", COLONY_FILE_EXTENSION))
+            .unwrap_or(file_name)
+            .to_string();
+
+        Self { name, data }
+    }
+
+    /// Ensure the file contains a colony which this version of the app is able to load.
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        let decompressed_data = decompress_save_data(&self.data)
+            .map_err(|_| format!("{} is not a colony file.", self.name))?;
+
+        migrate_save_data(&decompressed_data)?;
+
+        Ok(())
+    }
+}
+
+/// Describes the outcome of the most recent export or import so it can be shown to the user.
+#[derive(Resource, Default)]
+pub struct ColonyFileStatus(pub Option<String>);
+
+pub(crate) fn get_colony_file_name(name: &str) -> String {
+    get_export_file_name(name, COLONY_FILE_EXTENSION)
+}
+
+/// Name an exported file after a colony, replacing characters which aren't safe to use in file names.
+pub(crate) fn get_export_file_name(name: &str, extension: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' || c == ' ' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    format!("{}.{}", name.trim(), extension)
+}
@@ -0,0 +1,1015 @@
+use rmpv::Value;
+use std::borrow::Cow;
+
+/// The version of the save format written by this build.
+/// Bump this whenever a change to a persisted type (a reflected component or resource) would prevent older saves
+/// from being applied to the world, and register a migration from the previous version in `SAVE_MIGRATIONS`.
+pub const SAVE_FORMAT_VERSION: u32 = 16;
+
+/// Upgrades a snapshot from `from_version` to `from_version + 1`.
+/// Migrations operate on the raw MessagePack value of the snapshot because older snapshots can't be deserialized
+/// into the current versions of their types - that's the whole reason for migrating them.
+pub struct SaveMigration {
+    pub from_version: u32,
+    pub description: &'static str,
+    pub migrate: fn(&mut Value) -> Result<(), String>,
+}
+
+/// Every migration, in order. Snapshots are upgraded one step at a time until they reach `SAVE_FORMAT_VERSION`.
+const SAVE_MIGRATIONS: &[SaveMigration] = &[
+    SaveMigration {
+        from_version: 0,
+        description: "Introduce the version header. The snapshot itself is unchanged.",
+        migrate: |_| Ok(()),
+    },
+    SaveMigration {
+        from_version: 1,
+        description:
+            "Add rng_seed to Settings. Colonies created before seeds existed are given seed 0.",
+        migrate: |snapshot| {
+            let mut result = Ok(());
+
+            for_each_reflected_value(
+                snapshot,
+                "simulation::settings::Settings",
+                &mut |settings| match settings {
+                    Value::Array(fields) => fields.push(Value::from(0u64)),
+                    _ => result = Err("Settings is not an array".to_string()),
+                },
+            );
+
+            result
+        },
+    },
+    SaveMigration {
+        from_version: 2,
+        description: "Add nest_terrain to Settings. Colonies created before terrain generation had flat, uniform, dirt.",
+        migrate: |snapshot| {
+            let mut result = Ok(());
+
+            for_each_reflected_value(
+                snapshot,
+                "simulation::settings::Settings",
+                &mut |settings| match settings {
+                    // strata_count, max_dirt_hardness, strata_roughness, cavity_count, max_cavity_radius,
+                    // food_pocket_count, max_food_pocket_radius
+                    Value::Array(fields) => fields.push(Value::Array(vec![
+                        Value::from(1),
+                        Value::from(0),
+                        Value::from(0),
+                        Value::from(0),
+                        Value::from(0),
+                        Value::from(0),
+                        Value::from(0),
+                    ])),
+                    _ => result = Err("Settings is not an array".to_string()),
+                },
+            );
+
+            result
+        },
+    },
+    SaveMigration {
+        from_version: 3,
+        description: "Add rock veins to NestTerrain. Colonies created before Rock existed have no rock.",
+        migrate: |snapshot| {
+            let mut result = Ok(());
+
+            for_each_reflected_value(
+                snapshot,
+                "simulation::settings::Settings",
+                &mut |settings| match settings {
+                    // NestTerrain is the last field of Settings.
+                    Value::Array(fields) => match fields.last_mut() {
+                        // rock_vein_count, max_rock_vein_length
+                        Some(Value::Array(terrain_fields)) => {
+                            terrain_fields.push(Value::from(0));
+                            terrain_fields.push(Value::from(0));
+                        }
+                        _ => result = Err("NestTerrain is not an array".to_string()),
+                    },
+                    _ => result = Err("Settings is not an array".to_string()),
+                },
+            );
+
+            result
+        },
+    },
+    SaveMigration {
+        from_version: 4,
+        description: "Add Water. Probabilities gain water_soak and NestTerrain gains water pockets, which older colonies lack.",
+        migrate: |snapshot| {
+            let mut result = Ok(());
+
+            for_each_reflected_value(
+                snapshot,
+                "simulation::settings::Settings",
+                &mut |settings| match settings {
+                    Value::Array(fields) => {
+                        // Probabilities is the 17th field of Settings. water_soak
+                        match fields.get_mut(16) {
+                            Some(Value::Array(probabilities_fields)) => {
+                                probabilities_fields.push(Value::from(0.002f32))
+                            }
+                            _ => result = Err("Probabilities is not an array".to_string()),
+                        }
+
+                        // NestTerrain is the last field of Settings. water_pocket_count, max_water_pocket_radius
+                        match fields.last_mut() {
+                            Some(Value::Array(terrain_fields)) => {
+                                terrain_fields.push(Value::from(0));
+                                terrain_fields.push(Value::from(0));
+                            }
+                            _ => result = Err("NestTerrain is not an array".to_string()),
+                        }
+                    }
+                    _ => result = Err("Settings is not an array".to_string()),
+                },
+            );
+
+            result
+        },
+    },
+    SaveMigration {
+        from_version: 5,
+        description: "Add weather. Probabilities gain rain tunables and Settings gains the length of dry spells and rain.",
+        migrate: |snapshot| {
+            let mut result = Ok(());
+
+            for_each_reflected_value(
+                snapshot,
+                "simulation::settings::Settings",
+                &mut |settings| match settings {
+                    Value::Array(fields) => {
+                        // Probabilities is the 17th field of Settings. rain_drop, rain_wash_sand, water_evaporate
+                        match fields.get_mut(16) {
+                            Some(Value::Array(probabilities_fields)) => {
+                                probabilities_fields.push(Value::from(0.02f32));
+                                probabilities_fields.push(Value::from(0.01f32));
+                                probabilities_fields.push(Value::from(0.001f32));
+                            }
+                            _ => result = Err("Probabilities is not an array".to_string()),
+                        }
+
+                        // max_dry_time, max_rain_time
+                        fields.push(Value::from(86_400));
+                        fields.push(Value::from(3_600));
+                    }
+                    _ => result = Err("Settings is not an array".to_string()),
+                },
+            );
+
+            result
+        },
+    },
+    SaveMigration {
+        from_version: 6,
+        description: "Add ceiling collapse. Probabilities gain collapse tunables and Settings gains the reach of a ceiling's support.",
+        migrate: |snapshot| {
+            let mut result = Ok(());
+
+            for_each_reflected_value(
+                snapshot,
+                "simulation::settings::Settings",
+                &mut |settings| match settings {
+                    Value::Array(fields) => {
+                        // Probabilities is the 17th field of Settings. dirt_collapse, mud_collapse
+                        match fields.get_mut(16) {
+                            Some(Value::Array(probabilities_fields)) => {
+                                probabilities_fields.push(Value::from(0.00002f32));
+                                probabilities_fields.push(Value::from(0.0002f32));
+                            }
+                            _ => result = Err("Probabilities is not an array".to_string()),
+                        }
+
+                        // ceiling_support_distance
+                        fields.push(Value::from(3));
+                    }
+                    _ => result = Err("Settings is not an array".to_string()),
+                },
+            );
+
+            result
+        },
+    },
+    SaveMigration {
+        from_version: 7,
+        description: "Add brood. Settings gains how long eggs, larvae and pupae take to develop.",
+        migrate: |snapshot| {
+            let mut result = Ok(());
+
+            for_each_reflected_value(
+                snapshot,
+                "simulation::settings::Settings",
+                &mut |settings| match settings {
+                    Value::Array(fields) => {
+                        // max_egg_time, max_larva_time, max_pupa_time
+                        fields.push(Value::from(14_400));
+                        fields.push(Value::from(28_800));
+                        fields.push(Value::from(14_400));
+                    }
+                    _ => result = Err("Settings is not an array".to_string()),
+                },
+            );
+
+            result
+        },
+    },
+    SaveMigration {
+        from_version: 8,
+        description: "Add worker castes. Settings gains how long workers serve in a caste and how the colony staffs them. Existing workers are assigned a caste when loaded.",
+        migrate: |snapshot| {
+            let mut result = Ok(());
+
+            for_each_reflected_value(
+                snapshot,
+                "simulation::settings::Settings",
+                &mut |settings| match settings {
+                    Value::Array(fields) => {
+                        // max_caste_time, brood_per_nurse, workers_per_soldier
+                        fields.push(Value::from(86_400));
+                        fields.push(Value::from(4));
+                        fields.push(Value::from(10));
+                    }
+                    _ => result = Err("Settings is not an array".to_string()),
+                },
+            );
+
+            result
+        },
+    },
+    SaveMigration {
+        from_version: 9,
+        description: "Replace the Tunneling, Chambering, Nesting, Asleep, LeavingNest, and LeavingFood markers with a single AntTask.",
+        migrate: migrate_ant_markers_to_ant_task,
+    },
+    SaveMigration {
+        from_version: 10,
+        description: "Add the queen's nuptial flight. Settings gains how long she circles before landing. Existing queens have already landed.",
+        migrate: |snapshot| {
+            let mut result = Ok(());
+
+            for_each_reflected_value(
+                snapshot,
+                "simulation::settings::Settings",
+                &mut |settings| match settings {
+                    // max_nuptial_flight_time
+                    Value::Array(fields) => fields.push(Value::from(60)),
+                    _ => result = Err("Settings is not an array".to_string()),
+                },
+            );
+
+            result
+        },
+    },
+    SaveMigration {
+        from_version: 11,
+        description: "Add aging. Settings gains how long workers and queens live and every ant gains an Age, starting from zero.",
+        migrate: migrate_ant_age,
+    },
+    SaveMigration {
+        from_version: 12,
+        description: "Add corpse decay. Settings gains how long corpses take to decay. Existing corpses start decaying once loaded.",
+        migrate: |snapshot| {
+            let mut result = Ok(());
+
+            for_each_reflected_value(
+                snapshot,
+                "simulation::settings::Settings",
+                &mut |settings| match settings {
+                    // max_corpse_decay_time
+                    Value::Array(fields) => fields.push(Value::from(259_200)),
+                    _ => result = Err("Settings is not an array".to_string()),
+                },
+            );
+
+            result
+        },
+    },
+    SaveMigration {
+        from_version: 13,
+        description: "Add food spoilage. Probabilities gain mold_spread and Settings gains how long food keeps. Existing food starts going off once loaded.",
+        migrate: |snapshot| {
+            let mut result = Ok(());
+
+            for_each_reflected_value(
+                snapshot,
+                "simulation::settings::Settings",
+                &mut |settings| match settings {
+                    Value::Array(fields) => {
+                        // Probabilities is the 17th field of Settings. mold_spread
+                        match fields.get_mut(16) {
+                            Some(Value::Array(probabilities_fields)) => {
+                                probabilities_fields.push(Value::from(0.0001f32))
+                            }
+                            _ => result = Err("Probabilities is not an array".to_string()),
+                        }
+
+                        // max_food_freshness_time
+                        fields.push(Value::from(432_000));
+                    }
+                    _ => result = Err("Settings is not an array".to_string()),
+                },
+            );
+
+            result
+        },
+    },
+    SaveMigration {
+        from_version: 14,
+        description: "Add food kinds. Digestion gains the kind of food being digested. Existing stomachs hold seeds.",
+        migrate: |snapshot| {
+            let mut result = Ok(());
+
+            for_each_reflected_value(
+                snapshot,
+                "simulation::common::ant::digestion::Digestion",
+                &mut |digestion| match digestion {
+                    // food_kind. Unit variants are encoded by name.
+                    Value::Array(fields) => fields.push(Value::from("Seed")),
+                    _ => result = Err("Digestion is not an array".to_string()),
+                },
+            );
+
+            result
+        },
+    },
+    SaveMigration {
+        from_version: 15,
+        description: "Add thirst. Settings gains how long ants can go without water and every ant gains a Thirst, starting quenched.",
+        migrate: migrate_ant_thirst,
+    },
+];
+
+/// Ants used to track what they were doing with a marker component per activity. They now have a single AntTask.
+/// Markers which were active at the same time are collapsed into one task, preferring whichever would have taken
+/// precedence. Nesting is put on hold, rather than lost, if the queen was doing something else at the time.
+fn migrate_ant_markers_to_ant_task(snapshot: &mut Value) -> Result<(), String> {
+    const NEST_ANT: &str = "simulation::nest_simulation::ant";
+    const CRATER_ANT: &str = "simulation::crater_simulation::ant";
+
+    // Listed in order of precedence. Old markers are all either unit structs, single-field tuple structs, or
+    // Nesting, which is carried over into Task::Nesting as-is.
+    let markers = [
+        ("Asleep", format!("{}::sleep::Asleep", NEST_ANT)),
+        ("Tunneling", format!("{}::tunneling::Tunneling", NEST_ANT)),
+        (
+            "Chambering",
+            format!("{}::chambering::Chambering", NEST_ANT),
+        ),
+        (
+            "LeavingFood",
+            format!("{}::emit_pheromone::LeavingFood", CRATER_ANT),
+        ),
+        (
+            "LeavingNest",
+            format!("{}::emit_pheromone::LeavingNest", CRATER_ANT),
+        ),
+        ("Nesting", format!("{}::nesting::Nesting", NEST_ANT)),
+    ];
+
+    let mut result = Ok(());
+
+    for_each_reflected_entity(
+        snapshot,
+        "simulation::common::ant::Ant",
+        &mut |components| {
+            let mut tasks = vec![];
+
+            for (task_name, type_path) in markers.iter() {
+                let Some(index) = components
+                    .iter()
+                    .position(|(key, _)| key.as_str() == Some(type_path))
+                else {
+                    continue;
+                };
+
+                let (_, marker) = components.remove(index);
+
+                // Unit variants are encoded by name and newtype variants as a single entry map of name to value.
+                let task = match (*task_name, marker) {
+                    ("Asleep", _) => Value::from("Asleep"),
+                    ("Nesting", nesting) => Value::Map(vec![(Value::from("Nesting"), nesting)]),
+                    (_, Value::Array(mut fields)) if fields.len() == 1 => {
+                        Value::Map(vec![(Value::from(*task_name), fields.remove(0))])
+                    }
+                    _ => {
+                        result = Err(format!("{} is not a single field array", task_name));
+                        continue;
+                    }
+                };
+
+                tasks.push((*task_name, task));
+            }
+
+            let mut tasks = tasks.into_iter();
+            let current = tasks.next().map_or(Value::from("Idle"), |(_, task)| task);
+            let interrupted = tasks
+                .filter(|(task_name, _)| *task_name == "Nesting")
+                .map(|(_, task)| task)
+                .collect::<Vec<_>>();
+
+            // current, interrupted, history
+            components.push((
+                Value::from("simulation::common::ant::task::AntTask"),
+                Value::Array(vec![
+                    current,
+                    Value::Array(interrupted),
+                    Value::Array(vec![]),
+                ]),
+            ));
+        },
+    );
+
+    result
+}
+
+/// Ants now age and die of old age. Existing ants are treated as though they've just been born, with a lifespan
+/// matching their role. Lifespans are written out, rather than read from Settings, so this migration never changes.
+fn migrate_ant_age(snapshot: &mut Value) -> Result<(), String> {
+    // 30 days and 1 year, respectively.
+    const MAX_WORKER_AGE_TIME: i64 = 2_592_000;
+    const MAX_QUEEN_AGE_TIME: i64 = 31_536_000;
+    const TICKS_PER_SECOND: i64 = 10;
+
+    let mut result = Ok(());
+
+    for_each_reflected_value(
+        snapshot,
+        "simulation::settings::Settings",
+        &mut |settings| match settings {
+            Value::Array(fields) => {
+                // max_worker_age_time, max_queen_age_time
+                fields.push(Value::from(MAX_WORKER_AGE_TIME));
+                fields.push(Value::from(MAX_QUEEN_AGE_TIME));
+            }
+            _ => result = Err("Settings is not an array".to_string()),
+        },
+    );
+
+    for_each_reflected_entity(
+        snapshot,
+        "simulation::common::ant::Ant",
+        &mut |components| {
+            // Unit variants are encoded by name.
+            let is_queen = components.iter().any(|(key, value)| {
+                key.as_str() == Some("simulation::common::ant::AntRole")
+                    && value.as_str() == Some("Queen")
+            });
+
+            let max_age_time = if is_queen {
+                MAX_QUEEN_AGE_TIME
+            } else {
+                MAX_WORKER_AGE_TIME
+            };
+
+            // ticks, max_ticks
+            components.push((
+                Value::from("simulation::common::ant::age::Age"),
+                Value::Array(vec![
+                    Value::from(0),
+                    Value::from(max_age_time * TICKS_PER_SECOND),
+                ]),
+            ));
+        },
+    );
+
+    result
+}
+
+/// Ants now need water as well as food. Existing ants start out quenched. As with aging, how long an ant can go
+/// without water is written out, rather than read from Settings, so this migration never changes.
+fn migrate_ant_thirst(snapshot: &mut Value) -> Result<(), String> {
+    // 2 days
+    const MAX_THIRST_TIME: i64 = 172_800;
+    const TICKS_PER_SECOND: i64 = 10;
+
+    let mut result = Ok(());
+
+    for_each_reflected_value(
+        snapshot,
+        "simulation::settings::Settings",
+        &mut |settings| match settings {
+            // max_thirst_time
+            Value::Array(fields) => fields.push(Value::from(MAX_THIRST_TIME)),
+            _ => result = Err("Settings is not an array".to_string()),
+        },
+    );
+
+    for_each_reflected_entity(
+        snapshot,
+        "simulation::common::ant::Ant",
+        &mut |components| {
+            let max = 100.0f32;
+
+            // value, max, rate
+            components.push((
+                Value::from("simulation::common::ant::thirst::Thirst"),
+                Value::Array(vec![
+                    Value::from(0.0f32),
+                    Value::from(max),
+                    Value::from(max / (MAX_THIRST_TIME * TICKS_PER_SECOND) as f32),
+                ]),
+            ));
+        },
+    );
+
+    result
+}
+
+/// Prefix serialized snapshots with a header so that the version they were written with is known when loading.
+/// The header and snapshot are written as a two element array: [version, snapshot].
+pub(crate) fn write_save_header(buffer: &mut Vec<u8>) {
+    // Writing into a Vec can't fail.
+    rmp::encode::write_array_len(buffer, 2).unwrap();
+    rmp::encode::write_uint(buffer, SAVE_FORMAT_VERSION as u64).unwrap();
+}
+
+/// Read the version header, if any, and return the version along with the remaining, unread, snapshot data.
+/// Saves written before versioning was introduced have no header and are considered version 0.
+fn read_save_header(data: &[u8]) -> (u32, &[u8]) {
+    let mut reader = data;
+
+    // Unversioned snapshots are also arrays, but their first element is never an integer.
+    if let Ok(2) = rmp::decode::read_array_len(&mut reader) {
+        if let Ok(version) = rmp::decode::read_int::<u32, _>(&mut reader) {
+            return (version, reader);
+        }
+    }
+
+    (0, data)
+}
+
+/// Strip the version header from decompressed save data and upgrade the snapshot to the current format.
+/// Returns the serialized snapshot ready for deserialization, or a user-facing reason why the save can't be loaded.
+pub(crate) fn migrate_save_data(data: &[u8]) -> Result<Cow<'_, [u8]>, String> {
+    migrate_save_data_with(data, SAVE_MIGRATIONS)
+}
+
+fn migrate_save_data_with<'a>(
+    data: &'a [u8],
+    migrations: &[SaveMigration],
+) -> Result<Cow<'a, [u8]>, String> {
+    let (version, snapshot_data) = read_save_header(data);
+
+    if version == SAVE_FORMAT_VERSION {
+        return Ok(Cow::Borrowed(snapshot_data));
+    }
+
+    if version > SAVE_FORMAT_VERSION {
+        return Err(format!(
+            "This colony was saved by a newer version of Symbiants (save format v{}) and can't be loaded by this version (save format v{}).",
+            version, SAVE_FORMAT_VERSION
+        ));
+    }
+
+    let mut snapshot = rmpv::decode::read_value(&mut &snapshot_data[..])
+        .map_err(|error| format!("This colony's save data is corrupt: {}", error))?;
+
+    for from_version in version..SAVE_FORMAT_VERSION {
+        let migration = migrations
+            .iter()
+            .find(|migration| migration.from_version == from_version)
+            .ok_or_else(|| {
+                format!(
+                    "Unable to upgrade this colony's save from format v{} - no migration exists.",
+                    from_version
+                )
+            })?;
+
+        (migration.migrate)(&mut snapshot).map_err(|error| {
+            format!(
+                "Unable to upgrade this colony's save from format v{} to v{} ({}): {}",
+                from_version,
+                from_version + 1,
+                migration.description,
+                error
+            )
+        })?;
+    }
+
+    let mut migrated_data = Vec::new();
+    rmpv::encode::write_value(&mut migrated_data, &snapshot)
+        .map_err(|error| format!("Failed to write upgraded save data: {}", error))?;
+
+    Ok(Cow::Owned(migrated_data))
+}
+
+/// Visit every reflected value - component or resource - which was serialized under the given type path,
+/// e.g. "simulation::common::ant::hunger::Hunger". Intended for use by migrations.
+///
+/// NOTE: Snapshots are serialized with MessagePack's compact encoding so struct fields are stored by position, not name.
+/// Renaming a field doesn't require a migration, but adding, removing, or reordering fields does.
+pub fn for_each_reflected_value(
+    value: &mut Value,
+    type_path: &str,
+    f: &mut impl FnMut(&mut Value),
+) {
+    match value {
+        Value::Map(entries) => {
+            for (key, value) in entries.iter_mut() {
+                if key.as_str() == Some(type_path) {
+                    f(value);
+                } else {
+                    for_each_reflected_value(value, type_path, f);
+                }
+            }
+        }
+        Value::Array(values) => {
+            for value in values.iter_mut() {
+                for_each_reflected_value(value, type_path, f);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Visit the serialized components of every entity which has a component of the given type path,
+/// e.g. "simulation::common::ant::Ant". Intended for use by migrations which add or remove components.
+pub fn for_each_reflected_entity(
+    value: &mut Value,
+    type_path: &str,
+    f: &mut impl FnMut(&mut Vec<(Value, Value)>),
+) {
+    match value {
+        Value::Map(entries) => {
+            if entries
+                .iter()
+                .any(|(key, _)| key.as_str() == Some(type_path))
+            {
+                f(entries);
+                return;
+            }
+
+            for (_, value) in entries.iter_mut() {
+                for_each_reflected_entity(value, type_path, f);
+            }
+        }
+        Value::Array(values) => {
+            for value in values.iter_mut() {
+                for_each_reflected_entity(value, type_path, f);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{migrate_save_data, migrate_save_data_with, SAVE_FORMAT_VERSION, SAVE_MIGRATIONS};
+    use crate::{
+        common::{
+            ant::{
+                age::Age,
+                digestion::Digestion,
+                hunger::Hunger,
+                task::{AntTask, Task},
+                thirst::Thirst,
+                AntRole,
+            },
+            position::Position,
+        },
+        nest_simulation::ant::nesting::Nesting,
+        settings::{NestTerrain, Settings},
+    };
+    use rmpv::Value;
+    use serde::{de::DeserializeOwned, Serialize};
+    use std::borrow::Cow;
+
+    const SETTINGS: &str = "simulation::settings::Settings";
+    const ANT_ROLE: &str = "simulation::common::ant::AntRole";
+    const HUNGER: &str = "simulation::common::ant::hunger::Hunger";
+    const DIGESTION: &str = "simulation::common::ant::digestion::Digestion";
+    const ANT_TASK: &str = "simulation::common::ant::task::AntTask";
+    const AGE: &str = "simulation::common::ant::age::Age";
+    const THIRST: &str = "simulation::common::ant::thirst::Thirst";
+
+    fn to_value<T: Serialize>(value: &T) -> Value {
+        let data = rmp_serde::to_vec(value).unwrap();
+        rmpv::decode::read_value(&mut &data[..]).unwrap()
+    }
+
+    fn from_value<T: DeserializeOwned>(value: &Value) -> T {
+        let mut data = Vec::new();
+        rmpv::encode::write_value(&mut data, value).unwrap();
+        rmp_serde::from_slice(&data).unwrap()
+    }
+
+    fn truncate_fields(value: &mut Value, len: usize) {
+        match value {
+            Value::Array(fields) => fields.truncate(len),
+            _ => panic!("{:?} is not an array", value),
+        }
+    }
+
+    /// Settings as they were written by the given save format. Fields have only ever been appended, so older layouts
+    /// are the current layout cut short. Values match those which migrations fill in so that, once migrated, every
+    /// version should be identical to the current one.
+    fn settings_at(version: u32) -> Value {
+        let settings = Settings {
+            rng_seed: 0,
+            nest_terrain: NestTerrain {
+                strata_count: 1,
+                max_dirt_hardness: 0,
+                strata_roughness: 0,
+                cavity_count: 0,
+                max_cavity_radius: 0,
+                food_pocket_count: 0,
+                max_food_pocket_radius: 0,
+                rock_vein_count: 0,
+                max_rock_vein_length: 0,
+                water_pocket_count: 0,
+                max_water_pocket_radius: 0,
+            },
+            ..Settings::default()
+        };
+
+        let mut value = to_value(&settings);
+
+        let Value::Array(fields) = &mut value else {
+            panic!("Settings is not an array");
+        };
+
+        // Probabilities is the 17th field of Settings and NestTerrain is the 19th.
+        let probabilities_len = match version {
+            0..=4 => 17,
+            5 => 18,
+            6 => 21,
+            7..=13 => 23,
+            _ => 24,
+        };
+        truncate_fields(&mut fields[16], probabilities_len);
+
+        let nest_terrain_len = match version {
+            0..=3 => 7,
+            4 => 9,
+            _ => 11,
+        };
+        truncate_fields(&mut fields[18], nest_terrain_len);
+
+        let settings_len = match version {
+            0..=1 => 17,
+            2 => 18,
+            3..=5 => 19,
+            6 => 21,
+            7 => 22,
+            8 => 25,
+            9..=10 => 28,
+            11 => 29,
+            12 => 31,
+            13 => 32,
+            14..=15 => 33,
+            _ => 34,
+        };
+        truncate_fields(&mut value, settings_len);
+
+        value
+    }
+
+    /// The components of an ant as they were written by the given save format. Before v10, ants tracked what they were
+    /// doing with `markers`, a component per activity, rather than with `ant_task`.
+    fn ant_at(version: u32, role: AntRole, markers: Vec<(&str, Value)>, ant_task: Value) -> Value {
+        let settings = Settings::default();
+        let mut digestion = to_value(&Digestion::new(settings.max_digestion_time));
+
+        // Digestion gained food_kind in v15.
+        if version < 15 {
+            truncate_fields(&mut digestion, 3);
+        }
+
+        let mut components = vec![
+            (
+                Value::from("simulation::common::ant::Ant"),
+                Value::Array(vec![]),
+            ),
+            (Value::from(ANT_ROLE), to_value(&role)),
+            (
+                Value::from(HUNGER),
+                to_value(&Hunger::new(settings.max_hunger_time)),
+            ),
+            (Value::from(DIGESTION), digestion),
+        ];
+
+        if version < 10 {
+            components.extend(
+                markers
+                    .into_iter()
+                    .map(|(type_path, marker)| (Value::from(type_path), marker)),
+            );
+        } else {
+            components.push((Value::from(ANT_TASK), ant_task));
+        }
+
+        if version >= 12 {
+            components.push((Value::from(AGE), to_value(&Age::for_role(role, &settings))));
+        }
+
+        if version >= 16 {
+            components.push((
+                Value::from(THIRST),
+                to_value(&Thirst::new(settings.max_thirst_time)),
+            ));
+        }
+
+        Value::Map(components)
+    }
+
+    /// A snapshot as it was written by the given save format, laid out like bevy_save's: a list of entities, each an id
+    /// and its components keyed by type path, followed by resources keyed by type path.
+    fn snapshot_at(version: u32) -> Value {
+        // A worker following a tunnel and a queen who fell asleep part way through founding the nest.
+        let worker = ant_at(
+            version,
+            AntRole::Worker,
+            vec![(
+                "simulation::nest_simulation::ant::tunneling::Tunneling",
+                Value::Array(vec![Value::from(5.0f32)]),
+            )],
+            to_value(&AntTask::new(Task::Tunneling(5.0))),
+        );
+
+        let nesting = to_value(&Nesting::Started(Position::new(3, 4)));
+        let queen = ant_at(
+            version,
+            AntRole::Queen,
+            vec![
+                (
+                    "simulation::nest_simulation::ant::sleep::Asleep",
+                    Value::Array(vec![]),
+                ),
+                (
+                    "simulation::nest_simulation::ant::nesting::Nesting",
+                    nesting.clone(),
+                ),
+            ],
+            // current, interrupted, history
+            Value::Array(vec![
+                to_value(&Task::Asleep),
+                Value::Array(vec![Value::Map(vec![(Value::from("Nesting"), nesting)])]),
+                Value::Array(vec![]),
+            ]),
+        );
+
+        Value::Array(vec![
+            Value::Array(vec![
+                Value::Array(vec![Value::from(0), worker]),
+                Value::Array(vec![Value::from(1), queen]),
+            ]),
+            Value::Map(vec![(Value::from(SETTINGS), settings_at(version))]),
+        ])
+    }
+
+    /// Save data as it was written by the given save format. Saves written before v1 have no version header.
+    fn save_data_at(version: u32) -> Vec<u8> {
+        let mut data = Vec::new();
+
+        if version > 0 {
+            rmp::encode::write_array_len(&mut data, 2).unwrap();
+            rmp::encode::write_uint(&mut data, version as u64).unwrap();
+        }
+
+        rmpv::encode::write_value(&mut data, &snapshot_at(version)).unwrap();
+
+        data
+    }
+
+    fn find_component<'a>(entity: &'a Value, type_path: &str) -> &'a Value {
+        entity
+            .as_map()
+            .unwrap()
+            .iter()
+            .find(|(key, _)| key.as_str() == Some(type_path))
+            .map(|(_, value)| value)
+            .unwrap_or_else(|| panic!("{} is missing", type_path))
+    }
+
+    // Confirm that every step from v0 up to the current format is registered, in order.
+    #[test]
+    fn every_save_format_has_a_migration() {
+        let from_versions = SAVE_MIGRATIONS
+            .iter()
+            .map(|migration| migration.from_version)
+            .collect::<Vec<_>>();
+
+        assert_eq!(from_versions, (0..SAVE_FORMAT_VERSION).collect::<Vec<_>>());
+    }
+
+    // Confirm that a snapshot written by each older save format is upgraded to exactly what the current format writes.
+    #[test]
+    fn migrates_every_save_format_to_current() {
+        let current_snapshot = snapshot_at(SAVE_FORMAT_VERSION);
+
+        for version in 0..SAVE_FORMAT_VERSION {
+            let data = save_data_at(version);
+            let migrated_data = migrate_save_data(&data)
+                .unwrap_or_else(|error| panic!("v{} failed to migrate: {}", version, error));
+            let migrated_snapshot = rmpv::decode::read_value(&mut &migrated_data[..]).unwrap();
+
+            assert_eq!(
+                migrated_snapshot, current_snapshot,
+                "v{} migrated incorrectly",
+                version
+            );
+        }
+    }
+
+    // Confirm that a migrated snapshot deserializes into the current versions of its types.
+    #[test]
+    fn migrated_save_deserializes_into_current_types() {
+        let data = save_data_at(0);
+        let migrated_data = migrate_save_data(&data).unwrap();
+        let snapshot = rmpv::decode::read_value(&mut &migrated_data[..]).unwrap();
+
+        let settings = from_value::<Settings>(find_component(&snapshot[1], SETTINGS));
+        assert_eq!(settings.rng_seed, 0);
+        assert_eq!(settings.nest_terrain.strata_count, 1);
+        assert_eq!(settings.probabilities.water_soak, 0.002);
+        assert_eq!(settings.max_thirst_time, 172_800);
+
+        let worker = &snapshot[0][0][1];
+        assert_eq!(
+            from_value::<AntRole>(find_component(worker, ANT_ROLE)),
+            AntRole::Worker
+        );
+        assert_eq!(
+            from_value::<AntTask>(find_component(worker, ANT_TASK)),
+            AntTask::new(Task::Tunneling(5.0))
+        );
+        from_value::<Hunger>(find_component(worker, HUNGER));
+        from_value::<Digestion>(find_component(worker, DIGESTION));
+        assert_eq!(
+            from_value::<Age>(find_component(worker, AGE)),
+            Age::for_role(AntRole::Worker, &settings)
+        );
+        assert_eq!(
+            from_value::<Thirst>(find_component(worker, THIRST)),
+            Thirst::new(settings.max_thirst_time)
+        );
+
+        let queen = &snapshot[0][1][1];
+        let queen_task = from_value::<AntTask>(find_component(queen, ANT_TASK));
+        assert_eq!(queen_task.current(), &Task::Asleep);
+        assert_eq!(
+            queen_task.interrupted(),
+            &[Task::Nesting(Nesting::Started(Position::new(3, 4)))]
+        );
+        assert_eq!(
+            from_value::<Age>(find_component(queen, AGE)),
+            Age::for_role(AntRole::Queen, &settings)
+        );
+    }
+
+    // Confirm that saves written before versioning, which have no header, are treated as v0.
+    #[test]
+    fn save_without_header_is_migrated_from_v0() {
+        let mut data = Vec::new();
+        rmpv::encode::write_value(&mut data, &snapshot_at(0)).unwrap();
+
+        let migrated_data = migrate_save_data(&data).unwrap();
+        let migrated_snapshot = rmpv::decode::read_value(&mut &migrated_data[..]).unwrap();
+
+        assert_eq!(migrated_snapshot, snapshot_at(SAVE_FORMAT_VERSION));
+    }
+
+    // Confirm that saves in the current format are passed through untouched.
+    #[test]
+    fn current_save_is_not_migrated() {
+        let data = save_data_at(SAVE_FORMAT_VERSION);
+
+        let migrated_data = migrate_save_data(&data).unwrap();
+
+        assert!(matches!(migrated_data, Cow::Borrowed(_)));
+        assert_eq!(
+            rmpv::decode::read_value(&mut &migrated_data[..]).unwrap(),
+            snapshot_at(SAVE_FORMAT_VERSION)
+        );
+    }
+
+    // Confirm that saves written by a newer version of the app are refused rather than misread.
+    #[test]
+    fn newer_save_format_is_rejected() {
+        let mut data = Vec::new();
+        rmp::encode::write_array_len(&mut data, 2).unwrap();
+        rmp::encode::write_uint(&mut data, SAVE_FORMAT_VERSION as u64 + 1).unwrap();
+        rmpv::encode::write_value(&mut data, &snapshot_at(SAVE_FORMAT_VERSION)).unwrap();
+
+        let error = migrate_save_data(&data).unwrap_err();
+
+        assert!(error.contains("newer version"));
+    }
+
+    // Confirm that a save can't be upgraded past a step which has no migration.
+    #[test]
+    fn missing_migration_is_rejected() {
+        let migrations = SAVE_MIGRATIONS
+            .iter()
+            .filter(|migration| migration.from_version != 5)
+            .map(|migration| super::SaveMigration { ..*migration })
+            .collect::<Vec<_>>();
+        let data = save_data_at(3);
+
+        let error = migrate_save_data_with(&data, &migrations).unwrap_err();
+
+        assert!(error.contains("v5 - no migration exists"));
+    }
+}
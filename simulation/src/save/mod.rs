@@ -2,6 +2,9 @@
 mod save_os;
 #[cfg(target_arch = "wasm32")]
 mod save_web;
+mod colony_file;
+mod migration;
+mod slots;
 
 // Re-export the platform-specific implementation
 #[cfg(target_arch = "wasm32")]
@@ -9,3 +12,328 @@ pub use crate::save::save_web::*;
 
 #[cfg(not(target_arch = "wasm32"))]
 pub use crate::save::save_os::*;
+
+pub use crate::save::colony_file::{ColonyFile, ColonyFileStatus, COLONY_FILE_EXTENSION};
+pub(crate) use crate::save::colony_file::get_export_file_name;
+pub use crate::save::migration::{for_each_reflected_value, SaveMigration, SAVE_FORMAT_VERSION};
+pub use crate::save::slots::{SaveSlotMetadata, SaveSlots};
+
+use bevy::{ecs::query::QueryFilter, prelude::*};
+use bevy_save::{
+    Backend, DefaultDebugFormat, Error, Format, Pipeline, Snapshot, SnapshotBuilder,
+    SnapshotSerializer, WorldSaveableExt,
+};
+use brotli::enc::BrotliEncoderInitParams;
+use serde::{de::DeserializeSeed, Serialize};
+use std::{
+    io::{Read, Write},
+    sync::Mutex,
+};
+
+use self::{
+    colony_file::get_colony_file_name,
+    migration::{migrate_save_data, write_save_header},
+    slots::update_active_save_slot,
+};
+use crate::{
+    common::{
+        ant::Ant, element::Element, pheromone::Pheromone, LoadProgress, SimulationLoadProgress,
+    },
+    crater_simulation::crater::Crater,
+    nest_simulation::{brood::Brood, granary::Granary, nest::Nest},
+    settings::Settings,
+    story_time::{StoryRealWorldTime, StoryTime},
+    weather::Weather,
+};
+
+// Everything below is shared by both platforms. Only reading and writing storage, and exchanging files with the user,
+// differ between them so those are left to the platform-specific implementation.
+
+const LOAD_ERROR: &str = "Failed to load world state";
+const DECOMPRESS_ERROR: &str = "Failed to decompress data";
+
+/// A serialized snapshot of the world along with the key of the save slot it belongs to.
+struct SaveSnapshot {
+    key: String,
+    data: Vec<u8>,
+}
+
+static SAVE_SNAPSHOT: Mutex<Option<SaveSnapshot>> = Mutex::new(None);
+
+#[derive(QueryFilter)]
+struct PersistentModelQueryFilter {
+    _or: Or<(
+        With<Ant>,
+        With<Brood>,
+        With<Granary>,
+        With<Element>,
+        With<Crater>,
+        With<Nest>,
+        With<Pheromone>,
+    )>,
+}
+
+#[derive(Resource, Default)]
+pub struct LastSnapshotTime(f32);
+
+#[derive(Resource, Default)]
+pub struct LastSaveTime(f32);
+
+/// Provide an opportunity to write world state to storage.
+/// This system does not run every time because saving is costly, but it does run periodically, rather than simply JIT,
+/// to avoid losing too much state in the event of a crash.
+/// NOTE: intentionally don't run immediately on first run because it's expensive and nothing has changed.
+/// Let the full interval pass before creating anything rather than initializing on first run then waiting.
+pub fn save(world: &mut World) {
+    let current_time = world.resource::<Time<Real>>().elapsed_seconds();
+    let last_snapshot_time = world.resource::<LastSnapshotTime>();
+    let snapshot_interval = world.resource::<Settings>().snapshot_interval;
+    if current_time - last_snapshot_time.0 < snapshot_interval as f32 {
+        return;
+    }
+
+    if let Some(snapshot) = create_save_snapshot(world) {
+        *SAVE_SNAPSHOT.lock().unwrap() = Some(snapshot);
+        world.resource_mut::<LastSnapshotTime>().0 = current_time;
+    } else {
+        error!("Failed to create snapshot");
+    }
+
+    let save_interval = world.resource::<Settings>().save_interval;
+    let last_save_time = world.resource::<LastSaveTime>();
+    if current_time - last_save_time.0 < save_interval as f32 {
+        return;
+    }
+
+    if write_save_snapshot() {
+        world.resource_mut::<LastSaveTime>().0 = current_time;
+        update_active_save_slot(world);
+    }
+}
+
+/// Stop playing the active save slot. If the user is exiting to the main menu then their colony is written to storage
+/// so it can be resumed later. Otherwise, the story has been reset or is over, and the colony is discarded.
+pub fn close_save_slot(world: &mut World) {
+    if world.resource::<SaveSlots>().is_exiting_active_slot() {
+        write_fresh_save_snapshot(world);
+    }
+
+    *SAVE_SNAPSHOT.lock().unwrap() = None;
+    world.resource_mut::<SaveSlots>().close_active_slot();
+}
+
+/// Take a new snapshot, rather than relying on the last periodic snapshot, and write it to storage immediately.
+fn write_fresh_save_snapshot(world: &mut World) {
+    if let Some(snapshot) = create_save_snapshot(world) {
+        *SAVE_SNAPSHOT.lock().unwrap() = Some(snapshot);
+    }
+
+    if write_save_snapshot() {
+        update_active_save_slot(world);
+    }
+}
+
+fn create_save_snapshot(world: &mut World) -> Option<SaveSnapshot> {
+    let key = world.resource::<SaveSlots>().active_slot()?.save_key();
+
+    let mut buffer: Vec<u8> = Vec::new();
+    write_save_header(&mut buffer);
+    let mut serde = rmp_serde::Serializer::new(&mut buffer);
+
+    // NOTE: Technically this could also include InventoryItem, but Element matches it (just by chance for now though?)
+    let mut model_query = world.query_filtered::<Entity, PersistentModelQueryFilter>();
+
+    model_query.update_archetypes(world);
+    let readonly_model_query = model_query.as_readonly();
+    let snapshot = build_snapshot(world, readonly_model_query);
+
+    let registry: &AppTypeRegistry = world.resource::<AppTypeRegistry>();
+    let result = SnapshotSerializer::new(&snapshot, registry).serialize(&mut serde);
+
+    if result.is_ok() {
+        return Some(SaveSnapshot { key, data: buffer });
+    } else {
+        error!("Failed to serialize snapshot: {:?}", result);
+    }
+
+    None
+}
+
+fn write_save_snapshot() -> bool {
+    let save_snapshot = SAVE_SNAPSHOT.lock().unwrap();
+
+    let snapshot = match save_snapshot.as_ref() {
+        Some(snapshot) => snapshot,
+        // SAVE_SNAPSHOT can be empty during the first few seconds of app load because snapshots are taken periodically.
+        None => return false,
+    };
+
+    write_save_data(&snapshot.key, compress_save_data(&snapshot.data))
+}
+
+fn compress_save_data(data: &[u8]) -> Vec<u8> {
+    // Compress snapshot using Brotli. In testing, this reduces a 4mb save file to 0.5mb with compression quality: 1.
+    let mut params = BrotliEncoderInitParams();
+    params.quality = 1; // Max compression (0-11 range)
+
+    let mut compressed_data = brotli::CompressorWriter::with_params(Vec::new(), 4096, &params);
+    compressed_data
+        .write_all(data)
+        .expect("Failed to write to compressor");
+
+    compressed_data.into_inner()
+}
+
+fn decompress_save_data(compressed_data: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut decompressor = brotli::Decompressor::new(compressed_data, 4096);
+    let mut decompressed_data = Vec::new();
+
+    decompressor.read_to_end(&mut decompressed_data)?;
+
+    Ok(decompressed_data)
+}
+
+/// Export the colony currently being played. A fresh snapshot is taken so the file reflects the world as it is now.
+pub fn export_active_colony(world: &mut World) {
+    let name = world
+        .resource::<SaveSlots>()
+        .active_slot()
+        .map(|active_slot| active_slot.name.clone());
+
+    let result = match (name, create_save_snapshot(world)) {
+        (Some(name), Some(snapshot)) => export_file(
+            &get_colony_file_name(&name),
+            compress_save_data(&snapshot.data),
+        ),
+        _ => Err("Failed to export colony: unable to create snapshot".to_string()),
+    };
+
+    let message = result.unwrap_or_else(|error| error);
+    world.resource_mut::<ColonyFileStatus>().0 = Some(message);
+}
+
+pub fn initialize_save_resources(mut commands: Commands) {
+    commands.init_resource::<SaveSlots>();
+    commands.init_resource::<ColonyFileStatus>();
+    commands.init_resource::<CompressedSaveBackend>();
+    commands.init_resource::<LastSnapshotTime>();
+    commands.init_resource::<LastSaveTime>();
+}
+
+pub fn remove_save_resources(mut commands: Commands) {
+    commands.remove_resource::<SaveSlots>();
+    commands.remove_resource::<ColonyFileStatus>();
+    commands.remove_resource::<CompressedSaveBackend>();
+    commands.remove_resource::<LastSnapshotTime>();
+    commands.remove_resource::<LastSaveTime>();
+}
+
+pub fn load_save_file(world: &mut World) {
+    let key = match world.resource::<SaveSlots>().active_slot() {
+        Some(active_slot) => active_slot.save_key(),
+        // Nothing to resume, the user needs to pick a colony from the main menu.
+        None => {
+            world.resource_mut::<SimulationLoadProgress>().save_file = LoadProgress::Failure(None);
+            return;
+        }
+    };
+
+    let mut model_query = world.query_filtered::<Entity, PersistentModelQueryFilter>();
+    model_query.update_archetypes(world);
+
+    let readonly_model_query = model_query.as_readonly();
+
+    world.resource_mut::<SimulationLoadProgress>().save_file = LoadProgress::Loading;
+
+    world.resource_mut::<SimulationLoadProgress>().save_file =
+        match world.load(SaveLoadPipeline::new(key, readonly_model_query)) {
+            Ok(_) => LoadProgress::Success,
+            Err(error) => LoadProgress::Failure(Some(error.to_string())),
+        };
+}
+
+struct SaveLoadPipeline<'q> {
+    key: String,
+    readonly_model_query: &'q QueryState<Entity, PersistentModelQueryFilter>,
+}
+
+impl<'q> SaveLoadPipeline<'q> {
+    pub fn new(
+        key: String,
+        readonly_model_query: &'q QueryState<Entity, PersistentModelQueryFilter>,
+    ) -> Self {
+        Self {
+            key,
+            readonly_model_query,
+        }
+    }
+}
+
+impl<'q> Pipeline for SaveLoadPipeline<'q> {
+    type Backend = CompressedSaveBackend;
+    type Format = DefaultDebugFormat;
+
+    type Key<'a> = &'a str;
+
+    fn key(&self) -> Self::Key<'_> {
+        &self.key
+    }
+
+    fn capture_seed(&self, builder: SnapshotBuilder) -> Snapshot {
+        build_snapshot(builder.world(), self.readonly_model_query)
+    }
+
+    fn apply_seed(&self, world: &mut World, snapshot: &Snapshot) -> Result<(), bevy_save::Error> {
+        snapshot.applier(world).apply()
+    }
+}
+
+/// Reads compressed snapshots from whichever storage the platform saves to.
+#[derive(Default, Resource)]
+pub struct CompressedSaveBackend;
+
+impl<'a> Backend<&'a str> for CompressedSaveBackend {
+    fn save<F: Format, T: Serialize>(&self, _key: &str, _value: &T) -> Result<(), Error> {
+        Err(Error::custom(
+            "Not implemented - expected to save by writing snapshot manually for now",
+        ))
+    }
+
+    fn load<F: Format, S: for<'de> DeserializeSeed<'de, Value = T>, T>(
+        &self,
+        key: &str,
+        seed: S,
+    ) -> Result<T, Error> {
+        // Attempt to retrieve the compressed state from storage
+        let compressed_saved_state = read_save_data(key).ok_or(Error::custom(LOAD_ERROR))?;
+
+        // Attempt to decompress the data
+        let decompressed_data = decompress_save_data(&compressed_saved_state).map_err(|e| {
+            error!("{}: {:?}", DECOMPRESS_ERROR, e);
+            Error::custom(DECOMPRESS_ERROR)
+        })?;
+
+        // Upgrade saves written by older versions of the app before attempting to deserialize them
+        let snapshot_data = migrate_save_data(&decompressed_data).map_err(|reason| {
+            error!("{}", reason);
+            Error::custom(reason)
+        })?;
+
+        // Deserialize the data
+        let mut deserializer = rmp_serde::Deserializer::new(&snapshot_data[..]);
+        seed.deserialize(&mut deserializer).map_err(Error::loading)
+    }
+}
+
+fn build_snapshot(
+    world: &World,
+    readonly_model_query: &QueryState<Entity, PersistentModelQueryFilter>,
+) -> Snapshot {
+    Snapshot::builder(world)
+        .extract_entities(readonly_model_query.iter_manual(world))
+        .extract_resource::<Settings>()
+        .extract_resource::<StoryTime>()
+        .extract_resource::<StoryRealWorldTime>()
+        .extract_resource::<Weather>()
+        .build()
+}
@@ -1,19 +1,157 @@
-use crate::common::{LoadProgress, SimulationLoadProgress};
-use bevy::prelude::*;
+use bevy::{app::AppExit, prelude::*};
+use platform_dirs::{AppDirs, UserDirs};
+use std::{
+    fs,
+    io::ErrorKind,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
 
-pub fn save() {}
+use super::{colony_file::ColonyFile, write_fresh_save_snapshot};
 
-pub fn bind_save_onbeforeunload() {}
+const APP_NAME: &str = "symbiants";
+const SAVE_FILE_EXTENSION: &str = "sav";
+const LOAD_ERROR: &str = "Failed to load world state from file";
 
-pub fn unbind_save_onbeforeunload() {}
+/// Native builds don't have a file picker, so the user types the path of the colony file they'd like to import.
+pub const IS_COLONY_FILE_PATH_REQUIRED: bool = true;
 
-pub fn delete_save_file() {}
+static IMPORTED_COLONY_FILE: Mutex<Option<Result<ColonyFile, String>>> = Mutex::new(None);
 
-pub fn load_save_file(world: &mut World) {
-    // TODO: Support saving on non-WASM targets.
-    world.resource_mut::<SimulationLoadProgress>().save_file = LoadProgress::Failure;
+/// Native equivalent of binding to `beforeunload` on the web. When set, the latest world state is written on AppExit.
+static SAVE_ON_EXIT: AtomicBool = AtomicBool::new(false);
+
+/// Write a fresh snapshot to disk when the app is closing.
+/// Unlike the browser, a native process is able to take a new snapshot during shutdown, so there's no need
+/// to rely on the last periodic snapshot which could be up to `snapshot_interval` seconds stale.
+pub fn save_on_exit(world: &mut World) {
+    if !SAVE_ON_EXIT.load(Ordering::Relaxed) || world.resource::<Events<AppExit>>().is_empty() {
+        return;
+    }
+
+    write_fresh_save_snapshot(world);
 }
 
-pub fn initialize_save_resources() {}
+/// Files are exported to the user's downloads directory, falling back to the app's data directory.
+fn get_export_directory() -> Option<PathBuf> {
+    UserDirs::new()
+        .map(|user_dirs| user_dirs.download_dir)
+        .or_else(|| AppDirs::new(Some(APP_NAME), false).map(|app_dirs| app_dirs.data_dir))
+}
+
+/// A sensible starting point for the import path so the user only needs to type the file name.
+pub fn get_default_colony_file_path() -> String {
+    get_export_directory()
+        .map(|directory| format!("{}{}", directory.display(), std::path::MAIN_SEPARATOR))
+        .unwrap_or_default()
+}
+
+/// Write a file where the user can easily find it. Returns a message describing where the file was written.
+pub fn export_file(file_name: &str, data: Vec<u8>) -> Result<String, String> {
+    let directory = get_export_directory().ok_or(format!(
+        "Failed to export {}: unable to locate downloads directory",
+        file_name
+    ))?;
+    let path = directory.join(file_name);
+
+    fs::create_dir_all(&directory)
+        .and_then(|_| fs::write(&path, data))
+        .map_err(|error| format!("Failed to export {}: {}", file_name, error))?;
+
+    Ok(format!("Exported {}", path.display()))
+}
 
-pub fn remove_save_resources() {}
+/// Read a colony file so that it can be imported. The result is retrieved with `take_imported_colony_file`.
+pub fn import_colony_file(path: &str) {
+    let path = PathBuf::from(path.trim());
+
+    let result = fs::read(&path)
+        .map(|data| {
+            let file_name = path
+                .file_name()
+                .map(|file_name| file_name.to_string_lossy().to_string())
+                .unwrap_or_default();
+
+            ColonyFile::new(&file_name, data)
+        })
+        .map_err(|error| format!("Failed to read {}: {}", path.display(), error));
+
+    *IMPORTED_COLONY_FILE.lock().unwrap() = Some(result);
+}
+
+pub fn take_imported_colony_file() -> Option<Result<ColonyFile, String>> {
+    IMPORTED_COLONY_FILE.lock().unwrap().take()
+}
+
+fn get_save_data_path(key: &str) -> Option<PathBuf> {
+    AppDirs::new(Some(APP_NAME), false).map(|app_dirs| {
+        app_dirs
+            .data_dir
+            .join(key)
+            .with_extension(SAVE_FILE_EXTENSION)
+    })
+}
+
+pub(crate) fn read_save_data(key: &str) -> Option<Vec<u8>> {
+    let save_data_path = get_save_data_path(key)?;
+
+    match fs::read(save_data_path) {
+        Ok(data) => Some(data),
+        Err(error) => {
+            // A missing file is expected on first launch - it just means there's nothing to resume.
+            if error.kind() != ErrorKind::NotFound {
+                error!("{}: {:?}", LOAD_ERROR, error);
+            }
+
+            None
+        }
+    }
+}
+
+pub(crate) fn write_save_data(key: &str, data: Vec<u8>) -> bool {
+    let save_data_path = match get_save_data_path(key) {
+        Some(save_data_path) => save_data_path,
+        None => {
+            error!("Failed to save world state: unable to locate user data directory");
+            return false;
+        }
+    };
+
+    // Write to a temporary file and then rename it over the real save file so a crash mid-write can't corrupt the save.
+    let temp_file_path = save_data_path.with_extension("tmp");
+    let save_result = save_data_path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|_| fs::write(&temp_file_path, data))
+        .and_then(|_| fs::rename(&temp_file_path, &save_data_path));
+
+    if save_result.is_err() {
+        error!("Failed to save world state to file: {:?}", save_result);
+    }
+
+    save_result.is_ok()
+}
+
+pub(crate) fn delete_save_data(key: &str) {
+    let save_data_path = match get_save_data_path(key) {
+        Some(save_data_path) => save_data_path,
+        None => return,
+    };
+
+    if let Err(error) = fs::remove_file(save_data_path) {
+        if error.kind() != ErrorKind::NotFound {
+            error!("Failed to delete save file: {:?}", error);
+        }
+    }
+}
+
+pub fn bind_save_onbeforeunload() {
+    SAVE_ON_EXIT.store(true, Ordering::Relaxed);
+}
+
+pub fn unbind_save_onbeforeunload() {
+    SAVE_ON_EXIT.store(false, Ordering::Relaxed);
+}
@@ -1,123 +1,143 @@
-use bevy::{ecs::query::QueryFilter, prelude::*};
-use bevy_save::{
-    Backend, DefaultDebugFormat, Error, Format, Pipeline, Snapshot, SnapshotBuilder,
-    SnapshotSerializer, WorldSaveableExt,
-};
-use brotli::enc::BrotliEncoderInitParams;
-use gloo_storage::{LocalStorage, Storage};
-use serde::de::DeserializeSeed;
-use serde::Serialize;
-use std::{cell::RefCell, io::Read, io::Write, sync::Mutex};
+use bevy::prelude::*;
+use gloo_storage::{errors::StorageError, LocalStorage, Storage};
+use std::{cell::RefCell, sync::Mutex};
 use wasm_bindgen::{prelude::Closure, JsCast};
-use web_sys::BeforeUnloadEvent;
-
-use crate::{
-    common::{
-        ant::Ant, element::Element, pheromone::Pheromone, LoadProgress, SimulationLoadProgress,
-    },
-    crater_simulation::crater::Crater,
-    nest_simulation::nest::Nest,
-    settings::Settings,
-    story_time::{StoryRealWorldTime, StoryTime},
+use web_sys::{BeforeUnloadEvent, Blob, FileReader, HtmlAnchorElement, HtmlInputElement, Url};
+
+use super::{
+    colony_file::{ColonyFile, COLONY_FILE_EXTENSION},
+    write_save_snapshot,
 };
 
-const LOCAL_STORAGE_KEY: &str = "world-save-state";
 const LOAD_ERROR: &str = "Failed to load world state from local storage";
-const DECOMPRESS_ERROR: &str = "Failed to decompress data";
-
-static SAVE_SNAPSHOT: Mutex<Option<Vec<u8>>> = Mutex::new(None);
-
-#[derive(QueryFilter)]
-struct PersistentModelQueryFilter {
-    _or: Or<(
-        With<Ant>,
-        With<Element>,
-        With<Crater>,
-        With<Nest>,
-        With<Pheromone>,
-    )>,
-}
-
-#[derive(Resource, Default)]
-pub struct LastSnapshotTime(f32);
-
-#[derive(Resource, Default)]
-pub struct LastSaveTime(f32);
-
-/// Provide an opportunity to write world state to disk.
-/// This system does not run every time because saving is costly, but it does run periodically, rather than simply JIT,
-/// to avoid losing too much state in the event of a crash.
-/// NOTE: intentionally don't run immediately on first run because it's expensive and nothing has changed.
-/// Let the full interval pass before creating anything rather than initializing on first run then waiting.
-pub fn save(world: &mut World) {
-    let current_time = world.resource::<Time<Real>>().elapsed_seconds();
-    let last_snapshot_time = world.resource::<LastSnapshotTime>();
-    let snapshot_interval = world.resource::<Settings>().snapshot_interval;
-    if current_time - last_snapshot_time.0 < snapshot_interval as f32 {
-        return;
-    }
 
-    if let Some(snapshot) = create_save_snapshot(world) {
-        *SAVE_SNAPSHOT.lock().unwrap() = Some(snapshot);
-        world.resource_mut::<LastSnapshotTime>().0 = current_time;
-    } else {
-        error!("Failed to create snapshot");
-    }
+/// Browsers provide a file picker so there's no need for the user to type the path of the colony file to import.
+pub const IS_COLONY_FILE_PATH_REQUIRED: bool = false;
+
+/// Browsers read files asynchronously so the picked colony file is stored here until the UI is ready for it.
+static IMPORTED_COLONY_FILE: Mutex<Option<Result<ColonyFile, String>>> = Mutex::new(None);
+
+pub fn get_default_colony_file_path() -> String {
+    String::new()
+}
+
+/// Offer the colony file to the user as a download.
+/// Write a file where the user can easily find it. Returns a message describing where the file was written.
+pub fn export_file(file_name: &str, data: Vec<u8>) -> Result<String, String> {
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or(format!(
+            "Failed to export {}: document not available",
+            file_name
+        ))?;
+
+    let blob_parts = js_sys::Array::of1(&js_sys::Uint8Array::from(&data[..]));
+    let blob = Blob::new_with_u8_array_sequence(&blob_parts)
+        .map_err(|error| format!("Failed to export {}: {:?}", file_name, error))?;
+    let url = Url::create_object_url_with_blob(&blob)
+        .map_err(|error| format!("Failed to export {}: {:?}", file_name, error))?;
+
+    let anchor = document
+        .create_element("a")
+        .ok()
+        .and_then(|element| element.dyn_into::<HtmlAnchorElement>().ok())
+        .ok_or(format!(
+            "Failed to export {}: unable to create download link",
+            file_name
+        ))?;
+    anchor.set_href(&url);
+    anchor.set_download(file_name);
+    anchor.click();
+
+    let _ = Url::revoke_object_url(&url);
+
+    Ok(format!("Downloaded {}", file_name))
+}
+
+/// Open the browser's file picker. The picked file is read asynchronously and retrieved with `take_imported_colony_file`.
+/// The path is ignored because browsers don't expose the file system.
+pub fn import_colony_file(_path: &str) {
+    let input = match web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.create_element("input").ok())
+        .and_then(|element| element.dyn_into::<HtmlInputElement>().ok())
+    {
+        Some(input) => input,
+        None => {
+            error!("Failed to open file picker");
+            return;
+        }
+    };
 
-    let save_interval = world.resource::<Settings>().save_interval;
-    let last_save_time = world.resource::<LastSaveTime>();
-    if current_time - last_save_time.0 < save_interval as f32 {
-        return;
-    }
+    input.set_type("file");
+    input.set_accept(&format!(".{}", COLONY_FILE_EXTENSION));
+
+    let on_change = Closure::once_into_js(move |event: web_sys::Event| {
+        let file = event
+            .target()
+            .and_then(|target| target.dyn_into::<HtmlInputElement>().ok())
+            .and_then(|input| input.files())
+            .and_then(|files| files.get(0));
+
+        let file = match file {
+            Some(file) => file,
+            None => return,
+        };
+
+        let file_reader = match FileReader::new() {
+            Ok(file_reader) => file_reader,
+            Err(error) => {
+                *IMPORTED_COLONY_FILE.lock().unwrap() =
+                    Some(Err(format!("Failed to read {}: {:?}", file.name(), error)));
+                return;
+            }
+        };
+
+        let file_name = file.name();
+        let on_load = Closure::once_into_js(move |event: web_sys::Event| {
+            let result = event
+                .target()
+                .and_then(|target| target.dyn_into::<FileReader>().ok())
+                .and_then(|file_reader| file_reader.result().ok())
+                .map(|array_buffer| js_sys::Uint8Array::new(&array_buffer).to_vec())
+                .map(|data| ColonyFile::new(&file_name, data))
+                .ok_or(format!("Failed to read {}", file_name));
+
+            *IMPORTED_COLONY_FILE.lock().unwrap() = Some(result);
+        });
+
+        file_reader.set_onload(Some(on_load.unchecked_ref()));
+
+        if let Err(error) = file_reader.read_as_array_buffer(&file) {
+            *IMPORTED_COLONY_FILE.lock().unwrap() =
+                Some(Err(format!("Failed to read {}: {:?}", file.name(), error)));
+        }
+    });
 
-    if write_save_snapshot() {
-        world.resource_mut::<LastSaveTime>().0 = current_time;
-    }
+    input.set_onchange(Some(on_change.unchecked_ref()));
+    input.click();
 }
 
-fn create_save_snapshot(world: &mut World) -> Option<Vec<u8>> {
-    let mut buffer: Vec<u8> = Vec::new();
-    let mut serde = rmp_serde::Serializer::new(&mut buffer);
-
-    // Persistent entities must have an Id marker because Id is fit for uniquely identifying across sessions.
-    // NOTE: Technically this could also include InventoryItem, but Element matches it (just by chance for now though?)
-    let mut model_query = world.query_filtered::<Entity, PersistentModelQueryFilter>();
-
-    model_query.update_archetypes(world);
-    let readonly_model_query = model_query.as_readonly();
-    let snapshot = build_snapshot(world, readonly_model_query);
+pub fn take_imported_colony_file() -> Option<Result<ColonyFile, String>> {
+    IMPORTED_COLONY_FILE.lock().unwrap().take()
+}
 
-    let registry: &AppTypeRegistry = world.resource::<AppTypeRegistry>();
-    let result = SnapshotSerializer::new(&snapshot, registry).serialize(&mut serde);
+pub(crate) fn read_save_data(key: &str) -> Option<Vec<u8>> {
+    match LocalStorage::get::<Vec<u8>>(key) {
+        Ok(data) => Some(data),
+        Err(error) => {
+            // A missing key is expected on first launch - it just means there's nothing to resume.
+            if !matches!(error, StorageError::KeyNotFound(_)) {
+                error!("{}: {:?}", LOAD_ERROR, error);
+            }
 
-    if result.is_ok() {
-        return Some(buffer);
-    } else {
-        error!("Failed to serialize snapshot: {:?}", result);
+            None
+        }
     }
-
-    None
 }
 
-fn write_save_snapshot() -> bool {
-    let save_snapshot = SAVE_SNAPSHOT.lock().unwrap();
-
-    let buffer = match save_snapshot.as_ref() {
-        Some(buffer) => buffer,
-        // SAVE_SNAPSHOT can be empty during the first few seconds of app load because snapshots are taken periodically.
-        None => return false,
-    };
-
-    // Compress snapshot using Brotli. In testing, this reduces a 4mb save file to 0.5mb with compression quality: 1.
-    let mut params = BrotliEncoderInitParams();
-    params.quality = 1; // Max compression (0-11 range)
-
-    let mut compressed_data = brotli::CompressorWriter::with_params(Vec::new(), 4096, &params);
-    compressed_data
-        .write_all(buffer)
-        .expect("Failed to write to compressor");
-
-    let save_result = LocalStorage::set(LOCAL_STORAGE_KEY, compressed_data.into_inner());
+pub(crate) fn write_save_data(key: &str, data: Vec<u8>) -> bool {
+    let save_result = LocalStorage::set(key, data);
 
     if save_result.is_err() {
         error!(
@@ -129,6 +149,10 @@ fn write_save_snapshot() -> bool {
     save_result.is_ok()
 }
 
+pub(crate) fn delete_save_data(key: &str) {
+    LocalStorage::delete(key);
+}
+
 thread_local! {
     static ON_BEFORE_UNLOAD: RefCell<Option<Closure<dyn FnMut(BeforeUnloadEvent) -> bool>>> = RefCell::new(None);
 }
@@ -166,120 +190,5 @@ pub fn unbind_save_onbeforeunload() {
     });
 }
 
-pub fn delete_save_file() {
-    LocalStorage::delete(LOCAL_STORAGE_KEY);
-}
-
-pub fn initialize_save_resources(mut commands: Commands) {
-    commands.init_resource::<CompressedWebStorageBackend>();
-    commands.init_resource::<LastSnapshotTime>();
-    commands.init_resource::<LastSaveTime>();
-}
-
-pub fn remove_save_resources(mut commands: Commands) {
-    commands.remove_resource::<CompressedWebStorageBackend>();
-    commands.remove_resource::<LastSnapshotTime>();
-    commands.remove_resource::<LastSaveTime>();
-}
-
-pub fn load_save_file(world: &mut World) {
-    let mut model_query = world.query_filtered::<Entity, PersistentModelQueryFilter>();
-    model_query.update_archetypes(world);
-
-    let readonly_model_query = model_query.as_readonly();
-
-    world.resource_mut::<SimulationLoadProgress>().save_file = LoadProgress::Loading;
-
-    if world
-        .load(SaveLoadPipeline::new(readonly_model_query))
-        .is_ok()
-    {
-        world.resource_mut::<SimulationLoadProgress>().save_file = LoadProgress::Success;
-    } else {
-        world.resource_mut::<SimulationLoadProgress>().save_file = LoadProgress::Failure;
-    }
-}
-
-struct SaveLoadPipeline<'q> {
-    key: String,
-    readonly_model_query: &'q QueryState<Entity, PersistentModelQueryFilter>,
-}
-
-impl<'q> SaveLoadPipeline<'q> {
-    pub fn new(readonly_model_query: &'q QueryState<Entity, PersistentModelQueryFilter>) -> Self {
-        Self {
-            key: LOCAL_STORAGE_KEY.to_string(),
-            readonly_model_query,
-        }
-    }
-}
-
-impl<'q> Pipeline for SaveLoadPipeline<'q> {
-    type Backend = CompressedWebStorageBackend;
-    type Format = DefaultDebugFormat;
-
-    type Key<'a> = &'a str;
-
-    fn key(&self) -> Self::Key<'_> {
-        &self.key
-    }
-
-    fn capture_seed(&self, builder: SnapshotBuilder) -> Snapshot {
-        build_snapshot(builder.world(), self.readonly_model_query)
-    }
-
-    fn apply_seed(&self, world: &mut World, snapshot: &Snapshot) -> Result<(), bevy_save::Error> {
-        snapshot.applier(world).apply()
-    }
-}
-
-#[derive(Default, Resource)]
-pub struct CompressedWebStorageBackend;
-
-impl<'a> Backend<&'a str> for CompressedWebStorageBackend {
-    fn save<F: Format, T: Serialize>(&self, _key: &str, _value: &T) -> Result<(), Error> {
-        Err(Error::custom(
-            "Not implemented - expected to save by writing snapshot manually for now",
-        ))
-    }
-
-    fn load<F: Format, S: for<'de> DeserializeSeed<'de, Value = T>, T>(
-        &self,
-        key: &str,
-        seed: S,
-    ) -> Result<T, Error> {
-        // Attempt to retrieve the compressed state from local storage
-        let compressed_saved_state = LocalStorage::get::<Vec<u8>>(key).map_err(|e| {
-            error!("{}: {:?}", LOAD_ERROR, e);
-            Error::custom(LOAD_ERROR)
-        })?;
-
-        // Initialize the decompressor
-        let mut decompressor = brotli::Decompressor::new(&compressed_saved_state[..], 4096);
-        let mut decompressed_data = Vec::new();
-
-        // Attempt to decompress the data
-        decompressor
-            .read_to_end(&mut decompressed_data)
-            .map_err(|e| {
-                error!("{}: {:?}", DECOMPRESS_ERROR, e);
-                Error::custom(DECOMPRESS_ERROR)
-            })?;
-
-        // Deserialize the data
-        let mut deserializer = rmp_serde::Deserializer::new(&decompressed_data[..]);
-        seed.deserialize(&mut deserializer).map_err(Error::loading)
-    }
-}
-
-fn build_snapshot(
-    world: &World,
-    readonly_model_query: &QueryState<Entity, PersistentModelQueryFilter>,
-) -> Snapshot {
-    Snapshot::builder(world)
-        .extract_entities(readonly_model_query.iter_manual(world))
-        .extract_resource::<Settings>()
-        .extract_resource::<StoryTime>()
-        .extract_resource::<StoryRealWorldTime>()
-        .build()
-}
+/// Browsers don't allow work to run after the tab closes, so `beforeunload` writes the last snapshot instead.
+pub fn save_on_exit() {}
@@ -0,0 +1,279 @@
+use bevy::prelude::*;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    colony_file::{get_colony_file_name, ColonyFile},
+    delete_save_data, export_file, read_save_data, write_save_data,
+};
+use crate::{
+    common::ant::{Ant, Dead},
+    story_time::StoryTime,
+};
+
+/// The index of all slots is persisted separately from the slots' snapshots so that it can be read without
+/// needing to decompress and deserialize every colony.
+const SAVE_SLOTS_KEY: &str = "save-slots";
+const SAVE_KEY_PREFIX: &str = "world-save-state";
+/// Saves written before slots existed were persisted under a single, hard-coded key.
+/// These are adopted into a slot, rather than discarded, the first time the slot index is created.
+const LEGACY_SAVE_KEY: &str = SAVE_KEY_PREFIX;
+const LEGACY_SLOT_NAME: &str = "My Colony";
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SaveSlotMetadata {
+    pub id: String,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub story_day: isize,
+    pub alive_ant_count: usize,
+}
+
+impl SaveSlotMetadata {
+    pub fn save_key(&self) -> String {
+        format!("{}-{}", SAVE_KEY_PREFIX, self.id)
+    }
+}
+
+/// Tracks each named colony the user has created as well as which colony is currently being played.
+/// Every mutation is persisted immediately so that the slot index stays in sync with the snapshots it describes.
+#[derive(Resource, Serialize, Deserialize, Debug)]
+pub struct SaveSlots {
+    slots: Vec<SaveSlotMetadata>,
+    active_slot_id: Option<String>,
+    // Not persisted - only meaningful while transitioning out of the active story.
+    #[serde(skip)]
+    is_exiting_active_slot: bool,
+    #[serde(skip)]
+    next_active_slot_id: Option<String>,
+}
+
+impl FromWorld for SaveSlots {
+    fn from_world(_world: &mut World) -> Self {
+        SaveSlots::load()
+    }
+}
+
+impl SaveSlots {
+    fn load() -> Self {
+        if let Some(data) = read_save_data(SAVE_SLOTS_KEY) {
+            match rmp_serde::from_slice::<SaveSlots>(&data) {
+                Ok(save_slots) => return save_slots,
+                Err(error) => error!("Failed to read save slots: {:?}", error),
+            }
+        }
+
+        let mut save_slots = SaveSlots {
+            slots: vec![],
+            active_slot_id: None,
+            is_exiting_active_slot: false,
+            next_active_slot_id: None,
+        };
+
+        if let Some(legacy_data) = read_save_data(LEGACY_SAVE_KEY) {
+            let slot = save_slots.create(LEGACY_SLOT_NAME);
+            let save_key = slot.save_key();
+
+            if write_save_data(&save_key, legacy_data) {
+                delete_save_data(LEGACY_SAVE_KEY);
+            }
+        }
+
+        save_slots
+    }
+
+    fn persist(&self) {
+        match rmp_serde::to_vec(self) {
+            Ok(data) => {
+                write_save_data(SAVE_SLOTS_KEY, data);
+            }
+            Err(error) => error!("Failed to serialize save slots: {:?}", error),
+        }
+    }
+
+    fn generate_id(&self) -> String {
+        let mut timestamp = Utc::now().timestamp_millis();
+
+        while self.get(&timestamp.to_string()).is_some() {
+            timestamp += 1;
+        }
+
+        timestamp.to_string()
+    }
+
+    pub fn slots(&self) -> &[SaveSlotMetadata] {
+        &self.slots
+    }
+
+    pub fn get(&self, id: &str) -> Option<&SaveSlotMetadata> {
+        self.slots.iter().find(|slot| slot.id == id)
+    }
+
+    pub fn active_slot(&self) -> Option<&SaveSlotMetadata> {
+        self.active_slot_id.as_ref().and_then(|id| self.get(id))
+    }
+
+    pub fn is_exiting_active_slot(&self) -> bool {
+        self.is_exiting_active_slot
+    }
+
+    fn insert(&mut self, name: &str) -> String {
+        let id = self.generate_id();
+
+        self.slots.push(SaveSlotMetadata {
+            id: id.clone(),
+            name: name.to_string(),
+            created_at: Utc::now(),
+            story_day: 0,
+            alive_ant_count: 0,
+        });
+
+        id
+    }
+
+    /// Create a new, empty, slot and make it the active slot. The slot's snapshot is written by the regular save process.
+    pub fn create(&mut self, name: &str) -> &SaveSlotMetadata {
+        let id = self.insert(name);
+
+        self.active_slot_id = Some(id);
+        self.persist();
+
+        self.slots.last().unwrap()
+    }
+
+    /// Create a new slot from a colony file. Returns the new slot's id, or a reason the file can't be imported.
+    /// The imported slot isn't activated. Its metadata is filled in the first time it's played and saved.
+    pub fn import(&mut self, colony_file: &ColonyFile) -> Result<String, String> {
+        colony_file.validate()?;
+
+        let id = self.insert(&colony_file.name);
+        let save_key = self.get(&id).unwrap().save_key();
+
+        if !write_save_data(&save_key, colony_file.data.clone()) {
+            self.slots.retain(|slot| slot.id != id);
+            return Err(format!("Failed to import {}.", colony_file.name));
+        }
+
+        self.persist();
+
+        Ok(id)
+    }
+
+    /// Write a slot's save data to a colony file. Returns a message describing where the file was written.
+    pub fn export(&self, id: &str) -> Result<String, String> {
+        let slot = self.get(id).ok_or("Unable to find colony to export.")?;
+        let data = read_save_data(&slot.save_key())
+            .ok_or(format!("{} hasn't been saved yet.", slot.name))?;
+
+        export_file(&get_colony_file_name(&slot.name), data)
+    }
+
+    /// Mark a slot as the one to be loaded when the app next enters `AppState::Loading`.
+    pub fn activate(&mut self, id: &str) {
+        if self.get(id).is_none() {
+            return;
+        }
+
+        self.active_slot_id = Some(id.to_string());
+        self.persist();
+    }
+
+    pub fn rename(&mut self, id: &str, name: &str) {
+        if let Some(slot) = self.slots.iter_mut().find(|slot| slot.id == id) {
+            slot.name = name.to_string();
+            self.persist();
+        }
+    }
+
+    pub fn duplicate(&mut self, id: &str) {
+        let mut slot = match self.get(id) {
+            Some(slot) => slot.clone(),
+            None => return,
+        };
+
+        let data = match read_save_data(&slot.save_key()) {
+            Some(data) => data,
+            None => {
+                error!("Failed to duplicate save slot {}: no save data", id);
+                return;
+            }
+        };
+
+        slot.id = self.generate_id();
+        slot.name = format!("{} (Copy)", slot.name);
+        slot.created_at = Utc::now();
+
+        if write_save_data(&slot.save_key(), data) {
+            self.slots.push(slot);
+            self.persist();
+        }
+    }
+
+    pub fn delete(&mut self, id: &str) {
+        let index = match self.slots.iter().position(|slot| slot.id == id) {
+            Some(index) => index,
+            None => return,
+        };
+
+        let slot = self.slots.remove(index);
+        delete_save_data(&slot.save_key());
+
+        if self.active_slot_id.as_deref() == Some(id) {
+            self.active_slot_id = None;
+        }
+
+        self.persist();
+    }
+
+    /// Keep the active slot's save data when the story is next cleaned up rather than deleting it.
+    /// This allows the user to return to the main menu without losing their colony.
+    pub fn exit_active_slot(&mut self) {
+        self.is_exiting_active_slot = true;
+    }
+
+    /// Exit the active slot, keeping its save data, and load the given slot once the story has been cleaned up.
+    pub fn switch_to_slot(&mut self, id: &str) {
+        self.exit_active_slot();
+        self.next_active_slot_id = Some(id.to_string());
+    }
+
+    /// Stop playing the active slot. The slot's save data is deleted unless the user chose to exit rather than reset.
+    /// If the user chose to switch slots then the next slot becomes active.
+    pub(crate) fn close_active_slot(&mut self) {
+        if let Some(id) = self.active_slot_id.take() {
+            if !self.is_exiting_active_slot {
+                self.delete(&id);
+            }
+        }
+
+        self.is_exiting_active_slot = false;
+        self.active_slot_id = self.next_active_slot_id.take();
+        self.persist();
+    }
+}
+
+/// Record the active colony's progress onto its slot so it can be shown without loading the colony.
+pub(crate) fn update_active_save_slot(world: &mut World) {
+    let story_day = world.resource::<StoryTime>().as_time_info().days();
+    let alive_ant_count = world
+        .query_filtered::<(), (With<Ant>, Without<Dead>)>()
+        .iter(world)
+        .count();
+
+    let mut save_slots = world.resource_mut::<SaveSlots>();
+    let active_slot_id = match save_slots.active_slot_id.clone() {
+        Some(active_slot_id) => active_slot_id,
+        None => return,
+    };
+
+    if let Some(slot) = save_slots
+        .slots
+        .iter_mut()
+        .find(|slot| slot.id == active_slot_id)
+    {
+        slot.story_day = story_day;
+        slot.alive_ant_count = alive_ant_count;
+    }
+
+    save_slots.persist();
+}
@@ -0,0 +1,423 @@
+//! Authored starting layouts for a new colony. A scenario replaces the generated nest and/or crater with one read from
+//! a map file so that tutorial layouts and stress-test terrain can be built by hand.
+//!
+//! Maps are either ASCII text or PNG images. Each cell, or pixel, describes one position in the grid:
+//!     '#' - Dirt   - rgb(128, 64, 0)
+//!     '.' - Air    - rgb(255, 255, 255), or any fully transparent pixel
+//!     's' - Sand   - rgb(255, 255, 0)
+//!     'f' - Food   - rgb(0, 255, 0)
+//!     'R' - Rock   - rgb(128, 128, 128)
+//!     'w' - Water  - rgb(0, 0, 255)
+//!     'm' - Mud    - rgb(64, 32, 0)
+//!     'o' - Mold   - rgb(96, 128, 64)
+//!     'Q' - Queen standing in Air - rgb(255, 0, 255)
+//!     'W' - Worker standing in Air - rgb(255, 0, 0)
+//!
+//! An ASCII file may describe both zones by starting each zone's rows with a `[nest]` or `[crater]` line. Rows which
+//! aren't preceded by a section header belong to the nest. A PNG describes a single zone.
+//!
+//! The nest's surface is the row above the highest dirt. If there's no dirt then the whole nest is aboveground.
+//!
+//! A nest map must have exactly one queen. She lays every egg and the story ends when she dies. The crater can't have a
+//! queen because she starts in the nest.
+
+use crate::{
+    common::{ant::AntRole, element::Element, position::Position},
+    settings::Settings,
+};
+use bevy::prelude::*;
+
+/// The grid of a single zone, along with where its ants start.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScenarioMap {
+    pub width: isize,
+    pub height: isize,
+    pub elements: Vec<(Position, Element)>,
+    pub ants: Vec<(Position, AntRole)>,
+}
+
+impl ScenarioMap {
+    pub fn from_ascii(text: &str) -> Result<Self, String> {
+        let rows = text
+            .lines()
+            .map(str::trim)
+            .filter(|row| !row.is_empty())
+            .collect::<Vec<_>>();
+
+        let width = rows.first().map_or(0, |row| row.chars().count());
+        let mut cells = vec![];
+
+        for (y, row) in rows.iter().enumerate() {
+            if row.chars().count() != width {
+                return Err(format!(
+                    "Row {} of the map is {} wide but the first row is {} wide.",
+                    y + 1,
+                    row.chars().count(),
+                    width
+                ));
+            }
+
+            for (x, symbol) in row.chars().enumerate() {
+                let cell = match symbol {
+                    '#' => (Element::Dirt, None),
+                    '.' => (Element::Air, None),
+                    's' => (Element::Sand, None),
+                    'f' => (Element::Food, None),
+                    'R' => (Element::Rock, None),
+                    'w' => (Element::Water, None),
+                    'm' => (Element::Mud, None),
+                    'o' => (Element::Mold, None),
+                    'Q' => (Element::Air, Some(AntRole::Queen)),
+                    'W' => (Element::Air, Some(AntRole::Worker)),
+                    _ => {
+                        return Err(format!(
+                            "Unknown map symbol '{}' at row {}, column {}.",
+                            symbol,
+                            y + 1,
+                            x + 1
+                        ))
+                    }
+                };
+
+                cells.push((Position::new(x as isize, y as isize), cell));
+            }
+        }
+
+        Self::new(width, rows.len(), cells)
+    }
+
+    pub fn from_png(data: &[u8]) -> Result<Self, String> {
+        let mut decoder = png::Decoder::new(data);
+        // Palettes and 16-bit channels are expanded so every image can be read as 8-bit color.
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+
+        let mut reader = decoder
+            .read_info()
+            .map_err(|error| format!("Failed to read map image: {}", error))?;
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        let frame = reader
+            .next_frame(&mut buffer)
+            .map_err(|error| format!("Failed to read map image: {}", error))?;
+
+        let pixel_size = frame.color_type.samples();
+        let width = frame.width as usize;
+        let mut cells = vec![];
+
+        for (index, pixel) in buffer[..frame.buffer_size()]
+            .chunks_exact(pixel_size)
+            .enumerate()
+        {
+            let (red, green, blue, alpha) = match *pixel {
+                [gray] => (gray, gray, gray, 255),
+                [gray, alpha] => (gray, gray, gray, alpha),
+                [red, green, blue] => (red, green, blue, 255),
+                [red, green, blue, alpha] => (red, green, blue, alpha),
+                _ => unreachable!("PNG pixels have between one and four samples"),
+            };
+
+            let position = Position::new((index % width) as isize, (index / width) as isize);
+
+            let cell = match (red, green, blue, alpha) {
+                (_, _, _, 0) => (Element::Air, None),
+                (128, 64, 0, _) => (Element::Dirt, None),
+                (255, 255, 255, _) => (Element::Air, None),
+                (255, 255, 0, _) => (Element::Sand, None),
+                (0, 255, 0, _) => (Element::Food, None),
+                (128, 128, 128, _) => (Element::Rock, None),
+                (0, 0, 255, _) => (Element::Water, None),
+                (64, 32, 0, _) => (Element::Mud, None),
+                (96, 128, 64, _) => (Element::Mold, None),
+                (255, 0, 255, _) => (Element::Air, Some(AntRole::Queen)),
+                (255, 0, 0, _) => (Element::Air, Some(AntRole::Worker)),
+                _ => {
+                    return Err(format!(
+                        "Unknown map color rgb({}, {}, {}) at x: {}, y: {}.",
+                        red, green, blue, position.x, position.y
+                    ))
+                }
+            };
+
+            cells.push((position, cell));
+        }
+
+        Self::new(width, frame.height as usize, cells)
+    }
+
+    /// Read a map from a file's contents. The file's extension decides whether it's read as a PNG or as ASCII text.
+    pub fn from_file(file_name: &str, data: &[u8]) -> Result<Self, String> {
+        if is_png_file_name(file_name) {
+            Self::from_png(data)
+        } else {
+            Self::from_ascii(&read_text(data)?)
+        }
+    }
+
+    fn new(
+        width: usize,
+        height: usize,
+        cells: Vec<(Position, (Element, Option<AntRole>))>,
+    ) -> Result<Self, String> {
+        if width == 0 || height == 0 {
+            return Err(String::from("The map is empty."));
+        }
+
+        let ants = cells
+            .iter()
+            .filter_map(|&(position, (_, ant_role))| ant_role.map(|ant_role| (position, ant_role)))
+            .collect();
+
+        let elements = cells
+            .into_iter()
+            .map(|(position, (element, _))| (position, element))
+            .collect();
+
+        Ok(Self {
+            width: width as isize,
+            height: height as isize,
+            elements,
+            ants,
+        })
+    }
+
+    /// The surface is the row above the highest dirt. If there's no dirt then the whole map is aboveground.
+    pub fn surface_level(&self) -> isize {
+        self.elements
+            .iter()
+            .filter(|(_, element)| *element == Element::Dirt)
+            .map(|(position, _)| position.y - 1)
+            .min()
+            .unwrap_or(self.height - 1)
+    }
+
+    fn queen_count(&self) -> usize {
+        self.ants
+            .iter()
+            .filter(|(_, ant_role)| *ant_role == AntRole::Queen)
+            .count()
+    }
+}
+
+/// When present as a new colony is created, replaces the generated nest and/or crater with authored maps.
+/// Zones without a map are generated as usual.
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub struct Scenario {
+    pub nest: Option<ScenarioMap>,
+    pub crater: Option<ScenarioMap>,
+}
+
+impl Scenario {
+    /// Read an ASCII scenario, which may contain `[nest]` and `[crater]` sections.
+    pub fn from_ascii(text: &str) -> Result<Self, String> {
+        let mut nest_rows = vec![];
+        let mut crater_rows = vec![];
+        let mut section_rows = &mut nest_rows;
+
+        for line in text.lines() {
+            match line.trim() {
+                "[nest]" => section_rows = &mut nest_rows,
+                "[crater]" => section_rows = &mut crater_rows,
+                row => section_rows.push(row),
+            }
+        }
+
+        let read_section = |rows: Vec<&str>, name: &str| {
+            if rows.iter().all(|row| row.is_empty()) {
+                return Ok(None);
+            }
+
+            ScenarioMap::from_ascii(&rows.join("\n"))
+                .map(Some)
+                .map_err(|error| format!("Invalid {} map: {}", name, error))
+        };
+
+        let scenario = Self {
+            nest: read_section(nest_rows, "nest")?,
+            crater: read_section(crater_rows, "crater")?,
+        };
+
+        if scenario.nest.is_none() && scenario.crater.is_none() {
+            return Err(String::from("The map is empty."));
+        }
+
+        scenario.validate()?;
+
+        Ok(scenario)
+    }
+
+    /// Read a scenario from a file's contents. A PNG describes only the nest while ASCII text may describe both zones.
+    pub fn from_file(file_name: &str, data: &[u8]) -> Result<Self, String> {
+        if is_png_file_name(file_name) {
+            let scenario = Self {
+                nest: Some(ScenarioMap::from_png(data)?),
+                crater: None,
+            };
+
+            scenario.validate()?;
+
+            Ok(scenario)
+        } else {
+            Self::from_ascii(&read_text(data)?)
+        }
+    }
+
+    /// A colony needs exactly one queen. Without her no eggs are laid and the story never ends, and with several
+    /// every one of them would decide when it's over.
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(nest_map) = &self.nest {
+            match nest_map.queen_count() {
+                1 => {}
+                0 => {
+                    return Err(String::from(
+                        "The nest map has no queen. It needs exactly one.",
+                    ))
+                }
+                queen_count => {
+                    return Err(format!(
+                        "The nest map has {} queens. It needs exactly one.",
+                        queen_count
+                    ))
+                }
+            }
+        }
+
+        if self
+            .crater
+            .as_ref()
+            .is_some_and(|crater_map| crater_map.queen_count() > 0)
+        {
+            return Err(String::from(
+                "The crater map can't have a queen. She starts in the nest.",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Read a scenario from the file system.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn read(path: &str) -> Result<Self, String> {
+        let data =
+            std::fs::read(path).map_err(|error| format!("Failed to read {}: {}", path, error))?;
+
+        Self::from_file(path, &data)
+    }
+
+    /// Browsers don't expose the file system so maps can only be read from a path natively.
+    #[cfg(target_arch = "wasm32")]
+    pub fn read(_path: &str) -> Result<Self, String> {
+        Err(String::from("Maps can only be read from a path natively."))
+    }
+}
+
+fn is_png_file_name(file_name: &str) -> bool {
+    file_name.to_lowercase().ends_with(".png")
+}
+
+fn read_text(data: &[u8]) -> Result<String, String> {
+    String::from_utf8(data.to_vec()).map_err(|_| String::from("The map isn't an image or text."))
+}
+
+/// The grid is sized to fit the scenario's maps. This must run before any zone is spawned.
+pub fn apply_scenario_settings(scenario: Res<Scenario>, mut settings: ResMut<Settings>) {
+    if let Some(nest_map) = &scenario.nest {
+        settings.nest_width = nest_map.width;
+        settings.nest_height = nest_map.height;
+    }
+
+    if let Some(crater_map) = &scenario.crater {
+        settings.crater_width = crater_map.width;
+        settings.crater_height = crater_map.height;
+    }
+}
+
+pub fn remove_scenario_resources(mut commands: Commands) {
+    commands.remove_resource::<Scenario>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ascii_scenario_reads_both_zones() {
+        let scenario = Scenario::from_ascii(
+            "
+            [nest]
+            .Q.
+            #s#
+            [crater]
+            fW
+            ",
+        )
+        .unwrap();
+
+        let nest_map = scenario.nest.unwrap();
+        assert_eq!((nest_map.width, nest_map.height), (3, 2));
+        assert_eq!(nest_map.surface_level(), 0);
+        assert_eq!(nest_map.ants, vec![(Position::new(1, 0), AntRole::Queen)]);
+        assert!(nest_map
+            .elements
+            .contains(&(Position::new(1, 1), Element::Sand)));
+
+        let crater_map = scenario.crater.unwrap();
+        assert_eq!((crater_map.width, crater_map.height), (2, 1));
+        assert_eq!(
+            crater_map.ants,
+            vec![(Position::new(1, 0), AntRole::Worker)]
+        );
+        assert!(crater_map
+            .elements
+            .contains(&(Position::new(0, 0), Element::Food)));
+    }
+
+    #[test]
+    fn ascii_scenario_without_sections_is_the_nest() {
+        let scenario = Scenario::from_ascii(".Q\n##").unwrap();
+
+        assert!(scenario.nest.is_some());
+        assert!(scenario.crater.is_none());
+    }
+
+    #[test]
+    fn ascii_map_rejects_unknown_symbols_and_ragged_rows() {
+        assert!(ScenarioMap::from_ascii("..\n#x").is_err());
+        assert!(ScenarioMap::from_ascii("..\n###").is_err());
+        assert!(Scenario::from_ascii("[crater]\n").is_err());
+        assert!(Scenario::from_ascii("..\n##").is_err());
+        assert!(Scenario::from_ascii("QQ\n##").is_err());
+        assert!(Scenario::from_ascii("[nest]\n.Q\n##\n[crater]\nQ.").is_err());
+    }
+
+    #[test]
+    fn png_map_reads_colors() {
+        #[rustfmt::skip]
+        let pixels: [u8; 16] = [
+            255, 0, 255, 255,   0, 0, 0, 0,
+            128, 64, 0, 255,    0, 255, 0, 255,
+        ];
+
+        let mut data = vec![];
+        {
+            let mut encoder = png::Encoder::new(&mut data, 2, 2);
+            encoder.set_color(png::ColorType::Rgba);
+            encoder.set_depth(png::BitDepth::Eight);
+
+            let mut writer = encoder.write_header().unwrap();
+            writer.write_image_data(&pixels).unwrap();
+        }
+
+        let map = ScenarioMap::from_file("tutorial.PNG", &data).unwrap();
+
+        assert_eq!((map.width, map.height), (2, 2));
+        assert_eq!(map.ants, vec![(Position::new(0, 0), AntRole::Queen)]);
+        assert_eq!(
+            map.elements,
+            vec![
+                (Position::new(0, 0), Element::Air),
+                (Position::new(1, 0), Element::Air),
+                (Position::new(0, 1), Element::Dirt),
+                (Position::new(1, 1), Element::Food),
+            ]
+        );
+    }
+}
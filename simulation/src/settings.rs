@@ -1,6 +1,8 @@
 use bevy::{prelude::*, reflect::Reflect};
+use bevy_turborand::{DelegatedRng, GlobalRng};
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy, Reflect, Debug)]
+#[derive(Clone, Copy, Reflect, Debug, Serialize, Deserialize)]
 pub struct Probabilities {
     pub random_drop: f32,        // drop while wandering
     pub random_nest_turn: f32,   // turn while wandering
@@ -19,9 +21,32 @@ pub struct Probabilities {
     pub below_surface_queen_nest_dig: f32,
     pub expand_nest: f32,
     pub sleep_emote: f32,
+    pub water_soak: f32, // chance for water to soak into adjacent dirt, turning it into mud
+    pub rain_drop: f32,  // chance, per column of sky, for a drop of rain to appear while raining
+    pub rain_wash_sand: f32, // chance for rain to wash exposed sand on the surface sideways
+    pub water_evaporate: f32, // chance for surface water to evaporate while it's not raining
+    pub dirt_collapse: f32, // chance for ceiling dirt to collapse, per tile it's beyond the reach of its support
+    pub mud_collapse: f32, // chance for ceiling mud to collapse, per tile it's beyond the reach of its support
+    pub mold_spread: f32,  // chance for mold to spread to adjacent food
 }
 
-#[derive(Resource, Copy, Clone, Reflect, Debug)]
+/// Shapes the underground of a newly generated nest. Only used when the nest is created.
+#[derive(Clone, Copy, Reflect, Debug, Serialize, Deserialize)]
+pub struct NestTerrain {
+    pub strata_count: isize, // layers of dirt, each harder than the one above
+    pub max_dirt_hardness: isize, // extra digs needed to excavate the deepest layer
+    pub strata_roughness: isize, // how far, in tiles, layers wander up and down
+    pub cavity_count: isize, // pre-existing pockets of air
+    pub max_cavity_radius: isize,
+    pub food_pocket_count: isize, // buried pockets of food
+    pub max_food_pocket_radius: isize,
+    pub rock_vein_count: isize, // winding seams of undiggable rock below the topmost layer
+    pub max_rock_vein_length: isize,
+    pub water_pocket_count: isize, // groundwater trapped in pockets of air
+    pub max_water_pocket_radius: isize,
+}
+
+#[derive(Resource, Copy, Clone, Reflect, Debug, Serialize, Deserialize)]
 #[reflect(Resource)]
 pub struct Settings {
     pub snapshot_interval: isize,
@@ -41,6 +66,32 @@ pub struct Settings {
     pub max_birthing_time: isize,
     pub is_breathwork_scheduled: bool,
     pub probabilities: Probabilities,
+    /// Seeds GlobalRng when a new colony is created. Colonies created from the same seed, and given the same external events,
+    /// are identical tick for tick. GlobalRng isn't persisted, so this only holds when replaying from the colony's creation.
+    pub rng_seed: u64,
+    pub nest_terrain: NestTerrain,
+    pub max_dry_time: isize,
+    pub max_rain_time: isize,
+    /// How far, in tiles, dirt can span from the nearest wall before a ceiling is at risk of collapse.
+    pub ceiling_support_distance: isize,
+    pub max_egg_time: isize,
+    pub max_larva_time: isize, // larvae only grow while they're well fed
+    pub max_pupa_time: isize,
+    /// How long a worker serves in its caste before moving on to another.
+    pub max_caste_time: isize,
+    pub brood_per_nurse: isize, // 0 means workers aren't called back to nursing however much brood there is
+    pub workers_per_soldier: isize, // 0 means no workers are kept back as soldiers
+    /// How long the queen circles above the nest, waiting to be shown where to land, before landing on her own.
+    pub max_nuptial_flight_time: isize,
+    /// How long ants live before dying of old age.
+    pub max_worker_age_time: isize,
+    pub max_queen_age_time: isize,
+    /// How long a corpse takes to decay away.
+    pub max_corpse_decay_time: isize,
+    /// How long food keeps in the nest before it spoils into mold.
+    pub max_food_freshness_time: isize,
+    /// How long an ant can go without water before dying of dehydration.
+    pub max_thirst_time: isize,
 }
 
 impl Default for Settings {
@@ -89,14 +140,57 @@ impl Default for Settings {
                 // TODO: keep playing with this value. lower chance = more cramped nest, but less sand to manage.
                 expand_nest: 0.2,
                 sleep_emote: 0.001,
+                water_soak: 0.002,
+                rain_drop: 0.02,
+                rain_wash_sand: 0.01,
+                water_evaporate: 0.001,
+                // Collapses should be rare enough that ants can haul out the sand, but common enough to shape chambers.
+                dirt_collapse: 0.00002,
+                mud_collapse: 0.0002,
+                mold_spread: 0.0001,
             },
+            // Every colony is seeded, even if the user doesn't choose a seed, so that any colony can be reproduced.
+            rng_seed: generate_rng_seed(),
+            nest_terrain: NestTerrain {
+                strata_count: 4,
+                max_dirt_hardness: 3,
+                strata_roughness: 3,
+                cavity_count: 4,
+                max_cavity_radius: 3,
+                food_pocket_count: 3,
+                max_food_pocket_radius: 2,
+                rock_vein_count: 3,
+                max_rock_vein_length: 16,
+                water_pocket_count: 2,
+                max_water_pocket_radius: 2,
+            },
+            max_dry_time: 86_400, // 1 day
+            max_rain_time: 3_600, // 1 hour
+            ceiling_support_distance: 3,
+            max_egg_time: 14_400,   // 4 hours
+            max_larva_time: 28_800, // 8 hours
+            max_pupa_time: 14_400,  // 4 hours
+            max_caste_time: 86_400, // 1 day
+            brood_per_nurse: 4,
+            workers_per_soldier: 10,
+            max_nuptial_flight_time: 60,      // 1 minute
+            max_worker_age_time: 2_592_000,   // 30 days
+            max_queen_age_time: 31_536_000,   // 1 year
+            max_corpse_decay_time: 259_200,   // 3 days
+            max_food_freshness_time: 432_000, // 5 days
+            max_thirst_time: 172_800,         // 2 days
         }
     }
 }
 
+pub fn generate_rng_seed() -> u64 {
+    GlobalRng::new().u64(..)
+}
+
 pub fn register_settings(app_type_registry: ResMut<AppTypeRegistry>) {
     app_type_registry.write().register::<Settings>();
     app_type_registry.write().register::<Probabilities>();
+    app_type_registry.write().register::<NestTerrain>();
 }
 
 pub fn initialize_settings_resources(mut commands: Commands) {
@@ -106,3 +200,8 @@ pub fn initialize_settings_resources(mut commands: Commands) {
 pub fn remove_settings_resources(mut commands: Commands) {
     commands.remove_resource::<Settings>();
 }
+
+/// Reseed GlobalRng before a new colony is spawned so that the colony is reproducible from its seed.
+pub fn seed_global_rng(settings: Res<Settings>, mut rng: ResMut<GlobalRng>) {
+    *rng = GlobalRng::with_seed(settings.rng_seed);
+}
@@ -0,0 +1,330 @@
+//! Builds small, seeded, simulations from ASCII maps so that simulation rules can be tested with `cargo test`.
+//!
+//! Maps are written one row per line, using the symbols of a `ScenarioMap`. Leading/trailing whitespace and blank lines
+//! are ignored so maps can be indented.
+
+use crate::{
+    app_state::AppState,
+    common::{
+        ant::{
+            age::Age, digestion::Digestion, hunger::Hunger, initiative::Initiative, thirst::Thirst,
+            Ant, AntBundle, AntColor, AntInventory, AntName, AntRole, InventoryItemBundle,
+        },
+        element::{Element, ElementBundle},
+        grid::ElementEntityPositionCache,
+        position::Position,
+        Zone,
+    },
+    crater_simulation::{
+        ant::CraterOrientation,
+        crater::{AtCrater, Crater},
+    },
+    nest_simulation::{
+        ant::{NestAngle, NestFacing, NestOrientation},
+        brood::{BroodBundle, BroodStage, Development},
+        nest::{AtNest, Nest},
+    },
+    scenario::ScenarioMap,
+    settings::Settings,
+    SimulationPlugin,
+};
+use bevy::{app::FixedMain, prelude::*, state::app::StatesPlugin};
+use bevy_turborand::prelude::*;
+
+/// Tests are seeded so they behave the same on every run.
+pub const TEST_RNG_SEED: u64 = 42069;
+
+/// An empty crater for tests which only care about the nest.
+const EMPTY_CRATER_MAP: &str = "
+    ...
+    ...
+    ...
+";
+
+fn element_symbol(element: Element) -> char {
+    match element {
+        Element::Air => '.',
+        Element::Dirt => '#',
+        Element::Sand => 's',
+        Element::Food => 'f',
+        Element::Rock => 'R',
+        Element::Water => 'w',
+        Element::Mud => 'm',
+        Element::Mold => 'o',
+    }
+}
+
+fn ant_symbol(ant_role: AntRole) -> char {
+    match ant_role {
+        AntRole::Queen => 'Q',
+        AntRole::Worker => 'W',
+    }
+}
+
+/// Strip indentation from a map so it can be compared with the output of `TestSimulation::nest_map`.
+fn normalize_map(map: &str) -> String {
+    map.lines()
+        .map(str::trim)
+        .filter(|row| !row.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A simulation running without rendering, UI, or saving. Ticks are run manually, rather than in real time.
+pub struct TestSimulation {
+    app: App,
+}
+
+impl TestSimulation {
+    /// Create a simulation from a map of the nest. The crater is empty.
+    pub fn new(nest_map: &str) -> Self {
+        Self::with_settings(nest_map, Settings::default())
+    }
+
+    /// Create a simulation from a map of the nest using the given Settings. Settings' grid sizes are taken from the map.
+    pub fn with_settings(nest_map: &str, settings: Settings) -> Self {
+        Self::with_crater(nest_map, EMPTY_CRATER_MAP, settings)
+    }
+
+    pub fn with_crater(nest_map: &str, crater_map: &str, settings: Settings) -> Self {
+        let nest_map = ScenarioMap::from_ascii(nest_map).unwrap();
+        let crater_map = ScenarioMap::from_ascii(crater_map).unwrap();
+
+        let settings = Settings {
+            nest_width: nest_map.width,
+            nest_height: nest_map.height,
+            crater_width: crater_map.width,
+            crater_height: crater_map.height,
+            rng_seed: TEST_RNG_SEED,
+            ..settings
+        };
+
+        let mut app = App::new();
+
+        // MinimalPlugins doesn't include state support which the simulation relies on.
+        app.add_plugins((MinimalPlugins, StatesPlugin, RngPlugin::default()));
+        app.insert_resource(GlobalRng::with_seed(settings.rng_seed));
+        app.insert_resource(settings);
+
+        // Start by finishing setup, as if loading a saved colony, so the world spawned from the map is kept.
+        // This must be inserted before SimulationPlugin initializes AppState because the first state to be inserted wins.
+        app.insert_state(AppState::FinishSetup);
+        app.add_plugins(SimulationPlugin);
+
+        app.finish();
+        app.cleanup();
+
+        let world = app.world_mut();
+        world.spawn((Nest::new(nest_map.surface_level()), AtNest));
+        world.spawn((Crater, AtCrater));
+
+        spawn_map(world, &nest_map, AtNest, NestOrientation::default());
+        spawn_map(world, &crater_map, AtCrater, CraterOrientation::default());
+
+        // Each setup state is entered on its own frame, as it would be when running normally, so change detection is
+        // cleared between setup and the first tick.
+        while *app.world().resource::<State<AppState>>().get()
+            != AppState::PostSetupClearChangeDetection
+        {
+            app.update();
+        }
+
+        // The story is entered without running a full frame because saving runs every frame while the story is told.
+        app.world_mut().run_schedule(StateTransition);
+
+        Self { app }
+    }
+
+    pub fn world(&self) -> &World {
+        self.app.world()
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        self.app.world_mut()
+    }
+
+    /// Run the simulation for the given number of ticks, skipping everything else a frame would do.
+    pub fn tick(&mut self, ticks: usize) {
+        for _ in 0..ticks {
+            let world = self.app.world_mut();
+
+            // First swaps event buffers so events don't accumulate forever.
+            world.run_schedule(First);
+            world.run_schedule(FixedMain);
+            world.run_schedule(StateTransition);
+            world.clear_trackers();
+        }
+    }
+
+    /// Returns the entity of the ant at the given position in the nest.
+    pub fn nest_ant(&mut self, position: Position) -> Entity {
+        self.ant::<AtNest>(position)
+    }
+
+    pub fn crater_ant(&mut self, position: Position) -> Entity {
+        self.ant::<AtCrater>(position)
+    }
+
+    fn ant<Z: Zone>(&mut self, position: Position) -> Entity {
+        let world = self.world_mut();
+
+        world
+            .query_filtered::<(Entity, &Position), (With<Ant>, With<Z>)>()
+            .iter(world)
+            .find(|(_, ant_position)| **ant_position == position)
+            .map(|(entity, _)| entity)
+            .unwrap_or_else(|| panic!("No ant found at {:?}", position))
+    }
+
+    /// Turn an ant in the nest to face the given way, standing upright.
+    pub fn face_nest_ant(&mut self, ant_entity: Entity, facing: NestFacing) {
+        self.world_mut()
+            .entity_mut(ant_entity)
+            .insert(NestOrientation::new(facing, NestAngle::Zero));
+    }
+
+    /// Spawn brood lying in the nest as though it had just reached the given stage. Larvae are given Hunger and Digestion, as they are when they hatch.
+    pub fn spawn_brood(&mut self, position: Position, stage: BroodStage) -> Entity {
+        let settings = *self.world().resource::<Settings>();
+
+        let mut brood = self.world_mut().spawn(BroodBundle::new(
+            position,
+            AntColor(settings.ant_color),
+            &settings,
+        ));
+
+        match stage {
+            BroodStage::Egg => {}
+            BroodStage::Larva => {
+                brood.insert((
+                    stage,
+                    Development::new(settings.max_larva_time),
+                    Hunger::new(settings.max_hunger_time),
+                    Digestion::new(settings.max_digestion_time),
+                ));
+            }
+            BroodStage::Pupa => {
+                brood.insert((stage, Development::new(settings.max_pupa_time)));
+            }
+        }
+
+        brood.id()
+    }
+
+    /// Returns the entity of the element at the given position in the nest.
+    pub fn nest_element_entity(&mut self, position: Position) -> Entity {
+        let world = self.world_mut();
+
+        world
+            .query_filtered::<&ElementEntityPositionCache, With<AtNest>>()
+            .single(world)
+            .0[position.y as usize][position.x as usize]
+    }
+
+    /// Returns the element at the given position in the nest.
+    pub fn nest_element(&mut self, position: Position) -> Element {
+        let element_entity = self.nest_element_entity(position);
+
+        *self.world().get::<Element>(element_entity).unwrap()
+    }
+
+    /// Put an element into the ant's inventory, replacing anything it was carrying.
+    pub fn give_nest_ant(&mut self, ant_entity: Entity, element: Element) {
+        let world = self.world_mut();
+        let item_entity = world.spawn(InventoryItemBundle::new(element, AtNest)).id();
+
+        world.get_mut::<AntInventory>(ant_entity).unwrap().0 = Some(item_entity);
+    }
+
+    /// Returns the element the ant is carrying, if any.
+    pub fn inventory_element(&self, ant_entity: Entity) -> Option<Element> {
+        let world = self.world();
+
+        world
+            .get::<AntInventory>(ant_entity)
+            .unwrap()
+            .0
+            .map(|item_entity| *world.get::<Element>(item_entity).unwrap())
+    }
+
+    /// Draw the nest using the same symbols as the map it was created from.
+    pub fn nest_map(&mut self) -> String {
+        self.map::<AtNest>()
+    }
+
+    pub fn crater_map(&mut self) -> String {
+        self.map::<AtCrater>()
+    }
+
+    fn map<Z: Zone>(&mut self) -> String {
+        let world = self.app.world_mut();
+
+        let mut rows = world
+            .query_filtered::<&ElementEntityPositionCache, With<Z>>()
+            .single(world)
+            .0
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|&entity| element_symbol(*world.get::<Element>(entity).unwrap()))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        for (position, ant_role) in world
+            .query_filtered::<(&Position, &AntRole), (With<Ant>, With<Z>)>()
+            .iter(world)
+        {
+            rows[position.y as usize][position.x as usize] = ant_symbol(*ant_role);
+        }
+
+        rows.iter()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[track_caller]
+    pub fn assert_nest(&mut self, expected_map: &str) {
+        assert_eq!(
+            self.nest_map(),
+            normalize_map(expected_map),
+            "\nThe nest doesn't match the expected map."
+        );
+    }
+}
+
+fn spawn_map<Z: Zone + Copy>(
+    world: &mut World,
+    map: &ScenarioMap,
+    zone: Z,
+    orientation: impl Component + Copy,
+) {
+    for &(position, element) in map.elements.iter() {
+        world.spawn(ElementBundle::new(element, position, zone));
+    }
+
+    let settings = *world.resource::<Settings>();
+
+    for &(position, ant_role) in map.ants.iter() {
+        let mut rng = world.resource_mut::<GlobalRng>();
+        let initiative = Initiative::new(&mut rng);
+
+        world.spawn((
+            AntBundle::new(
+                position,
+                AntColor(settings.ant_color),
+                AntInventory::default(),
+                ant_role,
+                AntName(format!("{:?}", ant_role)),
+                initiative,
+                zone,
+                Hunger::new(settings.max_hunger_time),
+                Digestion::new(settings.max_digestion_time),
+                Thirst::new(settings.max_thirst_time),
+                Age::for_role(ant_role, &settings),
+            ),
+            orientation,
+        ));
+    }
+}
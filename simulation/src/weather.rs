@@ -0,0 +1,69 @@
+use crate::{
+    settings::Settings,
+    story_time::{StoryTime, DEFAULT_TICKS_PER_SECOND},
+};
+use bevy::prelude::*;
+use bevy_turborand::{DelegatedRng, GlobalRng};
+
+/// Rain comes and goes with the passing of story time. Dry spells and showers each last a random amount of time,
+/// between half and all of `Settings::max_dry_time` and `Settings::max_rain_time` respectively.
+#[derive(Resource, Clone, Reflect, Default, Debug, PartialEq)]
+#[reflect(Resource)]
+pub struct Weather {
+    is_raining: bool,
+    /// The tick of story time at which rain next starts, or stops. Unscheduled until the story's first tick.
+    next_change_tick: Option<isize>,
+}
+
+impl Weather {
+    pub fn is_raining(&self) -> bool {
+        self.is_raining
+    }
+
+    /// Rain until the given tick of story time, after which a dry spell is scheduled as usual.
+    pub fn rain_until(&mut self, tick: isize) {
+        self.is_raining = true;
+        self.next_change_tick = Some(tick);
+    }
+}
+
+pub fn register_weather(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<Weather>();
+    app_type_registry.write().register::<Option<isize>>();
+}
+
+pub fn initialize_weather_resources(mut commands: Commands) {
+    commands.init_resource::<Weather>();
+}
+
+pub fn remove_weather_resources(mut commands: Commands) {
+    commands.remove_resource::<Weather>();
+}
+
+/// Start or stop the rain once the current spell of weather has run its course, and schedule the next change.
+pub fn update_weather(
+    mut weather: ResMut<Weather>,
+    story_time: Res<StoryTime>,
+    settings: Res<Settings>,
+    mut rng: ResMut<GlobalRng>,
+) {
+    let elapsed_ticks = story_time.elapsed_ticks();
+
+    match weather.next_change_tick {
+        Some(next_change_tick) if elapsed_ticks < next_change_tick => return,
+        Some(_) => weather.is_raining = !weather.is_raining,
+        // A new story, or one saved before weather existed, starts with a dry spell.
+        None => weather.is_raining = false,
+    }
+
+    let max_time = if weather.is_raining {
+        settings.max_rain_time
+    } else {
+        settings.max_dry_time
+    };
+
+    let max_ticks = (max_time * DEFAULT_TICKS_PER_SECOND).max(1);
+    let duration_ticks = rng.isize(max_ticks / 2..max_ticks + 1);
+
+    weather.next_change_tick = Some(elapsed_ticks + duration_ticks);
+}
@@ -0,0 +1,609 @@
+use crate::{
+    common::{
+        ant::{
+            age::Age,
+            caste::Caste,
+            digestion::Digestion,
+            hunger::Hunger,
+            task::{AntTask, Task},
+            thirst::Thirst,
+            Ant, AntInventory, AntRole, Dead, InventoryItem,
+        },
+        element::{Element, FoodKind, Freshness, Hardness},
+        grid::ElementEntityPositionCache,
+        pheromone::{Pheromone, PheromoneStrength},
+        position::Position,
+        Zone,
+    },
+    crater_simulation::{ant::CraterOrientation, crater::AtCrater},
+    nest_simulation::{
+        ant::NestOrientation,
+        brood::{Brood, BroodStage, Development},
+        nest::AtNest,
+    },
+    story_time::StoryTime,
+};
+use bevy::prelude::*;
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+    mem::discriminant,
+};
+
+/// The parts of the simulation which are hashed separately so that a desync can be traced back to where it started.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum WorldStateSubsystem {
+    Elements,
+    Ants,
+    Pheromones,
+    Hunger,
+    Brood,
+}
+
+impl WorldStateSubsystem {
+    pub const ALL: [WorldStateSubsystem; 5] = [
+        WorldStateSubsystem::Elements,
+        WorldStateSubsystem::Ants,
+        WorldStateSubsystem::Pheromones,
+        WorldStateSubsystem::Hunger,
+        WorldStateSubsystem::Brood,
+    ];
+}
+
+impl fmt::Display for WorldStateSubsystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            WorldStateSubsystem::Elements => "elements",
+            WorldStateSubsystem::Ants => "ants",
+            WorldStateSubsystem::Pheromones => "pheromones",
+            WorldStateSubsystem::Hunger => "hunger",
+            WorldStateSubsystem::Brood => "brood",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+/// A fingerprint of the simulation's state. Two worlds with equal hashes are, for all practical purposes, identical.
+///
+/// Hashes are stable across processes, and so can be compared between builds for the same target, because:
+///     - StableHasher's algorithm is fixed, unlike DefaultHasher's which may change between Rust releases.
+///     - Entities aren't hashed. Their ids depend on allocation order which isn't part of the simulation's behavior.
+///     - Per-entity hashes are sorted before being combined so query iteration order doesn't matter.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct WorldStateHash {
+    pub elements: u64,
+    pub ants: u64,
+    pub pheromones: u64,
+    pub hunger: u64,
+    pub brood: u64,
+}
+
+impl WorldStateHash {
+    pub fn new(world: &mut World) -> Self {
+        Self {
+            elements: hash_one((
+                hash_elements::<AtNest>(world),
+                hash_elements::<AtCrater>(world),
+            )),
+            ants: hash_ants(world),
+            pheromones: hash_pheromones(world),
+            hunger: hash_hunger(world),
+            brood: hash_brood(world),
+        }
+    }
+
+    pub fn get(&self, subsystem: WorldStateSubsystem) -> u64 {
+        match subsystem {
+            WorldStateSubsystem::Elements => self.elements,
+            WorldStateSubsystem::Ants => self.ants,
+            WorldStateSubsystem::Pheromones => self.pheromones,
+            WorldStateSubsystem::Hunger => self.hunger,
+            WorldStateSubsystem::Brood => self.brood,
+        }
+    }
+
+    /// Returns the first subsystem, in the order listed by `WorldStateSubsystem::ALL`, whose hash differs.
+    pub fn diverging_subsystem(&self, other: &WorldStateHash) -> Option<WorldStateSubsystem> {
+        WorldStateSubsystem::ALL
+            .into_iter()
+            .find(|&subsystem| self.get(subsystem) != other.get(subsystem))
+    }
+}
+
+/// FNV-1a, 64-bit. Integers are hashed in native byte order, and lengths at the target's pointer width, so hashes are
+/// only comparable between builds for the same target.
+struct StableHasher(u64);
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+fn hash_one(value: impl Hash) -> u64 {
+    let mut hasher = StableHasher::default();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Combine per-entity hashes without depending on the order in which entities were queried.
+fn hash_unordered(mut hashes: Vec<u64>) -> u64 {
+    hashes.sort_unstable();
+    hash_one(hashes)
+}
+
+/// The element grid is hashed row by row from the position cache, which is the simulation's source of truth for
+/// what's where, rather than from each element's Position.
+fn hash_elements<Z: Zone>(world: &mut World) -> u64 {
+    let mut hasher = StableHasher::default();
+
+    let mut elements_query = world.query::<(
+        &Element,
+        Option<&Hardness>,
+        Option<&Freshness>,
+        Option<&FoodKind>,
+    )>();
+    let mut elements_cache_query = world.query_filtered::<&ElementEntityPositionCache, With<Z>>();
+
+    for elements_cache in elements_cache_query.iter(world) {
+        for row in elements_cache.0.iter() {
+            for &entity in row.iter() {
+                elements_query.get(world, entity).ok().hash(&mut hasher);
+            }
+        }
+    }
+
+    hasher.finish()
+}
+
+/// Ants are hashed with everything which drives their behavior, i.e. their task, caste and age, so a divergence is caught
+/// before it moves an ant. Carried items are hashed separately, so only whether an ant is carrying something matters here.
+fn hash_ants(world: &mut World) -> u64 {
+    let hashes = world
+        .query_filtered::<(
+            &Position,
+            &AntRole,
+            Option<&NestOrientation>,
+            Option<&CraterOrientation>,
+            &AntTask,
+            Option<&Caste>,
+            &Age,
+            &AntInventory,
+            Has<AtNest>,
+            Has<Dead>,
+        ), With<Ant>>()
+        .iter(world)
+        .map(
+            |(
+                position,
+                role,
+                nest_orientation,
+                crater_orientation,
+                ant_task,
+                caste,
+                age,
+                inventory,
+                is_at_nest,
+                is_dead,
+            )| {
+                let mut hasher = StableHasher::default();
+
+                (
+                    position,
+                    role,
+                    nest_orientation,
+                    crater_orientation,
+                    caste,
+                    age,
+                    inventory.0.is_some(),
+                    is_at_nest,
+                    is_dead,
+                )
+                    .hash(&mut hasher);
+
+                hash_task(ant_task.current(), &mut hasher);
+                ant_task.interrupted().len().hash(&mut hasher);
+                for task in ant_task.interrupted() {
+                    hash_task(task, &mut hasher);
+                }
+
+                hasher.finish()
+            },
+        )
+        .collect();
+
+    hash_unordered(hashes)
+}
+
+/// Some tasks track a pheromone's strength, which is a float and so is hashed by its bits.
+fn hash_task(task: &Task, hasher: &mut StableHasher) {
+    discriminant(task).hash(hasher);
+
+    match task {
+        Task::Flying(flight) => flight.hash(hasher),
+        Task::Nesting(nesting) => nesting.hash(hasher),
+        Task::Tunneling(strength)
+        | Task::Chambering(strength)
+        | Task::LeavingNest(strength)
+        | Task::LeavingFood(strength) => strength.to_bits().hash(hasher),
+        Task::Idle | Task::Asleep => {}
+    }
+}
+
+fn hash_pheromones(world: &mut World) -> u64 {
+    let hashes = world
+        .query::<(&Position, &Pheromone, &PheromoneStrength, Has<AtNest>)>()
+        .iter(world)
+        .map(|(position, pheromone, pheromone_strength, is_at_nest)| {
+            hash_one((
+                position,
+                pheromone,
+                pheromone_strength.value().to_bits(),
+                pheromone_strength.max().to_bits(),
+                is_at_nest,
+            ))
+        })
+        .collect();
+
+    hash_unordered(hashes)
+}
+
+/// Hunger, digestion, the kind of food being digested, and thirst are paired with the ant's position so that swapping
+/// values between ants is detected.
+fn hash_hunger(world: &mut World) -> u64 {
+    let hashes = world
+        .query_filtered::<(&Position, &Hunger, &Digestion, Option<&Thirst>, Has<AtNest>), With<Ant>>()
+        .iter(world)
+        .map(|(position, hunger, digestion, thirst, is_at_nest)| {
+            hash_one((
+                position,
+                hunger.value().to_bits(),
+                digestion.value().to_bits(),
+                digestion.food_kind(),
+                thirst.map(|thirst| thirst.value().to_bits()),
+                is_at_nest,
+            ))
+        })
+        .collect();
+
+    hash_unordered(hashes)
+}
+
+/// Brood aren't ants until they emerge, so they're hashed on their own. Carried brood have no Position and are hashed as
+/// being carried instead. Only larvae have Hunger and Digestion.
+fn hash_brood(world: &mut World) -> u64 {
+    let hashes = world
+        .query_filtered::<(
+            Option<&Position>,
+            &BroodStage,
+            &Development,
+            Option<&Hunger>,
+            Option<&Digestion>,
+            Has<InventoryItem>,
+            Has<AtNest>,
+        ), With<Brood>>()
+        .iter(world)
+        .map(
+            |(position, stage, development, hunger, digestion, is_carried, is_at_nest)| {
+                hash_one((
+                    position,
+                    stage,
+                    development.value().to_bits(),
+                    hunger.map(|hunger| hunger.value().to_bits()),
+                    digestion.map(|digestion| (digestion.value().to_bits(), digestion.food_kind())),
+                    is_carried,
+                    is_at_nest,
+                ))
+            },
+        )
+        .collect();
+
+    hash_unordered(hashes)
+}
+
+/// Where two hash logs first disagree.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct WorldStateDivergence {
+    pub tick: isize,
+    pub subsystem: WorldStateSubsystem,
+    pub expected: u64,
+    pub actual: u64,
+}
+
+impl WorldStateDivergence {
+    pub fn find(
+        tick: isize,
+        expected: &WorldStateHash,
+        actual: &WorldStateHash,
+    ) -> Option<WorldStateDivergence> {
+        actual
+            .diverging_subsystem(expected)
+            .map(|subsystem| WorldStateDivergence {
+                tick,
+                subsystem,
+                expected: expected.get(subsystem),
+                actual: actual.get(subsystem),
+            })
+    }
+}
+
+impl fmt::Display for WorldStateDivergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Diverged at tick {} in {} (expected {:016x}, found {:016x})",
+            self.tick, self.subsystem, self.expected, self.actual
+        )
+    }
+}
+
+/// Records the world's hash after every simulated tick. Only exists while hashing, e.g. when checking for desyncs,
+/// because hashing the world every tick is expensive.
+#[derive(Resource, Default, Debug, PartialEq)]
+pub struct WorldStateHashLog(pub Vec<(isize, WorldStateHash)>);
+
+impl WorldStateHashLog {
+    /// Compare against an expected log, tick by tick, until either log runs out.
+    pub fn find_divergence(&self, expected: &WorldStateHashLog) -> Option<WorldStateDivergence> {
+        expected
+            .0
+            .iter()
+            .zip(self.0.iter())
+            .find_map(|((_, expected), (tick, actual))| {
+                WorldStateDivergence::find(*tick, expected, actual)
+            })
+    }
+
+    /// One line per tick: the tick followed by each subsystem's hash, in hex, in the order of `WorldStateSubsystem::ALL`.
+    pub fn to_text(&self) -> String {
+        self.0
+            .iter()
+            .map(|(tick, hash)| {
+                let hashes = WorldStateSubsystem::ALL
+                    .iter()
+                    .map(|&subsystem| format!("{:016x}", hash.get(subsystem)))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                format!("{} {}\n", tick, hashes)
+            })
+            .collect()
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, String> {
+        text.lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let invalid_line = || format!("Invalid hash log line: {}", line);
+                let mut values = line.split_whitespace();
+
+                let tick = values
+                    .next()
+                    .and_then(|tick| tick.parse().ok())
+                    .ok_or_else(invalid_line)?;
+
+                let mut next_hash = || {
+                    values
+                        .next()
+                        .and_then(|hash| u64::from_str_radix(hash, 16).ok())
+                        .ok_or_else(invalid_line)
+                };
+
+                let hash = WorldStateHash {
+                    elements: next_hash()?,
+                    ants: next_hash()?,
+                    pheromones: next_hash()?,
+                    hunger: next_hash()?,
+                    brood: next_hash()?,
+                };
+
+                Ok((tick, hash))
+            })
+            .collect::<Result<_, _>>()
+            .map(WorldStateHashLog)
+    }
+}
+
+/// Hash the world once the tick is over, and all of its commands have been applied, so every subsystem has settled.
+pub fn record_world_state_hash(world: &mut World) {
+    let tick = world.resource::<StoryTime>().elapsed_ticks();
+    let hash = WorldStateHash::new(world);
+
+    world
+        .resource_mut::<WorldStateHashLog>()
+        .0
+        .push((tick, hash));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{StableHasher, WorldStateHash, WorldStateHashLog};
+    use crate::{
+        common::{
+            ant::task::{AntTask, Task},
+            element::{FoodKind, Freshness},
+            position::Position,
+        },
+        nest_simulation::brood::{BroodStage, Development},
+        test_support::TestSimulation,
+    };
+    use std::hash::Hasher;
+
+    // Confirm that the hasher is FNV-1a so hash logs written by older builds stay comparable.
+    #[test]
+    fn stable_hasher_is_fnv_1a() {
+        let mut hasher = StableHasher::default();
+        hasher.write(b"a");
+
+        assert_eq!(hasher.finish(), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    // Confirm that two colonies created from the same seed stay identical tick for tick.
+    #[test]
+    fn seeded_simulations_hash_identically() {
+        let map = "
+            ....W.....
+            ..........
+            ####.#####
+            #fQW.#Www#
+            ##..######
+            ";
+
+        let hash_logs = [0, 1].map(|_| {
+            let mut simulation = TestSimulation::new(map);
+            simulation.world_mut().init_resource::<WorldStateHashLog>();
+
+            simulation.tick(200);
+
+            simulation
+                .world_mut()
+                .remove_resource::<WorldStateHashLog>()
+                .unwrap()
+        });
+
+        assert_eq!(hash_logs[0].0.len(), 200);
+        assert_eq!(hash_logs[0].find_divergence(&hash_logs[1]), None);
+        assert_eq!(hash_logs[0], hash_logs[1]);
+    }
+
+    // Confirm that a hash log survives being written to, and read back from, its text format.
+    #[test]
+    fn hash_log_text_round_trips() {
+        let hash_log = WorldStateHashLog(vec![
+            (
+                1,
+                WorldStateHash {
+                    elements: 0,
+                    ants: 1,
+                    pheromones: u64::MAX,
+                    hunger: 0x0123_4567_89ab_cdef,
+                    brood: 3,
+                },
+            ),
+            (
+                2,
+                WorldStateHash {
+                    elements: 42,
+                    ants: 0xfedc_ba98_7654_3210,
+                    pheromones: 7,
+                    hunger: 0,
+                    brood: u64::MAX,
+                },
+            ),
+        ]);
+
+        let text = hash_log.to_text();
+
+        assert_eq!(WorldStateHashLog::from_text(&text), Ok(hash_log));
+        assert!(WorldStateHashLog::from_text("1 not-a-hash").is_err());
+    }
+
+    // Confirm that food going stale is caught before it spoils.
+    #[test]
+    fn freshness_is_hashed() {
+        let mut simulation = TestSimulation::new(
+            "
+            ....
+            ####
+            #f.#
+            ####
+            ",
+        );
+        simulation.tick(1);
+
+        let food_entity = simulation.nest_element_entity(Position::new(1, 2));
+        let hash = WorldStateHash::new(simulation.world_mut());
+
+        let world = simulation.world_mut();
+        world.get_mut::<Freshness>(food_entity).unwrap().tick();
+
+        let ticked_hash = WorldStateHash::new(world);
+
+        assert_ne!(hash.elements, ticked_hash.elements);
+        assert_eq!(hash.ants, ticked_hash.ants);
+    }
+
+    // Confirm that swapping one kind of food for another is caught even though the grid looks the same.
+    #[test]
+    fn food_kind_is_hashed() {
+        let mut simulation = TestSimulation::new(
+            "
+            ....
+            ####
+            #f.#
+            ####
+            ",
+        );
+
+        let food_entity = simulation.nest_element_entity(Position::new(1, 2));
+
+        let world = simulation.world_mut();
+        world.entity_mut(food_entity).insert(FoodKind::Sugar);
+        let hash = WorldStateHash::new(world);
+
+        world.entity_mut(food_entity).insert(FoodKind::Protein);
+
+        assert_ne!(hash.elements, WorldStateHash::new(world).elements);
+    }
+
+    // Confirm that a change in what an ant is doing is caught before it moves the ant.
+    #[test]
+    fn ant_task_is_hashed() {
+        let mut simulation = TestSimulation::new(
+            "
+            ....
+            ####
+            #W.#
+            ####
+            ",
+        );
+
+        let ant_entity = simulation.nest_ant(Position::new(1, 2));
+
+        let world = simulation.world_mut();
+        *world.get_mut::<AntTask>(ant_entity).unwrap().current_mut() = Task::Tunneling(8.0);
+        let hash = WorldStateHash::new(world);
+
+        *world.get_mut::<AntTask>(ant_entity).unwrap().current_mut() = Task::Tunneling(7.0);
+
+        assert_ne!(hash.ants, WorldStateHash::new(world).ants);
+    }
+
+    // Confirm that brood developing differently is caught before any of them emerge.
+    #[test]
+    fn brood_development_is_hashed() {
+        let mut simulation = TestSimulation::new(
+            "
+            ....
+            ####
+            #..#
+            ####
+            ",
+        );
+
+        let brood_entity = simulation.spawn_brood(Position::new(1, 2), BroodStage::Egg);
+        let hash = WorldStateHash::new(simulation.world_mut());
+
+        let world = simulation.world_mut();
+        world.get_mut::<Development>(brood_entity).unwrap().tick();
+
+        let ticked_hash = WorldStateHash::new(world);
+
+        assert_ne!(hash.brood, ticked_hash.brood);
+        assert_eq!(hash.ants, ticked_hash.ants);
+    }
+}
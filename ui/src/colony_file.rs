@@ -0,0 +1,16 @@
+use bevy_egui::egui;
+use simulation::save::{import_colony_file, IS_COLONY_FILE_PATH_REQUIRED};
+
+/// Show the controls for importing a colony file. Native builds require the user to type the file's path,
+/// while web builds open the browser's file picker. The picked file is retrieved via `take_imported_colony_file`.
+pub fn show_colony_file_import(ui: &mut egui::Ui, colony_file_path: &mut String) {
+    ui.horizontal(|ui| {
+        if IS_COLONY_FILE_PATH_REQUIRED {
+            ui.add(egui::TextEdit::singleline(colony_file_path).hint_text("Colony file path"));
+        }
+
+        if ui.button("Import Colony").clicked() {
+            import_colony_file(colony_file_path);
+        }
+    });
+}
@@ -1,3 +1,4 @@
+mod colony_file;
 mod main_menu;
 pub mod story;
 
@@ -4,7 +4,15 @@ use bevy_egui::{
     EguiContexts,
 };
 
-use simulation::app_state::AppState;
+use simulation::{
+    app_state::AppState,
+    common::{LoadProgress, SimulationLoadProgress},
+    save::{get_default_colony_file_path, take_imported_colony_file, ColonyFileStatus, SaveSlots},
+    scenario::Scenario,
+    settings::Settings,
+};
+
+use crate::colony_file::show_colony_file_import;
 
 pub struct MainMenuUIPlugin;
 
@@ -17,10 +25,37 @@ impl Plugin for MainMenuUIPlugin {
     }
 }
 
+/// Text input state which only lives as long as the user is interacting with the menu.
+#[derive(Default)]
+pub struct MainMenuState {
+    new_colony_name: String,
+    rng_seed: String,
+    map_path: String,
+    renaming_slot: Option<(String, String)>,
+    colony_file_path: Option<String>,
+}
+
 pub fn update_main_menu(
     mut contexts: EguiContexts,
     mut next_app_state: ResMut<NextState<AppState>>,
+    mut save_slots: ResMut<SaveSlots>,
+    simulation_load_progress: Res<SimulationLoadProgress>,
+    mut colony_file_status: ResMut<ColonyFileStatus>,
+    mut main_menu_state: Local<MainMenuState>,
+    mut commands: Commands,
 ) {
+    // Importing a colony file loads it immediately so the user can see what they've imported.
+    if let Some(result) = take_imported_colony_file() {
+        match result.and_then(|colony_file| save_slots.import(&colony_file)) {
+            Ok(id) => {
+                colony_file_status.0 = None;
+                save_slots.activate(&id);
+                next_app_state.set(AppState::Loading);
+            }
+            Err(error) => colony_file_status.0 = Some(error),
+        }
+    }
+
     let ctx = contexts.ctx_mut();
 
     egui::Window::new("Main Menu")
@@ -31,12 +66,194 @@ pub fn update_main_menu(
             ui.vertical_centered(|ui| {
                 ui.label("Welcome to Symbiants");
 
+                if let LoadProgress::Failure(Some(reason)) = &simulation_load_progress.save_file {
+                    ui.colored_label(egui::Color32::LIGHT_RED, reason);
+                }
+
                 ui.add_enabled(false, egui::Button::new("Story Mode"))
                     .on_disabled_hover_text("Coming soon™!");
 
-                if ui.button("Sandbox Mode").clicked() {
+                ui.horizontal(|ui| {
+                    ui.label("Colony Name");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut main_menu_state.new_colony_name)
+                            .hint_text(format!("Colony {}", save_slots.slots().len() + 1)),
+                    );
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("Seed");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut main_menu_state.rng_seed)
+                            .hint_text("Random"),
+                    );
+                });
+
+                // A blank seed creates a unique colony, but any colony can be recreated by entering its seed.
+                let rng_seed = match main_menu_state.rng_seed.trim() {
+                    "" => Ok(None),
+                    rng_seed => rng_seed.parse::<u64>().map(Some),
+                };
+
+                if rng_seed.is_err() {
+                    ui.colored_label(egui::Color32::LIGHT_RED, "Seed must be a whole number.");
+                }
+
+                // Authored maps are read from a path so they're only offered natively.
+                if cfg!(not(target_arch = "wasm32")) {
+                    ui.horizontal(|ui| {
+                        ui.label("Map");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut main_menu_state.map_path)
+                                .hint_text("Generated"),
+                        );
+                    });
+                }
+
+                if ui
+                    .add_enabled(rng_seed.is_ok(), egui::Button::new("Sandbox Mode"))
+                    .clicked()
+                {
+                    let scenario = match main_menu_state.map_path.trim() {
+                        "" => Ok(None),
+                        map_path => Scenario::read(map_path).map(Some),
+                    };
+
+                    match scenario {
+                        Ok(Some(scenario)) => commands.insert_resource(scenario),
+                        Ok(None) => {}
+                        Err(error) => {
+                            colony_file_status.0 = Some(error);
+                            return;
+                        }
+                    }
+
+                    let name = main_menu_state.new_colony_name.trim();
+                    let name = if name.is_empty() {
+                        format!("Colony {}", save_slots.slots().len() + 1)
+                    } else {
+                        name.to_string()
+                    };
+
+                    save_slots.create(&name);
+                    main_menu_state.new_colony_name.clear();
+                    main_menu_state.rng_seed.clear();
+                    main_menu_state.map_path.clear();
+                    // Settings are only initialized, with a random seed, if they don't already exist.
+                    if let Ok(Some(rng_seed)) = rng_seed {
+                        commands.insert_resource(Settings {
+                            rng_seed,
+                            ..default()
+                        });
+                    }
                     next_app_state.set(AppState::FinishSetup);
                 }
+
+                let colony_file_path = main_menu_state
+                    .colony_file_path
+                    .get_or_insert_with(get_default_colony_file_path);
+                show_colony_file_import(ui, colony_file_path);
+
+                if let Some(message) = &colony_file_status.0 {
+                    ui.label(message);
+                }
             });
+
+            if save_slots.slots().is_empty() {
+                return;
+            }
+
+            ui.separator();
+            ui.label("Saved Colonies");
+
+            // Buttons mutate `save_slots` so defer acting on them until after the slots have been drawn.
+            let mut slot_to_load = None;
+            let mut slot_to_export = None;
+            let mut slot_to_duplicate = None;
+            let mut slot_to_delete = None;
+            let mut slot_to_rename = None;
+
+            egui::Grid::new("save_slots").striped(true).show(ui, |ui| {
+                ui.label("Name");
+                ui.label("Created");
+                ui.label("Day");
+                ui.label("Ants");
+                ui.end_row();
+
+                for slot in save_slots.slots() {
+                    match main_menu_state.renaming_slot.as_mut() {
+                        Some((id, name)) if *id == slot.id => {
+                            let response = ui.text_edit_singleline(name);
+
+                            if response.lost_focus() {
+                                slot_to_rename = Some((id.clone(), name.clone()));
+                            }
+                        }
+                        _ => {
+                            if ui
+                                .label(&slot.name)
+                                .on_hover_text("Double-click to rename")
+                                .double_clicked()
+                            {
+                                main_menu_state.renaming_slot =
+                                    Some((slot.id.clone(), slot.name.clone()));
+                            }
+                        }
+                    }
+
+                    ui.label(
+                        slot.created_at
+                            .with_timezone(&chrono::Local)
+                            .format("%Y-%m-%d %H:%M")
+                            .to_string(),
+                    );
+                    ui.label(slot.story_day.to_string());
+                    ui.label(slot.alive_ant_count.to_string());
+
+                    if ui.button("Load").clicked() {
+                        slot_to_load = Some(slot.id.clone());
+                    }
+
+                    if ui.button("Export").clicked() {
+                        slot_to_export = Some(slot.id.clone());
+                    }
+
+                    if ui.button("Duplicate").clicked() {
+                        slot_to_duplicate = Some(slot.id.clone());
+                    }
+
+                    if ui.button("Delete").clicked() {
+                        slot_to_delete = Some(slot.id.clone());
+                    }
+
+                    ui.end_row();
+                }
+            });
+
+            if let Some((id, name)) = slot_to_rename {
+                let name = name.trim();
+                if !name.is_empty() {
+                    save_slots.rename(&id, name);
+                }
+
+                main_menu_state.renaming_slot = None;
+            }
+
+            if let Some(id) = slot_to_export {
+                colony_file_status.0 = Some(save_slots.export(&id).unwrap_or_else(|error| error));
+            }
+
+            if let Some(id) = slot_to_duplicate {
+                save_slots.duplicate(&id);
+            }
+
+            if let Some(id) = slot_to_delete {
+                save_slots.delete(&id);
+            }
+
+            if let Some(id) = slot_to_load {
+                save_slots.activate(&id);
+                next_app_state.set(AppState::Loading);
+            }
         });
 }
@@ -7,8 +7,8 @@ use rendering::common::{
     visible_grid::{VisibleGrid, VisibleGridState},
 };
 use simulation::{
-    crater_simulation::crater::Crater, nest_simulation::nest::Nest, settings::Settings,
-    story_time::StoryTime,
+    common::element::FoodKind, crater_simulation::crater::Crater, nest_simulation::nest::Nest,
+    settings::Settings, story_time::StoryTime,
 };
 
 #[derive(Resource, Default, PartialEq, Copy, Clone, Debug)]
@@ -57,8 +57,18 @@ pub fn update_action_menu(
             ui.add_enabled_ui(!food_disabled, |ui| {
                 ui.selectable_value(
                     pointer_action.as_mut(),
-                    PointerAction::SpawnFood,
-                    "Place Food",
+                    PointerAction::SpawnFood(FoodKind::Seed),
+                    "Place Seeds",
+                );
+                ui.selectable_value(
+                    pointer_action.as_mut(),
+                    PointerAction::SpawnFood(FoodKind::Sugar),
+                    "Place Honeydew",
+                );
+                ui.selectable_value(
+                    pointer_action.as_mut(),
+                    PointerAction::SpawnFood(FoodKind::Protein),
+                    "Place Insects",
                 );
             });
 
@@ -67,6 +77,16 @@ pub fn update_action_menu(
                 PointerAction::SpawnDirt,
                 "Place Dirt",
             );
+            ui.selectable_value(
+                pointer_action.as_mut(),
+                PointerAction::SpawnRock,
+                "Place Rock",
+            );
+            ui.selectable_value(
+                pointer_action.as_mut(),
+                PointerAction::SpawnWater,
+                "Place Water",
+            );
             ui.selectable_value(
                 pointer_action.as_mut(),
                 PointerAction::DespawnElement,
@@ -85,6 +105,16 @@ pub fn update_action_menu(
             );
 
             ui.selectable_value(pointer_action.as_mut(), PointerAction::KillAnt, "Kill Ant");
+            ui.selectable_value(
+                pointer_action.as_mut(),
+                PointerAction::LandQueen,
+                "Land Queen",
+            );
+            ui.selectable_value(
+                pointer_action.as_mut(),
+                PointerAction::DesignateGranary,
+                "Designate Granary",
+            );
 
             ui.add_enabled_ui(!food_disabled, |ui| {
                 if ui.button("Breathe for Food").clicked() {
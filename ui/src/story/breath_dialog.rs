@@ -4,14 +4,14 @@ use bevy_egui::{
     egui::{self, Align2},
     EguiContexts,
 };
-use bevy_turborand::{DelegatedRng, GlobalRng};
+use bevy_turborand::{DelegatedRng, RngComponent};
 use simulation::{
     common::{
         ant::{hunger::Hunger, Dead},
-        element::{commands::ElementCommandsExt, Air, Element, Food},
-        grid::GridElements,
+        element::{Air, Food, FoodKind},
         position::Position,
     },
+    external_event::ExternalSimulationEvent,
     nest_simulation::nest::AtNest,
 };
 
@@ -30,9 +30,10 @@ pub fn update_breath_dialog(
     ant_query: Query<&Hunger, Without<Dead>>,
     food_query: Query<&Food>,
     air_query: Query<&Position, With<Air>>,
-    mut rng: ResMut<GlobalRng>,
-    grid_elements: GridElements<AtNest>,
-    mut commands: Commands,
+    // Not GlobalRng - the simulation is seeded and shouldn't be affected by when the user breathes.
+    mut rng: Local<RngComponent>,
+    // Food is spawned through an external event, rather than directly, so it's recorded for replays.
+    mut external_simulation_event_writer: EventWriter<ExternalSimulationEvent<AtNest>>,
     mut is_running: Local<bool>,
     mut is_open: Local<IsOpen>,
     mut timer: Local<f32>,
@@ -136,15 +137,15 @@ pub fn update_breath_dialog(
                                 spawn_positions.insert(*position);
                             }
 
+                            // Breathing brings in a mix of every kind of food.
                             for position in spawn_positions.iter() {
-                                if let Some(entity) = grid_elements.get_entity(*position) {
-                                    commands.replace_element(
-                                        *position,
-                                        Element::Food,
-                                        *entity,
-                                        AtNest,
-                                    );
-                                }
+                                let food_kind = *rng.sample(&FoodKind::all()).unwrap();
+
+                                external_simulation_event_writer.send(
+                                    ExternalSimulationEvent::SpawnFood(
+                                        *position, food_kind, AtNest,
+                                    ),
+                                );
                             }
                         }
                     });
@@ -3,32 +3,88 @@ use bevy_egui::{egui, EguiContexts};
 
 use simulation::{
     common::{
-        ant::{hunger::Hunger, AntRole, Dead},
-        element::Food,
+        ant::{age::Age, death::CauseOfDeath, hunger::Hunger, thirst::Thirst, Ant, AntRole, Dead},
+        element::{Food, FoodKind, Mold},
+        position::Position,
     },
-    nest_simulation::ant::birthing::Birthing,
+    nest_simulation::{
+        ant::{
+            birthing::Birthing,
+            midden::{get_colony_health, is_in_midden},
+        },
+        granary::{get_stored_food_days, is_in_any_granary, Granary},
+        nest::{AtNest, Nest},
+    },
+    settings::Settings,
     story_time::StoryTime,
 };
 
 pub fn update_info_window(
     mut contexts: EguiContexts,
-    ant_query: Query<(&AntRole, &Hunger, Option<&Birthing>), Without<Dead>>,
+    ant_query: Query<(&AntRole, &Hunger, Option<&Birthing>, Option<&Age>), Without<Dead>>,
+    thirst_query: Query<(&AntRole, &Thirst), Without<Dead>>,
+    dead_ant_query: Query<Option<&CauseOfDeath>, (With<Ant>, With<Dead>)>,
+    nest_corpse_query: Query<&Position, (With<Ant>, With<Dead>, With<AtNest>)>,
+    nest_query: Query<&Nest>,
     food_query: Query<&Food>,
+    mold_query: Query<&Mold>,
+    nest_food_query: Query<(&Position, Option<&FoodKind>), (With<Food>, With<AtNest>)>,
+    granary_query: Query<&Position, (With<Granary>, With<AtNest>)>,
     story_time: Res<StoryTime>,
+    settings: Res<Settings>,
 ) {
     let queen_ant = ant_query
         .iter()
-        .find(|(&role, _, _)| role == AntRole::Queen);
+        .find(|(&role, _, _, _)| role == AntRole::Queen);
     let queen_ant_hunger = queen_ant
-        .map(|(_, hunger, _)| hunger.value())
+        .map(|(_, hunger, _, _)| hunger.value())
         .unwrap_or(0.0);
     let queen_ant_birthing = queen_ant
-        .map(|(_, _, birthing_option)| birthing_option.map_or(0.0, |birthing| birthing.value()))
+        .map(|(_, _, birthing_option, _)| birthing_option.map_or(0.0, |birthing| birthing.value()))
+        .unwrap_or(0.0);
+    let queen_ant_age = queen_ant
+        .and_then(|(_, _, _, age_option)| age_option.map(|age| age.days()))
         .unwrap_or(0.0);
     let colony_average_hunger = ant_query
         .iter()
-        .fold(0.0, |acc, (_, hunger, _)| acc + hunger.value())
+        .fold(0.0, |acc, (_, hunger, _, _)| acc + hunger.value())
         / ant_query.iter().count() as f32;
+    let queen_ant_thirst = thirst_query
+        .iter()
+        .find(|(&role, _)| role == AntRole::Queen)
+        .map_or(0.0, |(_, thirst)| thirst.value());
+    let colony_average_thirst = thirst_query
+        .iter()
+        .fold(0.0, |acc, (_, thirst)| acc + thirst.value())
+        / thirst_query.iter().count() as f32;
+    let colony_average_age = ant_query.iter().fold(0.0, |acc, (_, _, _, age_option)| {
+        acc + age_option.map_or(0.0, |age| age.days())
+    }) / ant_query.iter().count() as f32;
+    let deaths_of = |cause: CauseOfDeath| {
+        dead_ant_query
+            .iter()
+            .filter(|&cause_of_death| cause_of_death == Some(&cause))
+            .count()
+    };
+    let uncollected_corpse_count = nest_query.get_single().map_or(0, |nest| {
+        nest_corpse_query
+            .iter()
+            .filter(|position| !is_in_midden(position, nest))
+            .count()
+    });
+    let colony_health = get_colony_health(uncollected_corpse_count);
+    let granary_positions = granary_query.iter().copied().collect::<Vec<_>>();
+    let stored_food_kinds = nest_food_query
+        .iter()
+        .filter(|(position, _)| is_in_any_granary(position, &granary_positions))
+        .map(|(_, food_kind)| food_kind.copied().unwrap_or_default())
+        .collect::<Vec<_>>();
+    let stored_food_nutrition = stored_food_kinds
+        .iter()
+        .map(|food_kind| food_kind.nutrition())
+        .sum::<f32>();
+    let stored_food_days =
+        get_stored_food_days(stored_food_nutrition, ant_query.iter().count(), &settings);
 
     egui::Window::new("Info")
         .default_pos(egui::Pos2::new(0.0, 0.0))
@@ -59,8 +115,36 @@ pub fn update_info_window(
                 "Colony Average Hunger: {:.0}%",
                 colony_average_hunger
             ));
+            ui.label(&format!(
+                "Colony Average Thirst: {:.0}%",
+                colony_average_thirst
+            ));
+            ui.label(&format!(
+                "Colony Average Age: {:.1} days",
+                colony_average_age
+            ));
+            ui.label(&format!(
+                "Corpses: {} (Old Age: {}, Starvation: {}, Dehydration: {}, Killed: {})",
+                dead_ant_query.iter().count(),
+                deaths_of(CauseOfDeath::OldAge),
+                deaths_of(CauseOfDeath::Starvation),
+                deaths_of(CauseOfDeath::Dehydration),
+                deaths_of(CauseOfDeath::Killed)
+            ));
+            ui.label(&format!(
+                "Colony Health: {:.0}% ({} corpses left in the nest)",
+                colony_health, uncollected_corpse_count
+            ));
             ui.label(&format!("Queen Hunger: {:.0}%", queen_ant_hunger));
+            ui.label(&format!("Queen Thirst: {:.0}%", queen_ant_thirst));
+            ui.label(&format!("Queen Age: {:.1} days", queen_ant_age));
             ui.label(&format!("Queen Birthing: {:.0}%", queen_ant_birthing));
             ui.label(&format!("Food: {}", food_query.iter().count()));
+            ui.label(&format!("Moldy Food: {}", mold_query.iter().count()));
+            ui.label(&format!(
+                "Stored Food: {} ({:.1} days)",
+                stored_food_kinds.len(),
+                stored_food_days
+            ));
         });
 }
@@ -5,16 +5,21 @@ use rendering::common::selection::SelectedEntity;
 
 use simulation::{
     common::{
-        ant::{hunger::Hunger, AntInventory, AntName, AntRole, Dead},
-        element::Element,
+        ant::{
+            age::Age,
+            caste::Caste,
+            death::{CauseOfDeath, Decay},
+            hunger::Hunger,
+            task::AntTask,
+            thirst::Thirst,
+            AntInventory, AntName, AntRole, Dead,
+        },
+        element::{Element, FoodKind, Freshness},
         pheromone::{Pheromone, PheromoneStrength},
         position::Position,
     },
     crater_simulation::crater::AtCrater,
-    nest_simulation::{
-        ant::{birthing::Birthing, sleep::Asleep},
-        nest::AtNest,
-    },
+    nest_simulation::{ant::birthing::Birthing, brood::BroodStage, nest::AtNest},
 };
 
 #[derive(Component, Default, PartialEq, Copy, Clone, Debug)]
@@ -25,14 +30,26 @@ pub fn update_selection_menu(
     primary_window_query: Query<&Window, With<PrimaryWindow>>,
     selected_ant_query: Query<(
         &Hunger,
+        Option<&Thirst>,
         &AntName,
         &AntRole,
         &AntInventory,
+        Option<&Caste>,
         Option<&Birthing>,
         Option<&Dead>,
-        Option<&Asleep>,
+        Option<&CauseOfDeath>,
+        Option<&Decay>,
+        Option<&Age>,
+        &AntTask,
+    )>,
+    selected_element_query: Query<(
+        &Element,
+        &Position,
+        Option<&Freshness>,
+        Option<&FoodKind>,
+        Option<&AtNest>,
+        Option<&AtCrater>,
     )>,
-    selected_element_query: Query<(&Element, &Position, Option<&AtNest>, Option<&AtCrater>)>,
     pheromone_query: Query<(
         &Position,
         &Pheromone,
@@ -40,7 +57,8 @@ pub fn update_selection_menu(
         Option<&AtNest>,
         Option<&AtCrater>,
     )>,
-    elements_query: Query<&Element>,
+    elements_query: Query<(&Element, Option<&FoodKind>)>,
+    brood_query: Query<&BroodStage>,
     selected_entity: Res<SelectedEntity>,
 ) {
     let window = primary_window_query.single();
@@ -62,12 +80,28 @@ pub fn update_selection_menu(
         .default_pos(egui::Pos2::new(0.0, window.height()))
         .resizable(false)
         .show(ctx, |ui| {
-            if let Ok((element, element_position, element_at_nest, element_at_crater)) =
-                selected_element
+            if let Ok((
+                element,
+                element_position,
+                freshness,
+                food_kind,
+                element_at_nest,
+                element_at_crater,
+            )) = selected_element
             {
                 ui.label("Element");
                 ui.label(&format!("Type: {:?}", element));
 
+                if *element == Element::Food {
+                    let food_kind = food_kind.copied().unwrap_or_default();
+                    ui.label(&format!("Food Kind: {:?}", food_kind));
+                    ui.label(&format!("Nutrition: {:.0}%", food_kind.nutrition() * 100.0));
+                }
+
+                if let Some(freshness) = freshness {
+                    ui.label(&format!("Freshness: {:.0}%", freshness.value()));
+                }
+
                 // TODO: This is weird because really the "Pheromone" is selected not necessarily the Element?
                 // TODO: This shows pheromones for unrelated inactive zone - want to match on zone
                 for (
@@ -92,30 +126,85 @@ pub fn update_selection_menu(
                         ));
                     }
                 }
-            } else if let Ok((hunger, name, ant_role, inventory, birthing, dead, asleep)) =
-                selected_ant
+            } else if let Ok((
+                hunger,
+                thirst,
+                name,
+                ant_role,
+                inventory,
+                caste,
+                birthing,
+                dead,
+                cause_of_death,
+                decay,
+                age,
+                ant_task,
+            )) = selected_ant
             {
                 ui.label("Ant");
                 ui.label(&format!("Name: {}", name.0));
                 ui.label(&format!("Role: {:?}", ant_role));
+
+                if let Some(caste) = caste {
+                    ui.label(&format!("Caste: {:?}", caste));
+                }
+
+                if let Some(age) = age {
+                    ui.label(&format!(
+                        "Age: {:.1} days ({:.0}% of lifespan)",
+                        age.days(),
+                        age.value()
+                    ));
+                }
+
                 ui.label(&format!("Hunger: {:.0}%", hunger.value()));
 
-                if let Some(element_entity) = inventory.0 {
-                    let element = elements_query.get(element_entity).unwrap();
-                    ui.label(&format!("Carrying: {:?}", element));
+                if let Some(thirst) = thirst {
+                    ui.label(&format!("Thirst: {:.0}%", thirst.value()));
+                }
+
+                if let Some(item_entity) = inventory.0 {
+                    if let Ok((element, food_kind)) = elements_query.get(item_entity) {
+                        if *element == Element::Food {
+                            let food_kind = food_kind.copied().unwrap_or_default();
+                            ui.label(&format!("Carrying: {:?} ({:?})", element, food_kind));
+                        } else {
+                            ui.label(&format!("Carrying: {:?}", element));
+                        }
+                    } else if let Ok(stage) = brood_query.get(item_entity) {
+                        ui.label(&format!("Carrying: {:?}", stage));
+                    } else {
+                        ui.label("Carrying: Corpse");
+                    }
                 }
 
                 if let Some(birthing) = birthing {
                     ui.label(&format!("Birthing: {:.0}%", birthing.value()));
                 }
 
-                if let Some(_) = asleep {
-                    ui.label(&format!("Sleeping"));
+                ui.label(&format!("Task: {:?}", ant_task.current()));
+
+                if let Some(interrupted_task) = ant_task.interrupted().last() {
+                    ui.label(&format!("Resumes: {:?}", interrupted_task));
                 }
 
+                ui.collapsing("Task History", |ui| {
+                    for transition in ant_task.history().iter().rev() {
+                        ui.label(&format!("{:?} -> {:?}", transition.from, transition.to));
+                    }
+                });
+
                 if let Some(_) = dead {
                     // TODO: Maybe have it say "Died at XXX"
                     ui.label("Dead");
+
+                    if let Some(cause_of_death) = cause_of_death {
+                        ui.label(&format!("Cause of Death: {:?}", cause_of_death));
+                    }
+
+                    if let Some(decay) = decay {
+                        ui.label(&format!("Decay: {:.0}%", decay.value()));
+                    }
                 }
             }
         });
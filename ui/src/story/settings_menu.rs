@@ -1,10 +1,16 @@
 use bevy::{prelude::*, window::PrimaryWindow};
 use bevy_egui::{egui, EguiContexts};
 
+use crate::colony_file::show_colony_file_import;
 use rendering::common::pheromone::PheromoneVisibility;
 use simulation::{
     app_state::AppState,
     common::ant::AntColor,
+    replay::ReplayRecorder,
+    save::{
+        export_active_colony, get_default_colony_file_path, take_imported_colony_file,
+        ColonyFileStatus, SaveSlots,
+    },
     settings::Settings,
     story_time::{
         StoryPlaybackState, StoryTime, TicksPerSecond, DEFAULT_TICKS_PER_SECOND,
@@ -22,8 +28,25 @@ pub fn update_settings_menu(
     mut pheromone_visibility: ResMut<PheromoneVisibility>,
     mut story_time: ResMut<StoryTime>,
     mut settings: ResMut<Settings>,
+    mut save_slots: ResMut<SaveSlots>,
+    mut colony_file_status: ResMut<ColonyFileStatus>,
+    replay_recorder: Option<Res<ReplayRecorder>>,
+    mut colony_file_path: Local<Option<String>>,
     mut ant_query: Query<&mut AntColor>,
+    mut commands: Commands,
 ) {
+    // Importing a colony file while a story is being told keeps the current colony and switches to the imported one.
+    if let Some(result) = take_imported_colony_file() {
+        match result.and_then(|colony_file| save_slots.import(&colony_file)) {
+            Ok(id) => {
+                colony_file_status.0 = None;
+                save_slots.switch_to_slot(&id);
+                next_app_state.set(AppState::Cleanup);
+            }
+            Err(error) => colony_file_status.0 = Some(error),
+        }
+    }
+
     let window = primary_window_query.single();
     let ctx = contexts.ctx_mut();
 
@@ -144,6 +167,51 @@ pub fn update_settings_menu(
                 }
             });
 
+            ui.label(format!("Seed: {}", settings.rng_seed))
+                .on_hover_text("Enter this seed when creating a colony to recreate this colony.");
+
+            if ui.button("Export Colony").clicked() {
+                commands.add(export_active_colony);
+            }
+
+            // Only colonies created this session are recorded because a loaded colony can't be reproduced from its seed.
+            if ui
+                .add_enabled(
+                    replay_recorder.is_some(),
+                    egui::Button::new("Export Replay"),
+                )
+                .on_disabled_hover_text(
+                    "Only colonies created since the app was opened can be replayed.",
+                )
+                .clicked()
+            {
+                if let Some(replay_recorder) = &replay_recorder {
+                    let name = save_slots
+                        .active_slot()
+                        .map_or("Colony".to_string(), |slot| slot.name.clone());
+
+                    colony_file_status.0 = Some(
+                        replay_recorder
+                            .export(&name, story_time.elapsed_ticks())
+                            .unwrap_or_else(|error| error),
+                    );
+                }
+            }
+
+            show_colony_file_import(
+                ui,
+                colony_file_path.get_or_insert_with(get_default_colony_file_path),
+            );
+
+            if let Some(message) = &colony_file_status.0 {
+                ui.label(message);
+            }
+
+            if ui.button("Exit to Main Menu").clicked() {
+                save_slots.exit_active_slot();
+                next_app_state.set(AppState::Cleanup);
+            }
+
             if ui.button("Reset Sandbox").clicked() {
                 next_app_state.set(AppState::Cleanup);
             }